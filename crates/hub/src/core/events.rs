@@ -16,3 +16,28 @@ pub struct SettingsRequested;
 
 #[derive(Clone, Debug)]
 pub struct FabSearchRequested;
+
+/// A control was pressed on a download manager item.
+#[derive(Clone, Debug)]
+pub struct DownloadActionRequested {
+    pub id: String,
+    pub action: DownloadAction,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DownloadAction {
+    Pause,
+    Resume,
+    Cancel,
+    /// Start this download before the one queued ahead of it.
+    MoveUp,
+    MoveDown,
+}
+
+/// The download manager's concurrency or bandwidth limit was changed.
+#[derive(Clone, Debug)]
+pub struct DownloadLimitsChanged {
+    pub max_concurrent: usize,
+    /// Per-download cap in bytes per second; 0 for unlimited.
+    pub max_bps_per_download: u64,
+}
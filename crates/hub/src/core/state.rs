@@ -29,8 +29,16 @@ pub struct UiState {
     pub show_create_project: bool,
     pub show_install_modal: bool,
     pub engine_prompt: Option<crate::core::types::EnginePrompt>,
+    pub module_picker: Option<crate::core::types::ModulePicker>,
+    pub install_check: Option<crate::core::types::InstallCheck>,
+    pub library_manager: Option<crate::core::types::LibraryManager>,
+    pub archive_import: Option<crate::core::types::ArchiveImport>,
     pub pending_engine_install: Option<String>,
+    /// Whether `pending_engine_install` replaces an installed build.
+    pub pending_engine_reinstall: bool,
     pub release_notes_modal: Option<crate::core::types::ReleaseNotesModal>,
+    /// Self-update of the hub, shown as a banner under the title bar.
+    pub hub_update: crate::core::types::HubUpdateState,
     /// True while the "src" engine is being compiled from a local source checkout.
     pub building_src: bool,
     /// Live cargo build progress for the source-build overlay.
@@ -58,8 +66,14 @@ impl UiState {
             show_create_project: false,
             show_install_modal: false,
             engine_prompt: None,
+            module_picker: None,
+            install_check: None,
+            library_manager: None,
+            archive_import: None,
             pending_engine_install: None,
+            pending_engine_reinstall: false,
             release_notes_modal: None,
+            hub_update: crate::core::types::HubUpdateState::Idle,
             building_src: false,
             build_progress: None,
             show_cloud_intro_modal: false,
@@ -82,6 +96,7 @@ pub struct InputEntities {
     pub create_project_name: Entity<ui::input::InputState>,
     pub create_project_description: Entity<ui::input::InputState>,
     pub plugin_search: Entity<ui::input::InputState>,
+    pub import_version: Entity<ui::input::InputState>,
 }
 
 impl InputEntities {
@@ -114,6 +129,8 @@ impl InputEntities {
             plugin_search: cx.new(|cx| {
                 ui::input::InputState::new(window, cx).placeholder("Search plugins\u{2026}")
             }),
+            import_version: cx
+                .new(|cx| ui::input::InputState::new(window, cx).placeholder("1.2.3")),
         }
     }
 
@@ -350,6 +367,9 @@ pub struct AppState {
     pub input: InputValues,
     pub clone_progress: Option<SharedCloneProgress>,
     pub clone_error: Option<String>,
+    /// Download queue id of the template clone currently running, if any.
+    pub cloning_download: Option<String>,
+    pub download_queue: crate::service::download_queue_service::DownloadQueue,
 
     pub git_fetch_statuses: Arc<Mutex<HashMap<String, GitFetchStatus>>>,
     pub(crate) git_repository_generations: HashMap<PathBuf, u64>,
@@ -458,6 +478,8 @@ impl AppState {
             input: InputValues::new(),
             clone_progress: None,
             clone_error: None,
+            cloning_download: None,
+            download_queue: crate::service::download_queue_service::DownloadQueue::load(),
             git_fetch_statuses: Arc::new(Mutex::new(HashMap::new())),
             git_repository_generations: HashMap::new(),
             is_fetching_updates: false,
@@ -12,6 +12,77 @@ pub struct ReleaseNotesModal {
     pub body: String,
 }
 
+// ── Engine Module Picker ──────────────────────────────────────────────────
+
+/// The module checklist shown before installing a release that ships in
+/// parts, or when changing the modules of an existing install.
+#[derive(Clone, Debug)]
+pub struct ModulePicker {
+    pub version: String,
+    /// `None` until the release has been fetched (for an existing install).
+    pub release: Option<crate::service::installer_service::GitHubRelease>,
+    /// Modules available on this platform, core first. Empty while loading.
+    pub modules: Vec<crate::service::installer_service::EngineModule>,
+    pub selected: Vec<String>,
+    /// The install being changed, or `None` for a new install.
+    pub install_path: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
+// ── Engine Install Check ──────────────────────────────────────────────────
+
+/// The "Verify / Repair" modal for one installed engine version.
+#[derive(Clone, Debug)]
+pub struct InstallCheck {
+    pub version: String,
+    pub install_path: PathBuf,
+    pub stage: InstallCheckStage,
+}
+
+#[derive(Clone, Debug)]
+pub enum InstallCheckStage {
+    Verifying,
+    Verified(crate::service::installer_service::InstallReport),
+    Repairing(Arc<Mutex<crate::service::installer_service::DownloadProgress>>),
+    /// The state of the install after a repair.
+    Repaired(crate::service::installer_service::InstallReport),
+    Failed(String),
+}
+
+// ── Engine Libraries ──────────────────────────────────────────────────────
+
+/// The "Libraries" modal listing the folders engines are installed into.
+/// With `moving` set it instead asks which library to move an install to.
+#[derive(Clone, Debug)]
+pub struct LibraryManager {
+    pub libraries: crate::service::engine_library_service::EngineLibraries,
+    pub moving: Option<LibraryMove>,
+    pub error: Option<String>,
+}
+
+/// An install being moved to another library.
+#[derive(Clone, Debug)]
+pub struct LibraryMove {
+    pub version: String,
+    pub install_path: PathBuf,
+    /// True once a library was picked and the files are being moved.
+    pub in_progress: bool,
+}
+
+// ── Engine Archive Import ─────────────────────────────────────────────────
+
+/// The "Import engine archive" modal: unpacking the picked archive, then
+/// asking the user to confirm the version it's installed as.
+#[derive(Clone, Debug)]
+pub struct ArchiveImport {
+    pub archive: PathBuf,
+    /// `None` while the archive is being unpacked.
+    pub pending: Option<crate::service::installer_service::PendingImport>,
+    /// True once the version was confirmed and the install is being written.
+    pub installing: bool,
+    pub error: Option<String>,
+}
+
 // ── Engine Prompt ─────────────────────────────────────────────────────────
 
 /// A pending prompt asking the user whether to auto-install a missing engine
@@ -21,6 +92,46 @@ pub struct EnginePrompt {
     pub project_name: String,
     pub project_path: String,
     pub required: String,
+    /// The engine is installed, but not the build the project's lock records.
+    pub reinstall: bool,
+}
+
+// ── Hub Self-Update ───────────────────────────────────────────────────────
+
+pub use crate::service::hub_update_service::{HubUpdateInfo, HubUpdateProgress};
+pub use crate::service::installer_service::DownloadFailure;
+
+/// Where the hub is in updating itself; drives the update banner.
+#[derive(Clone, Debug, Default)]
+pub enum HubUpdateState {
+    /// No update known (none found yet, up to date, skipped or dismissed).
+    #[default]
+    Idle,
+    /// A newer hub is available and waiting for the user to opt in.
+    Available(HubUpdateInfo),
+    /// The update is being downloaded and applied.
+    Installing {
+        info: HubUpdateInfo,
+        progress: Arc<Mutex<HubUpdateProgress>>,
+        /// Restart into the new build once it is installed.
+        relaunch: bool,
+    },
+    /// Installed; takes effect the next time the hub starts.
+    Staged(HubUpdateInfo),
+    Failed { info: HubUpdateInfo, message: String },
+}
+
+impl HubUpdateState {
+    /// The update this state refers to, if any.
+    pub fn info(&self) -> Option<&HubUpdateInfo> {
+        match self {
+            Self::Idle => None,
+            Self::Available(info)
+            | Self::Installing { info, .. }
+            | Self::Staged(info)
+            | Self::Failed { info, .. } => Some(info),
+        }
+    }
 }
 
 // ── Navigation ────────────────────────────────────────────────────────────
@@ -336,18 +447,27 @@ pub struct PendingInvite {
 #[derive(Clone, Debug, PartialEq)]
 pub enum DownloadKind {
     EngineVersion { version: String },
+    EngineModules { version: String },
     TemplateClone { name: String },
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum DownloadStatus {
+    /// Waiting in the download queue for a free slot.
+    Queued,
     Downloading {
         bytes_downloaded: u64,
         total_bytes: u64,
         speed_bps: u64,
     },
+    /// Stopped by the user; the partial file is kept for resuming.
+    Paused {
+        bytes_downloaded: u64,
+        total_bytes: u64,
+    },
     Complete,
-    Failed(String),
+    Cancelled,
+    Failed(DownloadFailure),
 }
 
 #[derive(Clone, Debug)]
@@ -362,6 +482,7 @@ impl DownloadItem {
     pub fn label(&self) -> String {
         match &self.kind {
             DownloadKind::EngineVersion { version } => format!("Engine v{}", version),
+            DownloadKind::EngineModules { version } => format!("Engine v{} modules", version),
             DownloadKind::TemplateClone { name } => format!("Template: {}", name),
         }
     }
@@ -372,6 +493,10 @@ impl DownloadItem {
                 bytes_downloaded,
                 total_bytes,
                 ..
+            }
+            | DownloadStatus::Paused {
+                bytes_downloaded,
+                total_bytes,
             } => {
                 if *total_bytes > 0 {
                     (*bytes_downloaded as f32 / *total_bytes as f32).clamp(0.0, 1.0)
@@ -389,9 +514,15 @@ impl DownloadItem {
                 bytes_downloaded,
                 total_bytes,
                 ..
+            }
+            | DownloadStatus::Paused {
+                bytes_downloaded,
+                total_bytes,
             } => format!("{} / {}", format_bytes(*bytes_downloaded), format_bytes(*total_bytes)),
+            DownloadStatus::Queued => "Queued".to_string(),
             DownloadStatus::Complete => "Complete".to_string(),
-            DownloadStatus::Failed(e) => e.clone(),
+            DownloadStatus::Cancelled => "Cancelled".to_string(),
+            DownloadStatus::Failed(e) => e.to_string(),
         }
     }
 
@@ -440,16 +571,24 @@ pub struct VersionState {
     pub fetching: bool,
     /// True while an additional page of releases is being fetched.
     pub loading_more: bool,
-    /// True if any source repo still has more pages to load.
+    /// True if any release source still has more pages to load.
     pub has_more: bool,
+    /// Every configured release channel, in menu order.
+    pub channels: Vec<crate::service::installer_service::ReleaseChannel>,
     /// Currently enabled release channels.
     pub selected_channels: Vec<crate::service::installer_service::ReleaseChannel>,
-    /// Per-repo pagination/loading state backing `available_releases`.
+    /// Per-source pagination/loading state backing `available_releases`.
     pub channel_sources: Vec<crate::service::installer_service::ChannelSource>,
+    /// Progress of running engine downloads, keyed by download manager item id.
+    pub downloads: std::collections::HashMap<
+        String,
+        std::sync::Arc<parking_lot::Mutex<crate::service::installer_service::DownloadProgress>>,
+    >,
 }
 
 impl Default for VersionState {
     fn default() -> Self {
+        let sources = crate::service::release_source_service::ReleaseSourcesConfig::load();
         Self {
             installed: Vec::new(),
             available_releases: Vec::new(),
@@ -457,12 +596,10 @@ impl Default for VersionState {
             fetching: false,
             loading_more: false,
             has_more: true,
-            selected_channels: crate::service::installer_service::ReleaseChannel::ALL
-                .to_vec()
-                .into_iter()
-                .filter(|c| *c != crate::service::installer_service::ReleaseChannel::Nightly)
-                .collect(),
-            channel_sources: crate::service::installer_service::default_channel_sources(),
+            selected_channels: sources.default_channels(),
+            channel_sources: sources.channel_sources(),
+            channels: sources.channels,
+            downloads: std::collections::HashMap::new(),
         }
     }
 }
@@ -8,6 +8,9 @@ mod window;
 pub use core::events::*;
 pub use core::types::*;
 pub use screen::EntryScreen;
+pub use service::hub_update_service::{set_hub_updater, HubUpdater};
+pub use service::{installer_service, project_service};
+pub use util::path_helpers::recent_projects_path;
 pub use window::EntryWindow;
 
 pub use engine_state::{EngineContext, WindowContext, WindowRequest};
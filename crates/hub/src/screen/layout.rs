@@ -35,6 +35,26 @@ pub fn render_layout(
         .into_any_element();
     }
 
+    if screen.state.ui.install_check.is_some() {
+        return crate::screen::views::install_check::render_install_check(screen, cx)
+            .into_any_element();
+    }
+
+    if screen.state.ui.archive_import.is_some() {
+        return crate::screen::views::archive_import::render_archive_import(screen, cx)
+            .into_any_element();
+    }
+
+    if screen.state.ui.library_manager.is_some() {
+        return crate::screen::views::library_manager::render_library_manager(screen, cx)
+            .into_any_element();
+    }
+
+    if screen.state.ui.module_picker.is_some() {
+        return crate::screen::views::module_picker::render_module_picker(screen, cx)
+            .into_any_element();
+    }
+
     if screen.state.ui.release_notes_modal.is_some() {
         return crate::screen::views::release_notes_modal::render_release_notes_modal(
             screen, window, cx,
@@ -119,6 +139,7 @@ pub fn render_layout(
                                     .content(move |_, _| theme_picker.clone()),
                                 )
                         }))
+                        .child(crate::screen::views::render_hub_update_banner(screen, cx))
                         .child(match view {
                             EntryScreenView::Recent => {
                                 let bounds = window.viewport_size();
@@ -16,6 +16,7 @@ use crate::screen::views::project_settings::ProjectSettingsTab;
 use crate::service::auth_service::AuthService;
 use crate::service::cloud_service::CloudService;
 use crate::service::dependency_service::DependencyService;
+use crate::service::download_queue_service::{QueuedDownload, QueuedJob};
 use crate::service::git_service::GitService;
 use crate::service::plugin_service::PluginService;
 use crate::service::project_service::ProjectService;
@@ -86,6 +87,39 @@ fn git_fetch_paths(
         .collect()
 }
 
+/// Where an engine download queued without a destination installs to.
+fn engine_install_path(release: &crate::service::installer_service::GitHubRelease) -> PathBuf {
+    crate::service::installer_service::default_install_path()
+        .join(release.tag_name.trim_start_matches('v'))
+}
+
+/// Download manager item for a restored or newly queued download.
+fn queued_download_item(entry: &QueuedDownload) -> DownloadItem {
+    DownloadItem {
+        id: entry.id.clone(),
+        kind: match &entry.job {
+            QueuedJob::EngineVersion { release, .. } => DownloadKind::EngineVersion {
+                version: release.tag_name.clone(),
+            },
+            QueuedJob::EngineModules { release, .. } => DownloadKind::EngineModules {
+                version: release.tag_name.clone(),
+            },
+            QueuedJob::TemplateClone { name, .. } => {
+                DownloadKind::TemplateClone { name: name.clone() }
+            }
+        },
+        status: if entry.paused {
+            DownloadStatus::Paused {
+                bytes_downloaded: 0,
+                total_bytes: 0,
+            }
+        } else {
+            DownloadStatus::Queued
+        },
+        started_at: std::time::Instant::now(),
+    }
+}
+
 pub struct EntryScreen {
     pub state: AppState,
     pub inputs: InputEntities,
@@ -155,6 +189,32 @@ impl EntryScreen {
 
         inputs.subscribe_all(self_entity.downgrade(), cx);
 
+        let download_manager = state.download_manager_view.clone();
+        let queue = state.download_queue.clone();
+        download_manager.update(cx, |view, _| {
+            view.max_concurrent = queue.max_concurrent;
+            view.max_bps_per_download = queue.max_bps_per_download;
+            for entry in &queue.entries {
+                view.add_item(queued_download_item(entry));
+            }
+        });
+        cx.subscribe(
+            &download_manager,
+            |this, _, event: &DownloadActionRequested, cx| {
+                this.handle_download_action(event, cx);
+                cx.notify();
+            },
+        )
+        .detach();
+        cx.subscribe(
+            &download_manager,
+            |this, _, event: &DownloadLimitsChanged, cx| {
+                this.handle_download_limits(event, cx);
+                cx.notify();
+            },
+        )
+        .detach();
+
         let profile_dropdown = state.auth.profile_dropdown.clone();
         cx.subscribe(
             &profile_dropdown,
@@ -175,6 +235,8 @@ impl EntryScreen {
         };
         this.state.git_auto_fetch_task = Some(Self::start_git_auto_fetch_task(cx));
         this.load_thumbnails(cx);
+        this.check_for_hub_update(cx);
+        this.pump_download_queue(cx);
         if this.state.ui.show_onboarding {
             this.refresh_plugin_registry(cx);
         }
@@ -429,145 +491,152 @@ impl EntryScreen {
         if repo_url.is_empty() {
             return;
         }
+        self.pick_clone_target(repo_url, None, cx);
+    }
+
+    pub(crate) fn clone_template(&mut self, template: Template, cx: &mut Context<Self>) {
+        self.pick_clone_target(template.repo_url, Some(template.name), cx);
+    }
+
+    /// Ask for a parent folder and clone `repo_url` into a new directory in
+    /// it. Template clones go through the download queue; plain clones start
+    /// straight away.
+    fn pick_clone_target(
+        &mut self,
+        repo_url: String,
+        template: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
+        self.state.clone_error = None;
+        cx.spawn(async move |entity, cx| {
+            let Some(folder) = rfd::AsyncFileDialog::new().pick_folder().await else {
+                return;
+            };
+            let parent = folder.path().to_path_buf();
+            let target = parent.join(
+                repo_url
+                    .trim_end_matches(".git")
+                    .split('/')
+                    .last()
+                    .unwrap_or("repo"),
+            );
+            let _ = cx.update(|cx| {
+                let _ = entity.update(cx, |this, cx| {
+                    if target.exists() {
+                        this.state.clone_error =
+                            Some(format!("Directory already exists: {}", target.display()));
+                    } else if let Some(name) = template {
+                        this.enqueue_download(
+                            format!("template-{}", name),
+                            QueuedJob::TemplateClone {
+                                name,
+                                repo_url,
+                                target,
+                            },
+                            cx,
+                        );
+                    } else {
+                        this.run_clone(repo_url, target, None, cx);
+                    }
+                    cx.notify();
+                });
+            });
+        })
+        .detach();
+    }
+
+    /// Clone `repo_url` into `target`, reporting to download manager item
+    /// `dl_id` when the clone came from the download queue.
+    fn run_clone(
+        &mut self,
+        repo_url: String,
+        target: PathBuf,
+        dl_id: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
         let recent_projects_path = self.state.recent_projects_path.clone();
         self.state.clone_error = None;
+        self.state.cloning_download = dl_id.clone();
         cx.spawn(async move |entity, cx| {
-            if let Some(folder) = rfd::AsyncFileDialog::new().pick_folder().await {
-                let parent = folder.path().to_path_buf();
-                let target = parent.join(
-                    repo_url
-                        .trim_end_matches(".git")
-                        .split('/')
-                        .last()
-                        .unwrap_or("repo"),
-                );
-                if target.exists() {
-                    let err = format!("Directory already exists: {}", target.display());
-                    let _ = cx.update(|cx| {
-                        let _ = entity.update(cx, |this, cx| {
-                            this.state.clone_error = Some(err);
-                            cx.notify();
-                        });
-                    });
-                    return;
-                }
-                let progress = Arc::new(Mutex::new(CloneProgress {
-                    current: 0,
-                    total: 0,
-                    message: "Starting clone...".to_string(),
-                    completed: false,
-                    error: None,
-                    cancelled: false,
-                }));
-                let p = progress.clone();
-                let url = repo_url.clone();
-                let t = target.clone();
-                let _ = cx
-                    .background_executor()
-                    .spawn(async move { GitService::clone_repository(url, t, p) })
-                    .await;
-                let has_error = progress.lock().error.is_some();
-                if has_error {
-                    let err = progress.lock().error.clone().unwrap_or_default();
-                    let _ = cx.update(|cx| {
-                        let _ = entity.update(cx, |this, cx| {
-                            this.state.clone_progress = None;
-                            this.state.clone_error = Some(err.clone());
-                            this.state.download_manager_view.update(cx, |view, cx| {
-                                for item in &mut view.items {
-                                    if matches!(
-                                        item.kind,
-                                        DownloadKind::TemplateClone { .. }
-                                    ) && matches!(
-                                        item.status,
-                                        DownloadStatus::Downloading { .. }
-                                    ) {
-                                        item.status =
-                                            DownloadStatus::Failed(err.clone());
-                                    }
-                                }
-                                cx.notify();
-                            });
-                            cx.notify();
-                        });
-                    });
-                    return;
-                }
-                let show_upstream = ProjectService::is_git_repo(&target)
-                    && !GitService::has_origin_remote(&target);
+            let progress = Arc::new(Mutex::new(CloneProgress {
+                current: 0,
+                total: 0,
+                message: "Starting clone...".to_string(),
+                completed: false,
+                error: None,
+                cancelled: false,
+            }));
+            let p = progress.clone();
+            let url = repo_url.clone();
+            let t = target.clone();
+            let _ = cx
+                .background_executor()
+                .spawn(async move { GitService::clone_repository(url, t, p) })
+                .await;
+            let error = progress.lock().error.clone();
+            if let Some(err) = error {
                 let _ = cx.update(|cx| {
                     let _ = entity.update(cx, |this, cx| {
                         this.state.clone_progress = None;
-                        this.state.clone_error = None;
-                        this.state.input.new_project_path = Some(target.clone());
-                        this.state.download_manager_view.update(cx, |view, cx| {
-                            for item in &mut view.items {
-                                if matches!(
-                                    item.kind,
-                                    DownloadKind::TemplateClone { .. }
-                                ) && matches!(
-                                    item.status,
-                                    DownloadStatus::Downloading { .. }
-                                ) {
-                                    item.status = DownloadStatus::Complete;
-                                }
-                            }
-                            cx.notify();
-                        });
-                        if show_upstream {
-                            let n = target
-                                .file_name()
-                                .map(|n| n.to_string_lossy().to_string())
-                                .unwrap_or_default();
-                            this.state.ui.show_git_upstream_prompt = Some((target.clone(), n));
-                        } else {
-                            let ps = target.to_string_lossy().to_string();
-                            let n = target
-                                .file_name()
-                                .map(|n| n.to_string_lossy().to_string())
-                                .unwrap_or_default();
-                            this.state.recent_projects.add_or_update(
-                                crate::service::project_service::RecentProject {
-                                    name: n,
-                                    path: ps,
-                                    last_opened: Some(
-                                        chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
-                                    ),
-                                    is_git: true,
-                                },
-                            );
-                            this.state.recent_projects.save(&recent_projects_path);
-                            cx.emit(ProjectSelected { path: target });
+                        this.state.clone_error = Some(err.clone());
+                        if let Some(id) = &dl_id {
+                            this.state.download_manager_view.update(cx, |view, cx| {
+                                view.fail(id, err.clone());
+                                cx.notify();
+                            });
+                            this.finish_queued_download(id, cx);
                         }
                         cx.notify();
                     });
                 });
+                return;
             }
+            let show_upstream = ProjectService::is_git_repo(&target)
+                && !GitService::has_origin_remote(&target);
+            let _ = cx.update(|cx| {
+                let _ = entity.update(cx, |this, cx| {
+                    this.state.clone_progress = None;
+                    this.state.clone_error = None;
+                    this.state.input.new_project_path = Some(target.clone());
+                    if let Some(id) = &dl_id {
+                        this.state.download_manager_view.update(cx, |view, cx| {
+                            view.complete(id);
+                            cx.notify();
+                        });
+                        this.finish_queued_download(id, cx);
+                    }
+                    if show_upstream {
+                        let n = target
+                            .file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        this.state.ui.show_git_upstream_prompt = Some((target.clone(), n));
+                    } else {
+                        let ps = target.to_string_lossy().to_string();
+                        let n = target
+                            .file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        this.state.recent_projects.add_or_update(
+                            crate::service::project_service::RecentProject {
+                                name: n,
+                                path: ps,
+                                last_opened: Some(
+                                    chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+                                ),
+                                is_git: true,
+                            },
+                        );
+                        this.state.recent_projects.save(&recent_projects_path);
+                        cx.emit(ProjectSelected { path: target });
+                    }
+                    cx.notify();
+                });
+            });
         })
         .detach();
     }
 
-    pub(crate) fn clone_template(&mut self, template: Template, cx: &mut Context<Self>) {
-        let dl_id = format!("template-{}", template.name);
-        self.state.download_manager_view.update(cx, |view, cx| {
-            view.add_item(DownloadItem {
-                id: dl_id,
-                kind: DownloadKind::TemplateClone {
-                    name: template.name.clone(),
-                },
-                status: DownloadStatus::Downloading {
-                    bytes_downloaded: 0,
-                    total_bytes: 0,
-                    speed_bps: 0,
-                },
-                started_at: std::time::Instant::now(),
-            });
-            cx.notify();
-        });
-        cx.notify();
-        self.clone_git_repo(Some(template.repo_url), cx);
-    }
-
     pub(crate) fn setup_git_upstream(&mut self, cx: &mut Context<Self>) {
         let (path, _) = match &self.state.ui.show_git_upstream_prompt.take() {
             Some(pair) => pair.clone(),
@@ -610,10 +679,12 @@ impl EntryScreen {
         .detach();
     }
 
-    /// Launch a project with the engine declared in its `Pulsar.toml`.
+    /// Launch a project with the engine pinned in its `Pulsar.lock`, or else
+    /// the newest installed engine satisfying its `Pulsar.toml`, which is then
+    /// locked.
     ///
-    /// If the declared engine is installed, it is launched with the project
-    /// path as a CLI argument (instant open). If it's required but missing, an
+    /// If that engine is installed, it is launched with the project path as a
+    /// CLI argument (instant open). If it's required but missing, an
     /// auto-install prompt is shown instead. With no declared requirement it
     /// falls back to the newest installed engine, or emits `ProjectSelected`
     /// for the embedder to handle when none is installed.
@@ -634,9 +705,9 @@ impl EntryScreen {
             .recent_projects
             .save(&self.state.recent_projects_path);
 
-        let required = self.required_engine_for_project(&path);
-        match required {
-            Some(req) if req.eq_ignore_ascii_case("src") => {
+        use crate::service::project_service::EngineResolution;
+        match ProjectService::resolve_project_engine(&path, &self.state.versions.installed) {
+            EngineResolution::Src => {
                 // Projects pinned to `src` use a local engine source checkout.
                 if let Some(src) = self.state.src_engine_path.clone() {
                     self.launch_src_project(src, &path, cx);
@@ -646,28 +717,20 @@ impl EntryScreen {
                         path.display()
                     );
                 }
-                return;
             }
-            Some(req) if self.engine_requirement_satisfied(&req) => {
-                if let Some(dir) = self.installed_engine_dir_satisfying(&req) {
-                    self.launch_project_with_engine(dir, &path);
-                    cx.emit(ProjectSelected { path });
-                    return;
-                }
+            EngineResolution::Installed(engine) => {
+                self.launch_project_with_engine(engine.metadata.install_path, &path);
+                cx.emit(ProjectSelected { path });
             }
-            Some(req) => {
-                // Declared but not installed → ask to auto-install.
-                self.request_engine_install(path, req, cx);
-                return;
+            EngineResolution::Missing(required) => {
+                // Locked or declared but not installed → ask to auto-install.
+                self.request_engine_install(path, required, cx);
             }
-            None => {}
-        }
-
-        // No usable engine requirement → open with the newest installed engine if any.
-        if let Some(dir) = self.newest_installed_engine_dir() {
-            self.launch_project_with_engine(dir, &path);
+            EngineResolution::Mismatch(engine) => {
+                self.request_engine_reinstall(path, &engine.metadata.version, cx);
+            }
+            EngineResolution::NoEngine => cx.emit(ProjectSelected { path }),
         }
-        cx.emit(ProjectSelected { path });
     }
 
     /// Compile the engine from the local `src` checkout in the background, then
@@ -805,29 +868,6 @@ impl EntryScreen {
         }
     }
 
-    /// The newest installed engine dir that satisfies `required`.
-    fn installed_engine_dir_satisfying(&self, required: &str) -> Option<PathBuf> {
-        use crate::service::installer_service as svc;
-        self.state
-            .versions
-            .installed
-            .iter()
-            .filter(|v| svc::installed_satisfies(&v.metadata.version, required))
-            .max_by(|a, b| {
-                svc::parse_version(&a.metadata.version).cmp(&svc::parse_version(&b.metadata.version))
-            })
-            .map(|v| v.metadata.install_path.clone())
-    }
-
-    /// The newest installed engine dir, if any.
-    fn newest_installed_engine_dir(&self) -> Option<PathBuf> {
-        self.state
-            .versions
-            .installed
-            .first()
-            .map(|v| v.metadata.install_path.clone())
-    }
-
     pub(crate) fn remove_recent_project(&mut self, path: &str, cx: &mut Context<Self>) {
         self.state.recent_projects.remove(path);
         self.state
@@ -1423,21 +1463,26 @@ impl EntryScreen {
                 }
             };
             let repo_tag = repo.clone();
-            let release: Option<(String, Option<String>)> = cx
+            let release = cx
                 .background_executor()
                 .spawn(async move { PluginService::fetch_latest_release(&owner, &repo) })
-                .await
-                .ok()
-                .flatten();
-            let Some((tag, binary_url_opt)) = release else {
-                let _ = cx.update(|cx| {
-                    entity.update(cx, |this, cx| {
-                        this.state.plugin_install_phase =
-                            Some(PluginInstallPhase::Error("No releases found".to_string()));
-                        cx.notify();
-                    })
-                });
-                return;
+                .await;
+            let (tag, binary_url_opt) = match release {
+                Ok(Some(release)) => release,
+                failed => {
+                    let message = match failed {
+                        Err(e) => e,
+                        _ => "No releases found".to_string(),
+                    };
+                    let _ = cx.update(|cx| {
+                        entity.update(cx, |this, cx| {
+                            this.state.plugin_install_phase =
+                                Some(PluginInstallPhase::Error(message));
+                            cx.notify();
+                        })
+                    });
+                    return;
+                }
             };
             let ext = native_plugin_ext();
             if let Some(binary_url) = binary_url_opt {
@@ -1676,12 +1721,13 @@ impl EntryScreen {
 
     // ── Version Management ──────────────────────────────────────────────
 
-    /// The repos backing the currently selected channels (deduped).
-    fn needed_repos(&self) -> Vec<&'static str> {
-        let mut out: Vec<&'static str> = Vec::new();
+    /// Ids of the release sources backing the currently selected channels
+    /// (deduped).
+    fn needed_sources(&self) -> Vec<String> {
+        let mut out: Vec<String> = Vec::new();
         for channel in &self.state.versions.selected_channels {
-            if !out.contains(&channel.repo()) {
-                out.push(channel.repo());
+            if !out.contains(&channel.source) {
+                out.push(channel.source.clone());
             }
         }
         out
@@ -1698,7 +1744,7 @@ impl EntryScreen {
             for release in &src.fetched {
                 let included = channels
                     .iter()
-                    .any(|ch| ch.repo() == src.repo && ch.includes(release));
+                    .any(|ch| ch.source == src.id() && ch.includes(release));
                 if included && seen.insert(release.tag_name.clone()) {
                     out.push(release.clone());
                 }
@@ -1710,67 +1756,79 @@ impl EntryScreen {
 
     /// Recompute `has_more` from the sources that back the selected channels.
     fn recompute_has_more(&mut self) {
-        let needed = self.needed_repos();
+        let needed = self.needed_sources();
         self.state.versions.has_more = self
             .state
             .versions
             .channel_sources
             .iter()
-            .any(|s| needed.contains(&s.repo) && s.has_more);
+            .any(|s| needed.iter().any(|id| id == s.id()) && s.has_more);
     }
 
     pub(crate) fn refresh_versions(&mut self, cx: &mut Context<Self>) {
         self.state.versions.installed = self.installed_versions();
         self.state.versions.fetching = true;
         self.state.versions.loading_more = false;
+        let needed = self.needed_sources();
         for src in &mut self.state.versions.channel_sources {
             src.page = 0;
             src.has_more = true;
             src.loading = false;
             src.error = None;
             src.fetched.clear();
+            // Show the last fetched list right away; the first page is
+            // replaced once it has been revalidated.
+            if needed.iter().any(|id| id == src.id()) {
+                if let Some(cached) = src.source.cached_page(1) {
+                    src.fetched = cached.releases;
+                }
+            }
         }
         self.rebuild_available_releases();
         cx.notify();
 
-        let repos = self.needed_repos();
-        use crate::service::installer_service as svc;
+        let sources: Vec<_> = self
+            .state
+            .versions
+            .channel_sources
+            .iter()
+            .filter(|s| needed.iter().any(|id| id == s.id()))
+            .map(|s| s.source.clone())
+            .collect();
         cx.spawn(async move |entity, cx| {
             let results = cx
                 .background_executor()
                 .spawn(async move {
-                    repos
+                    sources
                         .iter()
-                        .map(|repo| (*repo, svc::fetch_repo_releases_blocking(repo, 1)))
+                        .map(|source| (source.id().to_string(), source.fetch_page(1)))
                         .collect::<Vec<_>>()
                 })
                 .await;
             let _ = cx.update(|cx| {
                 entity.update(cx, |this, cx| {
                     this.state.versions.fetching = false;
-                    for (repo, result) in results {
+                    for (id, result) in results {
                         let Some(src) = this
                             .state
                             .versions
                             .channel_sources
                             .iter_mut()
-                            .find(|s| s.repo == repo)
+                            .find(|s| s.id() == id)
                         else {
                             continue;
                         };
                         match result {
                             Ok(list) => {
-                                let has_more = list.len()
-                                    == svc::RELEASES_PER_PAGE as usize;
-                                let mut seen: std::collections::HashSet<String> =
-                                    src.fetched.iter().map(|r| r.tag_name.clone()).collect();
-                                for release in list {
+                                src.fetched.clear();
+                                let mut seen = std::collections::HashSet::new();
+                                for release in list.releases {
                                     if seen.insert(release.tag_name.clone()) {
                                         src.fetched.push(release);
                                     }
                                 }
                                 src.page = 1;
-                                src.has_more = has_more;
+                                src.has_more = list.has_more;
                                 src.loading = false;
                             }
                             Err(e) => {
@@ -1794,14 +1852,14 @@ impl EntryScreen {
         if self.state.versions.fetching || self.state.versions.loading_more {
             return;
         }
-        let needed = self.needed_repos();
-        let targets: Vec<(&'static str, u32)> = self
+        let needed = self.needed_sources();
+        let targets: Vec<_> = self
             .state
             .versions
             .channel_sources
             .iter()
-            .filter(|s| needed.contains(&s.repo) && s.has_more)
-            .map(|s| (s.repo, s.page + 1))
+            .filter(|s| needed.iter().any(|id| id == s.id()) && s.has_more)
+            .map(|s| (s.source.clone(), s.page + 1))
             .collect();
         if targets.is_empty() {
             self.state.versions.has_more = false;
@@ -1810,49 +1868,47 @@ impl EntryScreen {
 
         self.state.versions.loading_more = true;
         for src in &mut self.state.versions.channel_sources {
-            if needed.contains(&src.repo) {
+            if needed.iter().any(|id| id == src.id()) {
                 src.loading = true;
             }
         }
         cx.notify();
 
-        use crate::service::installer_service as svc;
         cx.spawn(async move |entity, cx| {
             let results = cx
                 .background_executor()
                 .spawn(async move {
                     targets
                         .iter()
-                        .map(|(repo, page)| (*repo, svc::fetch_repo_releases_blocking(repo, *page)))
+                        .map(|(source, page)| (source.id().to_string(), source.fetch_page(*page)))
                         .collect::<Vec<_>>()
                 })
                 .await;
             let _ = cx.update(|cx| {
                 entity.update(cx, |this, cx| {
                     this.state.versions.loading_more = false;
-                    for (repo, result) in results {
+                    for (id, result) in results {
                         let Some(src) = this
                             .state
                             .versions
                             .channel_sources
                             .iter_mut()
-                            .find(|s| s.repo == repo)
+                            .find(|s| s.id() == id)
                         else {
                             continue;
                         };
                         match result {
                             Ok(list) => {
                                 let page = src.page + 1;
-                                let has_more = list.len() == svc::RELEASES_PER_PAGE as usize;
                                 let mut seen: std::collections::HashSet<String> =
                                     src.fetched.iter().map(|r| r.tag_name.clone()).collect();
-                                for release in list {
+                                for release in list.releases {
                                     if seen.insert(release.tag_name.clone()) {
                                         src.fetched.push(release);
                                     }
                                 }
                                 src.page = page;
-                                src.has_more = has_more;
+                                src.has_more = list.has_more;
                                 src.loading = false;
                             }
                             Err(e) => {
@@ -1888,63 +1944,63 @@ impl EntryScreen {
         self.rebuild_available_releases();
         self.recompute_has_more();
 
-        // If a newly-enabled channel's source repo was never fetched (e.g.
+        // If a newly-enabled channel's source was never fetched (e.g.
         // Nightly, which is off by default), go fetch its first page now.
-        let needed = self.needed_repos();
-        let to_fetch: Vec<&'static str> = self
+        let needed = self.needed_sources();
+        let to_fetch: Vec<_> = self
             .state
             .versions
             .channel_sources
             .iter()
             .filter(|s| {
-                needed.contains(&s.repo) && s.page == 0 && s.fetched.is_empty() && !s.loading
+                needed.iter().any(|id| id == s.id())
+                    && s.page == 0
+                    && s.fetched.is_empty()
+                    && !s.loading
             })
-            .map(|s| s.repo)
+            .map(|s| s.source.clone())
             .collect();
         if !to_fetch.is_empty() {
             self.state.versions.fetching = true;
             for src in &mut self.state.versions.channel_sources {
-                if to_fetch.contains(&src.repo) {
+                if to_fetch.iter().any(|s| s.id() == src.id()) {
                     src.loading = true;
                 }
             }
-            use crate::service::installer_service as svc;
             cx.spawn(async move |entity, cx| {
                 let results = cx
                     .background_executor()
                     .spawn(async move {
                         to_fetch
                             .iter()
-                            .map(|repo| (*repo, svc::fetch_repo_releases_blocking(repo, 1)))
+                            .map(|source| (source.id().to_string(), source.fetch_page(1)))
                             .collect::<Vec<_>>()
                     })
                     .await;
                 let _ = cx.update(|cx| {
                     entity.update(cx, |this, cx| {
                         this.state.versions.fetching = false;
-                        for (repo, result) in results {
+                        for (id, result) in results {
                             let Some(src) = this
                                 .state
                                 .versions
                                 .channel_sources
                                 .iter_mut()
-                                .find(|s| s.repo == repo)
+                                .find(|s| s.id() == id)
                             else {
                                 continue;
                             };
                             match result {
                                 Ok(list) => {
-                                    let has_more = list.len()
-                                        == svc::RELEASES_PER_PAGE as usize;
                                     let mut seen: std::collections::HashSet<String> =
                                         src.fetched.iter().map(|r| r.tag_name.clone()).collect();
-                                    for release in list {
+                                    for release in list.releases {
                                         if seen.insert(release.tag_name.clone()) {
                                             src.fetched.push(release);
                                         }
                                     }
                                     src.page = 1;
-                                    src.has_more = has_more;
+                                    src.has_more = list.has_more;
                                     src.loading = false;
                                 }
                                 Err(e) => {
@@ -1977,9 +2033,13 @@ impl EntryScreen {
         )
     }
 
-    /// `Some(required)` if `path` needs an engine version we don't have installed.
+    /// `Some(required)` if `path` needs an engine version we don't have
+    /// installed: its locked build, or else anything satisfying its requirement.
     pub(crate) fn missing_engine_for_project(&self, path: &std::path::Path) -> Option<String> {
-        let required = self.required_engine_for_project(path)?;
+        let required = match ProjectService::valid_engine_lock(path) {
+            Some(lock) => crate::service::project_service::exact_requirement(&lock.version),
+            None => self.required_engine_for_project(path)?,
+        };
         if self.engine_requirement_satisfied(&required) {
             None
         } else {
@@ -1987,6 +2047,43 @@ impl EntryScreen {
         }
     }
 
+    /// Move a project's engine lock to the newest engine satisfying its
+    /// requirement. If a newer matching release than any installed one is
+    /// available, the project is locked to it and it is offered for install.
+    /// The old lock is only replaced once the new one is known.
+    pub(crate) fn upgrade_project_engine(&mut self, path: PathBuf, cx: &mut Context<Self>) {
+        use crate::service::project_service::{exact_requirement, EngineLock, EngineResolution};
+        let required = self
+            .required_engine_for_project(&path)
+            .unwrap_or_else(|| "*".to_string());
+        if required.eq_ignore_ascii_case("src") {
+            return;
+        }
+        if let Some(tag) = self.pick_engine_tag(&required) {
+            let newest = exact_requirement(&tag);
+            if !self.engine_requirement_satisfied(&newest) {
+                let lock = EngineLock {
+                    version: tag,
+                    requirement: ProjectService::project_engine_version(&path),
+                    archive_sha256: None,
+                    archive_name: None,
+                };
+                if let Err(e) = ProjectService::write_engine_lock(&path, &lock) {
+                    tracing::error!("Failed to update the engine lock of {}: {}", path.display(), e);
+                    return;
+                }
+                self.request_engine_install(path, newest, cx);
+                return;
+            }
+        }
+        if let EngineResolution::Installed(engine) =
+            ProjectService::relock_project_engine(&path, &self.state.versions.installed)
+        {
+            tracing::info!("{} now uses engine {}", path.display(), engine.metadata.version);
+        }
+        cx.notify();
+    }
+
     pub(crate) fn request_engine_install(
         &mut self,
         project_path: std::path::PathBuf,
@@ -2001,10 +2098,26 @@ impl EntryScreen {
             project_name,
             project_path: project_path.to_string_lossy().to_string(),
             required,
+            reinstall: false,
         });
         cx.notify();
     }
 
+    /// Ask to reinstall `version`, which is installed from a different build
+    /// than the project's engine lock records.
+    fn request_engine_reinstall(
+        &mut self,
+        project_path: std::path::PathBuf,
+        version: &str,
+        cx: &mut Context<Self>,
+    ) {
+        let required = crate::service::project_service::exact_requirement(version);
+        self.request_engine_install(project_path, required, cx);
+        if let Some(prompt) = &mut self.state.ui.engine_prompt {
+            prompt.reinstall = true;
+        }
+    }
+
     pub(crate) fn close_engine_prompt(&mut self, cx: &mut Context<Self>) {
         self.state.ui.engine_prompt = None;
         cx.notify();
@@ -2016,21 +2129,24 @@ impl EntryScreen {
             return;
         };
         let required = prompt.required.clone();
-        // If the project pins a nightly, make sure the nightly channel is enabled.
-        if required.to_lowercase().starts_with("nightly-")
-            && !self
-                .state
-                .versions
+        // If the project pins a nightly, make sure a channel listing it is enabled.
+        if required.to_lowercase().starts_with("nightly-") {
+            let versions = &self.state.versions;
+            let listed = versions
                 .selected_channels
-                .contains(&crate::service::installer_service::ReleaseChannel::Nightly)
-        {
-            self.toggle_channel(
-                crate::service::installer_service::ReleaseChannel::Nightly,
-                true,
-                cx,
-            );
+                .iter()
+                .any(|c| c.includes_tag(&required));
+            let channel = versions
+                .channels
+                .iter()
+                .find(|c| c.includes_tag(&required))
+                .cloned();
+            if let (false, Some(channel)) = (listed, channel) {
+                self.toggle_channel(channel, true, cx);
+            }
         }
         self.state.ui.pending_engine_install = Some(required);
+        self.state.ui.pending_engine_reinstall = prompt.reinstall;
         if self.state.versions.available_releases.is_empty() && !self.state.versions.fetching {
             self.refresh_versions(cx);
         }
@@ -2044,7 +2160,8 @@ impl EntryScreen {
         let Some(required) = self.state.ui.pending_engine_install.clone() else {
             return;
         };
-        if self.engine_requirement_satisfied(&required) {
+        // A reinstall replaces a build that already satisfies the requirement.
+        if !self.state.ui.pending_engine_reinstall && self.engine_requirement_satisfied(&required) {
             self.state.ui.pending_engine_install = None;
             return;
         }
@@ -2052,31 +2169,29 @@ impl EntryScreen {
             return;
         };
         self.state.ui.pending_engine_install = None;
+        self.state.ui.pending_engine_reinstall = false;
         self.install_release_by_tag(tag, cx);
     }
 
+    /// The highest loaded release with an asset for this platform that
+    /// satisfies `required`.
     fn pick_engine_tag(&self, required: &str) -> Option<String> {
         use crate::service::installer_service as svc;
-        let releases = &self.state.versions.available_releases;
-        // Exact tag match first (covers nightly hashes and exact versions).
-        if let Some(r) = releases.iter().find(|r| r.tag_name == required) {
-            return Some(r.tag_name.clone());
-        }
-        if required.to_lowercase().starts_with("nightly-") {
-            return None;
-        }
-        let min = svc::required_min_version(required)?;
-        // Releases are sorted newest-first, so the first satisfying release wins.
-        for r in releases {
-            if svc::find_platform_asset(r).is_some()
-                && svc::parse_version(&r.tag_name)
-                    .map(|v| v >= min)
-                    .unwrap_or(false)
-            {
-                return Some(r.tag_name.clone());
+        let requirement = match svc::EngineRequirement::parse(required) {
+            Ok(requirement) => requirement,
+            Err(e) => {
+                tracing::warn!("{}", e);
+                return None;
             }
-        }
-        None
+        };
+        let installable = self
+            .state
+            .versions
+            .available_releases
+            .iter()
+            .filter(|r| svc::has_platform_build(r))
+            .map(|r| r.tag_name.as_str());
+        requirement.best_match(installable).map(str::to_string)
     }
 
     /// Open the full-screen release-notes modal for the given installed engine version.
@@ -2112,90 +2227,823 @@ impl EntryScreen {
         cx.notify();
     }
 
-    /// Debug helper: install every available release that has a platform asset.
-    pub(crate) fn install_all_versions(&mut self, cx: &mut Context<Self>) {
-        use crate::service::installer_service as svc;
-        let releases = self.state.versions.available_releases.clone();
-        for release in &releases {
-            if svc::find_platform_asset(release).is_some() {
-                self.install_release_by_tag(release.tag_name.clone(), cx);
+    /// Ask the host binary's updater whether a newer hub exists, in the
+    /// background. Updates the user chose to skip are ignored.
+    fn check_for_hub_update(&mut self, cx: &mut Context<Self>) {
+        use crate::service::hub_update_service::{hub_updater, HubUpdatePrefs};
+        let Some(updater) = hub_updater() else {
+            return;
+        };
+
+        cx.spawn(async move |entity, cx| {
+            let result = cx
+                .background_executor()
+                .spawn(async move { updater.check() })
+                .await;
+            let info = match result {
+                Ok(Some(info)) => info,
+                Ok(None) => return,
+                Err(e) => {
+                    tracing::warn!("Hub update check failed: {}", e);
+                    return;
+                }
+            };
+            if HubUpdatePrefs::load().skipped_version.as_deref() == Some(&info.latest_version) {
+                tracing::info!("Skipping hub update v{} at the user's request", info.latest_version);
+                return;
             }
-        }
+            let _ = cx.update(|cx| {
+                entity.update(cx, |this, cx| {
+                    this.state.ui.hub_update = HubUpdateState::Available(info);
+                    cx.notify();
+                });
+            });
+        })
+        .detach();
     }
 
-    /// Start downloading + extracting the given release, driven by the tag name.
-    pub(crate) fn install_release_by_tag(&mut self, tag: String, cx: &mut Context<Self>) {
-        let Some(release) = self
-            .state
-            .versions
-            .available_releases
-            .iter()
-            .find(|r| r.tag_name == tag)
-            .cloned()
-        else {
+    /// Download and install the available hub update. With `relaunch` the hub
+    /// restarts into the new build when done; otherwise it is picked up on the
+    /// next launch.
+    pub(crate) fn start_hub_update(&mut self, relaunch: bool, cx: &mut Context<Self>) {
+        let Some(updater) = crate::service::hub_update_service::hub_updater() else {
             return;
         };
-        let Some(asset) = crate::service::installer_service::find_platform_asset(&release) else {
-            return;
+        let info = match &self.state.ui.hub_update {
+            HubUpdateState::Available(info) | HubUpdateState::Failed { info, .. } => info.clone(),
+            _ => return,
+        };
+        let progress = Arc::new(Mutex::new(HubUpdateProgress {
+            total_bytes: info.download_bytes,
+            ..Default::default()
+        }));
+        self.state.ui.hub_update = HubUpdateState::Installing {
+            info: info.clone(),
+            progress: progress.clone(),
+            relaunch,
         };
-        let url = asset.browser_download_url.clone();
-        let dest = crate::service::installer_service::default_install_path()
-            .join(tag.trim_start_matches('v'));
-        let dl_id = format!("engine-{}", tag);
-
-        let dm_view = self.state.download_manager_view.clone();
-        dm_view.update(cx, |view, cx| {
-            view.add_item(crate::core::types::DownloadItem {
-                id: dl_id.clone(),
-                kind: crate::core::types::DownloadKind::EngineVersion {
-                    version: tag.clone(),
-                },
-                status: crate::core::types::DownloadStatus::Downloading {
-                    bytes_downloaded: 0,
-                    total_bytes: 0,
-                    speed_bps: 0,
-                },
-                started_at: std::time::Instant::now(),
-            });
-            cx.notify();
-        });
-        self.state.versions.install_state =
-            crate::service::installer_service::VersionInstallState::Downloading {
-                version: tag.clone(),
-                progress: 0.0,
-            };
         cx.notify();
 
         cx.spawn(async move |entity, cx| {
-            let dl_tag = tag.clone();
-            let progress = std::sync::Arc::new(parking_lot::Mutex::new(
-                crate::service::installer_service::DownloadProgress::default(),
-            ));
-            let progress_clone = progress.clone();
-
-            let _download_task = cx.background_executor().spawn(async move {
-                crate::service::installer_service::download_and_extract_with_progress(
-                    &url,
-                    &dest,
-                    &dl_tag,
-                    progress_clone,
+            let task_updater = updater.clone();
+            let task_info = info.clone();
+            let task_progress = progress.clone();
+            let install = cx.background_executor().spawn(async move {
+                crate::service::hub_update_service::install_hub_update(
+                    task_updater.as_ref(),
+                    &task_info,
+                    task_progress,
                 )
             });
 
+            // Poll so the banner's progress bar tracks the download.
             loop {
                 cx.background_executor()
                     .timer(std::time::Duration::from_millis(150))
                     .await;
-
-                let snapshot = {
-                    let p = progress.lock();
-                    (p.bytes_downloaded, p.total_bytes, p.speed_bps, p.done, p.error.clone())
-                };
-                let (bytes, total, speed, done, _error) = snapshot;
+                let done = progress.lock().done;
                 let _ = cx.update(|cx| {
-                    let _ = entity.update(cx, |this, cx| {
-                        if !done {
-                            dm_view.update(cx, |view, cx| {
+                    let _ = entity.update(cx, |_, cx| cx.notify());
+                });
+                if done {
+                    break;
+                }
+            }
+
+            let result = install.await;
+            let _ = cx.update(|cx| {
+                entity.update(cx, |this, cx| {
+                    match result {
+                        Ok(()) if relaunch => updater.relaunch(),
+                        Ok(()) => this.state.ui.hub_update = HubUpdateState::Staged(info),
+                        Err(message) => {
+                            tracing::error!("Hub update failed: {}", message);
+                            this.state.ui.hub_update = HubUpdateState::Failed { info, message };
+                        }
+                    }
+                    cx.notify();
+                });
+            });
+        })
+        .detach();
+    }
+
+    /// Hide the banner and stop offering the current update on future launches.
+    pub(crate) fn skip_hub_update(&mut self, cx: &mut Context<Self>) {
+        use crate::service::hub_update_service::HubUpdatePrefs;
+        if let HubUpdateState::Available(info) | HubUpdateState::Failed { info, .. } =
+            &self.state.ui.hub_update
+        {
+            HubUpdatePrefs {
+                skipped_version: Some(info.latest_version.clone()),
+            }
+            .save();
+        }
+        self.dismiss_hub_update(cx);
+    }
+
+    pub(crate) fn dismiss_hub_update(&mut self, cx: &mut Context<Self>) {
+        if !matches!(self.state.ui.hub_update, HubUpdateState::Installing { .. }) {
+            self.state.ui.hub_update = HubUpdateState::Idle;
+            cx.notify();
+        }
+    }
+
+    pub(crate) fn open_hub_release_notes(&mut self, info: &HubUpdateInfo, cx: &mut Context<Self>) {
+        let body = if info.release_notes.trim().is_empty() {
+            "No release notes were published for this update.".to_string()
+        } else {
+            info.release_notes.clone()
+        };
+        self.state.ui.release_notes_modal = Some(crate::core::types::ReleaseNotesModal {
+            title: format!("Pulsar Hub v{}", info.latest_version),
+            body,
+        });
+        cx.notify();
+    }
+
+    /// Debug helper: queue every available release that has a platform asset.
+    pub(crate) fn install_all_versions(&mut self, cx: &mut Context<Self>) {
+        use crate::service::installer_service as svc;
+        let releases = self.state.versions.available_releases.clone();
+        for release in &releases {
+            if svc::has_platform_build(release) {
+                self.install_release_by_tag(release.tag_name.clone(), cx);
+            }
+        }
+    }
+
+    /// Queue downloading + extracting the given release, driven by the tag
+    /// name. Releases shipped in modules get their default modules.
+    pub(crate) fn install_release_by_tag(&mut self, tag: String, cx: &mut Context<Self>) {
+        self.install_release_with_modules(tag, Vec::new(), cx);
+    }
+
+    fn install_release_with_modules(&mut self, tag: String, modules: Vec<String>, cx: &mut Context<Self>) {
+        let Some(release) = self
+            .state
+            .versions
+            .available_releases
+            .iter()
+            .find(|r| r.tag_name == tag)
+            .cloned()
+        else {
+            return;
+        };
+        if !crate::service::installer_service::has_platform_build(&release) {
+            return;
+        }
+        // A reinstall replaces the engine where it is; a new one goes into
+        // the default library.
+        let version = tag.trim_start_matches('v');
+        let install_path = self
+            .state
+            .versions
+            .installed
+            .iter()
+            .find(|v| v.metadata.version.trim_start_matches('v') == version)
+            .map(|v| v.metadata.install_path.clone())
+            .unwrap_or_else(|| engine_install_path(&release));
+        self.enqueue_download(
+            format!("engine-{}", tag),
+            QueuedJob::EngineVersion {
+                release,
+                modules,
+                install_path: Some(install_path),
+            },
+            cx,
+        );
+    }
+
+    /// The release list's Install button: ask which modules to install for
+    /// releases shipped in modules, otherwise queue the install right away.
+    pub(crate) fn request_release_install(&mut self, tag: String, cx: &mut Context<Self>) {
+        use crate::service::installer_service as svc;
+        let Some(release) = self
+            .state
+            .versions
+            .available_releases
+            .iter()
+            .find(|r| r.tag_name == tag)
+            .cloned()
+        else {
+            return;
+        };
+        if !svc::has_module_manifest(&release) {
+            self.install_release_by_tag(tag, cx);
+            return;
+        }
+        self.open_module_picker(tag, Some(release), None, Vec::new(), cx);
+    }
+
+    /// Let the user add or remove modules of the install of `version` at
+    /// `install_path`.
+    pub(crate) fn manage_engine_modules(
+        &mut self,
+        version: String,
+        install_path: std::path::PathBuf,
+        installed: Vec<String>,
+        cx: &mut Context<Self>,
+    ) {
+        self.open_module_picker(version, None, Some(install_path), installed, cx);
+    }
+
+    /// Show the module checklist and load the release's module manifest
+    /// (and the release itself, if not given) in the background.
+    fn open_module_picker(
+        &mut self,
+        version: String,
+        release: Option<crate::service::installer_service::GitHubRelease>,
+        install_path: Option<std::path::PathBuf>,
+        selected: Vec<String>,
+        cx: &mut Context<Self>,
+    ) {
+        use crate::service::installer_service as svc;
+        self.state.ui.module_picker = Some(crate::core::types::ModulePicker {
+            version: version.clone(),
+            release: None,
+            modules: Vec::new(),
+            selected,
+            install_path,
+            error: None,
+        });
+        cx.notify();
+
+        cx.spawn(async move |entity, cx| {
+            let loaded = cx
+                .background_executor()
+                .spawn(async move {
+                    let release = match release {
+                        Some(release) => release,
+                        None => svc::fetch_release_by_tag_blocking(&version)?,
+                    };
+                    let manifest = svc::fetch_module_manifest(&release)?
+                        .ok_or_else(|| format!("Release {} isn't split into modules", release.tag_name))?;
+                    let modules = manifest.available(&release);
+                    Ok::<_, String>((release, modules))
+                })
+                .await;
+
+            let _ = cx.update(|cx| {
+                let _ = entity.update(cx, |this, cx| {
+                    let Some(picker) = this.state.ui.module_picker.as_mut() else {
+                        return;
+                    };
+                    match loaded {
+                        Ok((release, modules)) => {
+                            if picker.selected.is_empty() {
+                                picker.selected = modules
+                                    .iter()
+                                    .filter(|m| m.is_core() || m.default)
+                                    .map(|m| m.id.clone())
+                                    .collect();
+                            }
+                            picker.release = Some(release);
+                            picker.modules = modules;
+                        }
+                        Err(e) => picker.error = Some(e),
+                    }
+                    cx.notify();
+                });
+            });
+        })
+        .detach();
+    }
+
+    pub(crate) fn toggle_picker_module(&mut self, id: String, checked: bool, cx: &mut Context<Self>) {
+        if let Some(picker) = self.state.ui.module_picker.as_mut() {
+            picker.selected.retain(|m| *m != id);
+            if checked {
+                picker.selected.push(id);
+            }
+            cx.notify();
+        }
+    }
+
+    /// Open the Verify / Repair modal for an installed engine and re-hash
+    /// its files in the background.
+    pub(crate) fn verify_engine(
+        &mut self,
+        version: String,
+        install_path: std::path::PathBuf,
+        cx: &mut Context<Self>,
+    ) {
+        use crate::core::types::{InstallCheck, InstallCheckStage};
+        self.state.ui.install_check = Some(InstallCheck {
+            version,
+            install_path: install_path.clone(),
+            stage: InstallCheckStage::Verifying,
+        });
+        cx.notify();
+
+        cx.spawn(async move |entity, cx| {
+            let result = cx
+                .background_executor()
+                .spawn(async move { crate::service::installer_service::verify_install(&install_path) })
+                .await;
+            let _ = cx.update(|cx| {
+                let _ = entity.update(cx, |this, cx| {
+                    if let Some(check) = this.state.ui.install_check.as_mut() {
+                        check.stage = match result {
+                            Ok(report) => InstallCheckStage::Verified(report),
+                            Err(e) => InstallCheckStage::Failed(e),
+                        };
+                    }
+                    cx.notify();
+                });
+            });
+        })
+        .detach();
+    }
+
+    /// Restore the files the last verification found broken.
+    pub(crate) fn repair_engine(&mut self, cx: &mut Context<Self>) {
+        use crate::core::types::InstallCheckStage;
+        use crate::service::installer_service as svc;
+        let Some(check) = self.state.ui.install_check.as_mut() else {
+            return;
+        };
+        let InstallCheckStage::Verified(report) = &check.stage else {
+            return;
+        };
+        let report = report.clone();
+        let install_path = check.install_path.clone();
+        let progress = std::sync::Arc::new(parking_lot::Mutex::new(svc::DownloadProgress {
+            max_bps: self.state.download_queue.max_bps_per_download,
+            ..Default::default()
+        }));
+        check.stage = InstallCheckStage::Repairing(progress.clone());
+        cx.notify();
+
+        cx.spawn(async move |entity, cx| {
+            let task = {
+                let progress = progress.clone();
+                cx.background_executor().spawn(async move {
+                    let result = svc::repair_install(&install_path, &report, &progress);
+                    progress.lock().done = true;
+                    result
+                })
+            };
+            while !progress.lock().done {
+                cx.background_executor()
+                    .timer(std::time::Duration::from_millis(150))
+                    .await;
+                let _ = cx.update(|cx| {
+                    let _ = entity.update(cx, |_, cx| cx.notify());
+                });
+            }
+            let result = task.await;
+            let _ = cx.update(|cx| {
+                let _ = entity.update(cx, |this, cx| {
+                    if let Some(check) = this.state.ui.install_check.as_mut() {
+                        check.stage = match result {
+                            Ok(report) => InstallCheckStage::Repaired(report),
+                            Err(e) => InstallCheckStage::Failed(e.to_string()),
+                        };
+                    }
+                    this.state.versions.installed = this.installed_versions();
+                    cx.notify();
+                });
+            });
+        })
+        .detach();
+    }
+
+    pub(crate) fn close_install_check(&mut self, cx: &mut Context<Self>) {
+        self.state.ui.install_check = None;
+        cx.notify();
+    }
+
+    /// Prompt for an engine archive downloaded outside the hub, unpack it and
+    /// ask the user to confirm the version it was recognised as.
+    pub(crate) fn prompt_import_archive(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        use crate::core::types::ArchiveImport;
+        use crate::service::installer_service as svc;
+        cx.spawn_in(window, async move |entity, cx| {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .add_filter("Engine archive", &["zip", "gz", "tgz"])
+                .pick_file()
+                .await
+            else {
+                return;
+            };
+            let archive = file.path().to_path_buf();
+            let _ = entity.update(cx, |this, cx| {
+                this.state.ui.archive_import = Some(ArchiveImport {
+                    archive: archive.clone(),
+                    pending: None,
+                    installing: false,
+                    error: None,
+                });
+                cx.notify();
+            });
+            let result = {
+                let archive = archive.clone();
+                cx.background_executor()
+                    .spawn(async move { svc::prepare_import(&archive) })
+                    .await
+            };
+            let _ = entity.update_in(cx, |this, window, cx| {
+                let Some(import) = this
+                    .state
+                    .ui
+                    .archive_import
+                    .as_mut()
+                    .filter(|i| i.archive == archive && i.pending.is_none())
+                else {
+                    // Closed while unpacking.
+                    if let Ok(pending) = &result {
+                        svc::cancel_import(pending);
+                    }
+                    return;
+                };
+                match result {
+                    Ok(pending) => {
+                        let version = pending.version.clone().unwrap_or_default();
+                        this.inputs.import_version.update(cx, |input, cx| {
+                            input.set_value(version, window, cx);
+                        });
+                        import.pending = Some(pending);
+                    }
+                    Err(e) => import.error = Some(e),
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Install the unpacked archive as the version typed in the modal.
+    pub(crate) fn confirm_archive_import(&mut self, cx: &mut Context<Self>) {
+        let version = self.inputs.import_version.read(cx).text().to_string();
+        let Some(import) = self.state.ui.archive_import.as_mut() else {
+            return;
+        };
+        let Some(pending) = import.pending.clone() else {
+            return;
+        };
+        if import.installing {
+            return;
+        }
+        import.installing = true;
+        import.error = None;
+        cx.notify();
+
+        cx.spawn(async move |entity, cx| {
+            let result = cx
+                .background_executor()
+                .spawn(async move { crate::service::installer_service::finish_import(&pending, &version) })
+                .await;
+            let _ = cx.update(|cx| {
+                let _ = entity.update(cx, |this, cx| {
+                    match result {
+                        Ok(_) => {
+                            this.state.ui.archive_import = None;
+                            this.state.versions.installed = this.installed_versions();
+                        }
+                        Err(e) => {
+                            if let Some(import) = this.state.ui.archive_import.as_mut() {
+                                import.installing = false;
+                                import.error = Some(e);
+                            }
+                        }
+                    }
+                    cx.notify();
+                });
+            });
+        })
+        .detach();
+    }
+
+    pub(crate) fn cancel_archive_import(&mut self, cx: &mut Context<Self>) {
+        let Some(import) = self.state.ui.archive_import.as_ref() else {
+            return;
+        };
+        if import.installing {
+            return;
+        }
+        if let Some(pending) = import.pending.clone() {
+            cx.background_executor()
+                .spawn(async move { crate::service::installer_service::cancel_import(&pending) })
+                .detach();
+        }
+        self.state.ui.archive_import = None;
+        cx.notify();
+    }
+
+    /// Show the engine library folders.
+    pub(crate) fn open_library_manager(&mut self, cx: &mut Context<Self>) {
+        use crate::core::types::LibraryManager;
+        use crate::service::engine_library_service::EngineLibraries;
+        self.state.ui.library_manager = Some(LibraryManager {
+            libraries: EngineLibraries::load(),
+            moving: None,
+            error: None,
+        });
+        cx.notify();
+    }
+
+    /// Ask which library the install at `install_path` should move to.
+    pub(crate) fn open_move_install(
+        &mut self,
+        version: String,
+        install_path: std::path::PathBuf,
+        cx: &mut Context<Self>,
+    ) {
+        use crate::core::types::{LibraryManager, LibraryMove};
+        use crate::service::engine_library_service::EngineLibraries;
+        self.state.ui.library_manager = Some(LibraryManager {
+            libraries: EngineLibraries::load(),
+            moving: Some(LibraryMove {
+                version,
+                install_path,
+                in_progress: false,
+            }),
+            error: None,
+        });
+        cx.notify();
+    }
+
+    pub(crate) fn close_library_manager(&mut self, cx: &mut Context<Self>) {
+        if self
+            .state
+            .ui
+            .library_manager
+            .as_ref()
+            .and_then(|m| m.moving.as_ref())
+            .is_some_and(|m| m.in_progress)
+        {
+            return;
+        }
+        self.state.ui.library_manager = None;
+        cx.notify();
+    }
+
+    /// Apply `change` to the library list, save it and rescan the installs.
+    fn update_libraries(
+        &mut self,
+        change: impl FnOnce(&mut crate::service::engine_library_service::EngineLibraries),
+        cx: &mut Context<Self>,
+    ) {
+        let Some(manager) = self.state.ui.library_manager.as_mut() else {
+            return;
+        };
+        change(&mut manager.libraries);
+        manager.error = manager.libraries.save().err();
+        self.state.versions.installed = self.installed_versions();
+        cx.notify();
+    }
+
+    /// Prompt for a folder and add it as an engine library.
+    pub(crate) fn prompt_add_library(&mut self, cx: &mut Context<Self>) {
+        cx.spawn(async move |entity, cx| {
+            if let Some(folder) = rfd::AsyncFileDialog::new().pick_folder().await {
+                let folder = folder.path().to_path_buf();
+                let _ = cx.update(|cx| {
+                    entity.update(cx, |this, cx| {
+                        this.update_libraries(
+                            |libraries| {
+                                libraries.add(folder);
+                            },
+                            cx,
+                        );
+                    })
+                });
+            }
+        })
+        .detach();
+    }
+
+    pub(crate) fn remove_library(&mut self, folder: std::path::PathBuf, cx: &mut Context<Self>) {
+        self.update_libraries(|libraries| libraries.remove(&folder), cx);
+    }
+
+    /// Install new engines into `folder`, or the platform folder for `None`.
+    pub(crate) fn set_default_library(
+        &mut self,
+        folder: Option<std::path::PathBuf>,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_libraries(|libraries| libraries.set_default(folder), cx);
+    }
+
+    /// Move the install picked in `open_move_install` into `library`.
+    pub(crate) fn move_install_to(&mut self, library: std::path::PathBuf, cx: &mut Context<Self>) {
+        let Some(manager) = self.state.ui.library_manager.as_mut() else {
+            return;
+        };
+        let Some(moving) = manager.moving.as_mut() else {
+            return;
+        };
+        if moving.in_progress {
+            return;
+        }
+        moving.in_progress = true;
+        manager.error = None;
+        let install_path = moving.install_path.clone();
+        cx.notify();
+
+        cx.spawn(async move |entity, cx| {
+            let result = cx
+                .background_executor()
+                .spawn(async move {
+                    crate::service::installer_service::move_install(&install_path, &library)
+                })
+                .await;
+            let _ = cx.update(|cx| {
+                let _ = entity.update(cx, |this, cx| {
+                    this.state.versions.installed = this.installed_versions();
+                    match result {
+                        Ok(_) => this.state.ui.library_manager = None,
+                        Err(e) => {
+                            if let Some(manager) = this.state.ui.library_manager.as_mut() {
+                                if let Some(moving) = manager.moving.as_mut() {
+                                    moving.in_progress = false;
+                                }
+                                manager.error = Some(e);
+                            }
+                        }
+                    }
+                    cx.notify();
+                });
+            });
+        })
+        .detach();
+    }
+
+    pub(crate) fn close_module_picker(&mut self, cx: &mut Context<Self>) {
+        self.state.ui.module_picker = None;
+        cx.notify();
+    }
+
+    /// Queue the install, or the module change, picked in the module picker.
+    pub(crate) fn confirm_module_picker(&mut self, cx: &mut Context<Self>) {
+        let Some(picker) = self.state.ui.module_picker.take() else {
+            return;
+        };
+        let Some(release) = picker.release else {
+            cx.notify();
+            return;
+        };
+        // Keep the manifest's order.
+        let modules: Vec<String> = picker
+            .modules
+            .iter()
+            .filter(|m| m.is_core() || picker.selected.contains(&m.id))
+            .map(|m| m.id.clone())
+            .collect();
+        match picker.install_path {
+            Some(install_path) => self.enqueue_download(
+                format!("engine-modules-{}", release.tag_name),
+                QueuedJob::EngineModules {
+                    release,
+                    install_path,
+                    modules,
+                },
+                cx,
+            ),
+            None => self.install_release_with_modules(release.tag_name.clone(), modules, cx),
+        }
+        cx.notify();
+    }
+
+    /// Add a download to the persistent queue and start it if a slot is free.
+    fn enqueue_download(&mut self, id: String, job: QueuedJob, cx: &mut Context<Self>) {
+        if !self.state.download_queue.push(id.clone(), job) {
+            return;
+        }
+        self.state.download_queue.save();
+        if let Some(entry) = self.state.download_queue.entries.last() {
+            let item = queued_download_item(entry);
+            self.state.download_manager_view.update(cx, |view, cx| {
+                view.add_item(item);
+                cx.notify();
+            });
+        }
+        self.pump_download_queue(cx);
+    }
+
+    fn is_download_running(&self, id: &str) -> bool {
+        self.state.versions.downloads.contains_key(id)
+            || self.state.cloning_download.as_deref() == Some(id)
+    }
+
+    /// Start queued downloads, in order, until the concurrency limit is hit.
+    pub(crate) fn pump_download_queue(&mut self, cx: &mut Context<Self>) {
+        let ready = self
+            .state
+            .download_queue
+            .ready(|entry| self.is_download_running(&entry.id));
+        for entry in ready {
+            if let Some(queued) = self.state.download_queue.get_mut(&entry.id) {
+                queued.started = true;
+            }
+            self.state.download_queue.save();
+            self.state.download_manager_view.update(cx, |view, cx| {
+                view.update_progress(&entry.id, 0, 0, 0);
+                cx.notify();
+            });
+            match entry.job {
+                QueuedJob::EngineVersion {
+                    release,
+                    modules,
+                    install_path,
+                } => {
+                    let dest = install_path.unwrap_or_else(|| engine_install_path(&release));
+                    self.start_engine_download(release, modules, dest, false, entry.id, cx);
+                }
+                QueuedJob::EngineModules {
+                    release,
+                    install_path,
+                    modules,
+                } => {
+                    self.start_engine_download(release, modules, install_path, true, entry.id, cx);
+                }
+                QueuedJob::TemplateClone {
+                    repo_url, target, ..
+                } => {
+                    // A clone interrupted by a restart left a partial
+                    // checkout in the directory it created; start over.
+                    if entry.started {
+                        let _ = std::fs::remove_dir_all(&target);
+                    }
+                    self.run_clone(repo_url, target, Some(entry.id), cx);
+                }
+            }
+        }
+    }
+
+    /// Drop a download that finished, failed or was cancelled from the queue
+    /// and let the next one start.
+    fn finish_queued_download(&mut self, id: &str, cx: &mut Context<Self>) {
+        if self.state.cloning_download.as_deref() == Some(id) {
+            self.state.cloning_download = None;
+        }
+        self.state.versions.downloads.remove(id);
+        if self.state.download_queue.remove(id).is_some() {
+            self.state.download_queue.save();
+        }
+        self.pump_download_queue(cx);
+    }
+
+    /// Run (or resume) the download behind download manager item `dl_id`:
+    /// an install of `release` with `modules` into `dest`, or, if
+    /// `change_modules`, a change of the modules of the install at `dest`.
+    fn start_engine_download(
+        &mut self,
+        release: crate::service::installer_service::GitHubRelease,
+        modules: Vec<String>,
+        dest: PathBuf,
+        change_modules: bool,
+        dl_id: String,
+        cx: &mut Context<Self>,
+    ) {
+        let tag = release.tag_name.clone();
+        let progress = std::sync::Arc::new(parking_lot::Mutex::new(
+            crate::service::installer_service::DownloadProgress {
+                max_bps: self.state.download_queue.max_bps_per_download,
+                ..Default::default()
+            },
+        ));
+        self.state
+            .versions
+            .downloads
+            .insert(dl_id.clone(), progress.clone());
+
+        let dm_view = self.state.download_manager_view.clone();
+        self.state.versions.install_state =
+            crate::service::installer_service::VersionInstallState::Downloading {
+                version: tag.clone(),
+                progress: 0.0,
+            };
+        cx.notify();
+
+        cx.spawn(async move |entity, cx| {
+            let progress_clone = progress.clone();
+
+            let _download_task = cx.background_executor().spawn(async move {
+                use crate::service::installer_service as svc;
+                if change_modules {
+                    svc::change_modules_with_progress(&release, &dest, &modules, progress_clone)
+                } else {
+                    svc::install_release_with_progress(&release, &dest, &modules, progress_clone)
+                }
+            });
+
+            loop {
+                cx.background_executor()
+                    .timer(std::time::Duration::from_millis(150))
+                    .await;
+
+                let snapshot = {
+                    let p = progress.lock();
+                    (p.bytes_downloaded, p.total_bytes, p.speed_bps, p.done)
+                };
+                let (bytes, total, speed, done) = snapshot;
+                let _ = cx.update(|cx| {
+                    let _ = entity.update(cx, |_this, cx| {
+                        if !done {
+                            dm_view.update(cx, |view, cx| {
                                 view.update_progress(&dl_id, bytes, total, speed);
                                 cx.notify();
                             });
@@ -2211,8 +3059,34 @@ impl EntryScreen {
 
             let _ = cx.update(|cx| {
                 let _ = entity.update(cx, |this, cx| {
-                    let p = progress.lock();
-                    if let Some(ref e) = p.error {
+                    let p = progress.lock().clone();
+                    if p.paused {
+                        dm_view.update(cx, |view, cx| {
+                            view.pause(&dl_id, p.bytes_downloaded, p.total_bytes);
+                            cx.notify();
+                        });
+                        this.state.versions.install_state =
+                            crate::service::installer_service::VersionInstallState::Idle;
+                        // Keep the entry, held, so it resumes from the
+                        // partial file after a restart too.
+                        this.state.versions.downloads.remove(&dl_id);
+                        if let Some(entry) = this.state.download_queue.get_mut(&dl_id) {
+                            entry.paused = true;
+                        }
+                        this.state.download_queue.save();
+                        this.pump_download_queue(cx);
+                        cx.notify();
+                        return;
+                    }
+
+                    if p.cancelled {
+                        dm_view.update(cx, |view, cx| {
+                            view.cancel(&dl_id);
+                            cx.notify();
+                        });
+                        this.state.versions.install_state =
+                            crate::service::installer_service::VersionInstallState::Idle;
+                    } else if let Some(ref e) = p.error {
                         dm_view.update(cx, |view, cx| {
                             view.fail(&dl_id, e.clone());
                             cx.notify();
@@ -2220,7 +3094,7 @@ impl EntryScreen {
                         this.state.versions.install_state =
                             crate::service::installer_service::VersionInstallState::Error {
                                 version: tag.clone(),
-                                message: e.clone(),
+                                message: e.to_string(),
                             };
                     } else {
                         dm_view.update(cx, |view, cx| {
@@ -2233,6 +3107,7 @@ impl EntryScreen {
                             };
                         this.state.versions.installed = this.installed_versions();
                     }
+                    this.finish_queued_download(&dl_id, cx);
                     cx.notify();
                 });
             });
@@ -2240,59 +3115,115 @@ impl EntryScreen {
         .detach();
     }
 
-    pub(crate) fn install_latest_version(&mut self, cx: &mut Context<Self>) {
-        if let Some(release) = self.state.versions.available_releases.first().cloned() {
-            let tag = release.tag_name.clone();
-            if let Some(asset) = crate::service::installer_service::find_platform_asset(&release) {
-                let url = asset.browser_download_url.clone();
-                let dest = crate::service::installer_service::default_install_path()
-                    .join(tag.trim_start_matches('v'));
+    /// Handle a control pressed on a download manager item.
+    pub(crate) fn handle_download_action(
+        &mut self,
+        event: &DownloadActionRequested,
+        cx: &mut Context<Self>,
+    ) {
+        use crate::service::installer_service::{self as svc, DownloadControl};
+
+        if matches!(event.action, DownloadAction::MoveUp | DownloadAction::MoveDown) {
+            let up = event.action == DownloadAction::MoveUp;
+            if self.state.download_queue.move_entry(&event.id, up) {
+                self.state.download_queue.save();
+                let order: Vec<String> = self
+                    .state
+                    .download_queue
+                    .entries
+                    .iter()
+                    .map(|e| e.id.clone())
+                    .collect();
+                self.state.download_manager_view.update(cx, |view, cx| {
+                    view.reorder(&order);
+                    cx.notify();
+                });
+            }
+            return;
+        }
 
-                self.state.versions.install_state =
-                    crate::service::installer_service::VersionInstallState::Downloading {
-                        version: tag.clone(),
-                        progress: 0.0,
-                    };
-                cx.notify();
+        if let Some(progress) = self.state.versions.downloads.get(&event.id) {
+            // Running: signal the download thread, which reports back once
+            // it has stopped.
+            progress.lock().control = match event.action {
+                DownloadAction::Pause => DownloadControl::Pause,
+                DownloadAction::Cancel => DownloadControl::Cancel,
+                _ => return,
+            };
+            return;
+        }
+        if self.is_download_running(&event.id) {
+            return;
+        }
 
-                cx.spawn(async move |entity, cx| {
-                    let dl_tag = tag.clone();
-                    let result = cx
-                        .background_executor()
-                        .spawn(async move {
-                            crate::service::installer_service::download_and_extract_blocking(
-                                &url,
-                                &dest,
-                                &dl_tag,
-                                |_| {},
-                            )
-                        })
-                        .await;
-                    let _ = cx.update(|cx| {
-                        entity.update(cx, |this, cx| {
-                            match result {
-                                Ok(()) => {
-                                    this.state.versions.install_state =
-                                        crate::service::installer_service::VersionInstallState::Complete {
-                                            version: tag,
-                                        };
-                                    this.state.versions.installed = this.installed_versions();
-                                }
-                                Err(e) => {
-                                    this.state.versions.install_state =
-                                        crate::service::installer_service::VersionInstallState::Error {
-                                            version: tag,
-                                            message: e,
-                                        };
-                                }
-                            }
-                            cx.notify();
-                        });
-                    });
-                })
-                .detach();
+        let Some(entry) = self.state.download_queue.get_mut(&event.id) else {
+            return;
+        };
+        match event.action {
+            DownloadAction::Pause => {
+                entry.paused = true;
+                self.state.download_queue.save();
+                self.state.download_manager_view.update(cx, |view, cx| {
+                    view.pause(&event.id, 0, 0);
+                    cx.notify();
+                });
+            }
+            DownloadAction::Resume => {
+                entry.paused = false;
+                self.state.download_queue.save();
+                self.state.download_manager_view.update(cx, |view, cx| {
+                    view.queue(&event.id);
+                    cx.notify();
+                });
+                self.pump_download_queue(cx);
             }
+            DownloadAction::Cancel => {
+                if entry.started {
+                    match &entry.job {
+                        QueuedJob::EngineVersion {
+                            release,
+                            install_path,
+                            ..
+                        } => {
+                            let dest = install_path
+                                .clone()
+                                .unwrap_or_else(|| engine_install_path(release));
+                            svc::remove_partial_download(release, &dest);
+                        }
+                        QueuedJob::EngineModules {
+                            release,
+                            install_path,
+                            ..
+                        } => svc::remove_partial_download(release, install_path),
+                        QueuedJob::TemplateClone { target, .. } => {
+                            let _ = std::fs::remove_dir_all(target);
+                        }
+                    }
+                }
+                self.state.download_manager_view.update(cx, |view, cx| {
+                    view.cancel(&event.id);
+                    cx.notify();
+                });
+                self.finish_queued_download(&event.id, cx);
+            }
+            DownloadAction::MoveUp | DownloadAction::MoveDown => {}
+        }
+    }
+
+    /// Apply new queue limits, including to downloads already running.
+    pub(crate) fn handle_download_limits(
+        &mut self,
+        event: &DownloadLimitsChanged,
+        cx: &mut Context<Self>,
+    ) {
+        let queue = &mut self.state.download_queue;
+        queue.max_concurrent = event.max_concurrent;
+        queue.max_bps_per_download = event.max_bps_per_download;
+        queue.save();
+        for progress in self.state.versions.downloads.values() {
+            progress.lock().max_bps = event.max_bps_per_download;
         }
+        self.pump_download_queue(cx);
     }
 
     pub(crate) fn remove_version(&mut self, version: &str, cx: &mut Context<Self>) {
@@ -0,0 +1,106 @@
+use gpui::prelude::*;
+use gpui::*;
+use ui::{
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    spinner::Spinner,
+    v_flex, ActiveTheme as _, Icon, IconName,
+};
+
+use crate::component::render_modal;
+use crate::screen::EntryScreen;
+
+/// The "Import engine archive" modal: unpacks the archive, then asks which
+/// version to install it as.
+pub fn render_archive_import(
+    screen: &mut EntryScreen,
+    cx: &mut Context<EntryScreen>,
+) -> gpui::AnyElement {
+    let theme = cx.theme();
+    let Some(import) = screen.state.ui.archive_import.clone() else {
+        return div().into_any_element();
+    };
+    let version_input = screen.inputs().import_version.clone();
+    let file_name = import
+        .archive
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let unpacking = import.pending.is_none() && import.error.is_none();
+    let busy = |label: &'static str| {
+        h_flex()
+            .w_full()
+            .gap_2()
+            .items_center()
+            .justify_center()
+            .py_6()
+            .child(Spinner::new().color(theme.muted_foreground))
+            .child(div().text_sm().text_color(theme.muted_foreground).child(label))
+    };
+
+    let body = match &import.pending {
+        None if unpacking => busy("Unpacking…").into_any_element(),
+        None => div().into_any_element(),
+        Some(_) if import.installing => busy("Installing…").into_any_element(),
+        Some(pending) => v_flex()
+            .gap_2()
+            .child(div().text_sm().text_color(theme.muted_foreground).child(
+                if pending.version.is_some() {
+                    "Check the version this build was recognised as."
+                } else {
+                    "The archive doesn't say which version it is. Enter it below."
+                },
+            ))
+            .child(ui::input::Input::new(&version_input).w_full())
+            .into_any_element(),
+    };
+
+    render_modal(
+        h_flex()
+            .gap_2()
+            .items_center()
+            .child(Icon::new(IconName::Package).size(px(18.)).text_color(theme.accent))
+            .child(div().child(format!("Import · {}", file_name))),
+        v_flex()
+            .gap_4()
+            .child(body)
+            .when_some(import.error.clone(), |this, error| {
+                this.child(div().text_sm().text_color(theme.danger).child(error))
+            })
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("archive-import-cancel")
+                            .label("Cancel")
+                            .compact()
+                            .ghost()
+                            .disabled(import.installing)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.cancel_archive_import(cx);
+                            })),
+                    )
+                    .when(import.pending.is_some(), |this| {
+                        this.child(
+                            Button::new("archive-import-confirm")
+                                .label("Import")
+                                .primary()
+                                .compact()
+                                .disabled(import.installing)
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.confirm_archive_import(cx);
+                                })),
+                        )
+                    }),
+            ),
+        (!import.installing).then(|| -> Box<dyn Fn(&mut EntryScreen, &mut Window, &mut Context<EntryScreen>)> {
+            Box::new(|this, _, cx| {
+                this.cancel_archive_import(cx);
+            })
+        }),
+        cx,
+    )
+    .into_any_element()
+}
@@ -36,10 +36,13 @@ impl Render for ChannelMenuView {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
         let weak = self.screen.clone();
-        let selected: Vec<ReleaseChannel> = self
+        let (channels, selected): (Vec<ReleaseChannel>, Vec<ReleaseChannel>) = self
             .screen
             .upgrade()
-            .map(|s| s.read(cx).state.versions.selected_channels.clone())
+            .map(|s| {
+                let versions = &s.read(cx).state.versions;
+                (versions.channels.clone(), versions.selected_channels.clone())
+            })
             .unwrap_or_default();
 
         v_flex()
@@ -61,10 +64,9 @@ impl Render for ChannelMenuView {
                     .text_color(theme.muted_foreground)
                     .child("Channels"),
             )
-            .children(ReleaseChannel::ALL.iter().map(|channel| {
-                let checked = selected.contains(channel);
+            .children(channels.into_iter().map(|channel| {
+                let checked = selected.contains(&channel);
                 let weak = weak.clone();
-                let channel = *channel;
                 h_flex()
                     .id(format!("channel-row-{}", channel.label()))
                     .w_full()
@@ -76,12 +78,12 @@ impl Render for ChannelMenuView {
                     .px_1()
                     .child(
                         Checkbox::new(format!("channel-check-{}", channel.label()))
-                            .label(channel.label())
+                            .label(channel.label().to_string())
                             .checked(checked)
                             .on_click(move |selected, _, cx| {
                                 if let Some(e) = weak.upgrade() {
                                     let _ = e.update(cx, |this, cx| {
-                                        this.toggle_channel(channel, *selected, cx);
+                                        this.toggle_channel(channel.clone(), *selected, cx);
                                     });
                                 }
                             }),
@@ -6,11 +6,22 @@ use ui::{
     StyledExt as _,
 };
 
-use crate::core::types::{DownloadItem, DownloadKind, DownloadStatus, format_bytes};
+use crate::core::events::{DownloadAction, DownloadActionRequested, DownloadLimitsChanged};
+use crate::core::types::{DownloadFailure, DownloadItem, DownloadKind, DownloadStatus, format_bytes};
+use crate::service::download_queue_service::DEFAULT_MAX_CONCURRENT;
+
+/// Choices offered by the "at a time" limit button, cycled in order.
+const CONCURRENCY_CHOICES: [usize; 4] = [1, 2, 3, 4];
+
+/// Choices offered by the per-download speed limit button; 0 is unlimited.
+const BANDWIDTH_CHOICES: [u64; 4] = [0, 1024 * 1024, 5 * 1024 * 1024, 20 * 1024 * 1024];
 
 pub struct DownloadManagerView {
     focus_handle: FocusHandle,
+    /// Items in queue order: the next queued item to start comes first.
     pub items: Vec<DownloadItem>,
+    pub max_concurrent: usize,
+    pub max_bps_per_download: u64,
 }
 
 impl DownloadManagerView {
@@ -18,6 +29,8 @@ impl DownloadManagerView {
         Self {
             focus_handle: cx.focus_handle(),
             items: Vec::new(),
+            max_concurrent: DEFAULT_MAX_CONCURRENT,
+            max_bps_per_download: 0,
         }
     }
 
@@ -28,8 +41,62 @@ impl DownloadManagerView {
             .count()
     }
 
+    pub fn queued_count(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|i| matches!(i.status, DownloadStatus::Queued))
+            .count()
+    }
+
     pub fn add_item(&mut self, item: DownloadItem) {
-        self.items.insert(0, item);
+        self.items.retain(|i| i.id != item.id);
+        self.items.push(item);
+    }
+
+    pub fn queue(&mut self, id: &str) {
+        if let Some(item) = self.items.iter_mut().find(|i| i.id == id) {
+            item.status = DownloadStatus::Queued;
+        }
+    }
+
+    /// Put the items whose ids appear in `order` into that order, leaving
+    /// every other item where it is.
+    pub fn reorder(&mut self, order: &[String]) {
+        let slots: Vec<usize> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| order.contains(&i.id))
+            .map(|(index, _)| index)
+            .collect();
+        let mut ordered: Vec<DownloadItem> = order
+            .iter()
+            .filter_map(|id| self.items.iter().find(|i| &i.id == id).cloned())
+            .collect();
+        for (slot, item) in slots.into_iter().zip(ordered.drain(..)) {
+            self.items[slot] = item;
+        }
+    }
+
+    fn cycle_limits(&mut self, concurrency: bool, cx: &mut Context<Self>) {
+        if concurrency {
+            let next = CONCURRENCY_CHOICES
+                .iter()
+                .position(|c| *c == self.max_concurrent)
+                .map_or(0, |i| (i + 1) % CONCURRENCY_CHOICES.len());
+            self.max_concurrent = CONCURRENCY_CHOICES[next];
+        } else {
+            let next = BANDWIDTH_CHOICES
+                .iter()
+                .position(|c| *c == self.max_bps_per_download)
+                .map_or(0, |i| (i + 1) % BANDWIDTH_CHOICES.len());
+            self.max_bps_per_download = BANDWIDTH_CHOICES[next];
+        }
+        cx.emit(DownloadLimitsChanged {
+            max_concurrent: self.max_concurrent,
+            max_bps_per_download: self.max_bps_per_download,
+        });
+        cx.notify();
     }
 
     pub fn update_progress(
@@ -54,20 +121,40 @@ impl DownloadManagerView {
         }
     }
 
-    pub fn fail(&mut self, id: &str, error: String) {
+    pub fn pause(&mut self, id: &str, bytes_downloaded: u64, total_bytes: u64) {
+        if let Some(item) = self.items.iter_mut().find(|i| i.id == id) {
+            item.status = DownloadStatus::Paused {
+                bytes_downloaded,
+                total_bytes,
+            };
+        }
+    }
+
+    pub fn cancel(&mut self, id: &str) {
+        if let Some(item) = self.items.iter_mut().find(|i| i.id == id) {
+            item.status = DownloadStatus::Cancelled;
+        }
+    }
+
+    pub fn fail(&mut self, id: &str, error: impl Into<DownloadFailure>) {
         if let Some(item) = self.items.iter_mut().find(|i| i.id == id) {
-            item.status = DownloadStatus::Failed(error);
+            item.status = DownloadStatus::Failed(error.into());
         }
     }
 
     pub fn clear_completed(&mut self) {
         self.items.retain(|i| {
-            !matches!(i.status, DownloadStatus::Complete | DownloadStatus::Failed(_))
+            !matches!(
+                i.status,
+                DownloadStatus::Complete | DownloadStatus::Cancelled | DownloadStatus::Failed(_)
+            )
         });
     }
 }
 
 impl EventEmitter<DismissEvent> for DownloadManagerView {}
+impl EventEmitter<DownloadActionRequested> for DownloadManagerView {}
+impl EventEmitter<DownloadLimitsChanged> for DownloadManagerView {}
 
 impl Focusable for DownloadManagerView {
     fn focus_handle(&self, _cx: &App) -> FocusHandle {
@@ -80,6 +167,12 @@ impl Render for DownloadManagerView {
         let theme = cx.theme();
         let items = self.items.clone();
         let active_count = self.active_count();
+        let queued_count = self.queued_count();
+        let speed_limit = if self.max_bps_per_download == 0 {
+            "No speed limit".to_string()
+        } else {
+            format!("{}/s each", format_bytes(self.max_bps_per_download))
+        };
 
         v_flex()
             .id("download-manager-panel")
@@ -118,10 +211,12 @@ impl Render for DownloadManagerView {
                                     .text_sm()
                                     .font_weight(gpui::FontWeight::SEMIBOLD)
                                     .text_color(theme.foreground)
-                                    .child(if active_count > 0 {
-                                        format!("Downloads ({})", active_count)
-                                    } else {
-                                        "Downloads".to_string()
+                                    .child(match (active_count, queued_count) {
+                                        (0, 0) => "Downloads".to_string(),
+                                        (active, 0) => format!("Downloads ({})", active),
+                                        (active, queued) => {
+                                            format!("Downloads ({}, {} queued)", active, queued)
+                                        }
                                     }),
                             ),
                     )
@@ -149,6 +244,44 @@ impl Render for DownloadManagerView {
                             ),
                     ),
             )
+            .child(
+                h_flex()
+                    .w_full()
+                    .items_center()
+                    .justify_between()
+                    .px_3()
+                    .py_1()
+                    .border_b_1()
+                    .border_color(theme.border.opacity(0.5))
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.muted_foreground)
+                            .child("Limits"),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                Button::new("dm-limit-concurrency")
+                                    .label(format!("{} at a time", self.max_concurrent))
+                                    .compact()
+                                    .ghost()
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.cycle_limits(true, cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("dm-limit-bandwidth")
+                                    .label(speed_limit)
+                                    .compact()
+                                    .ghost()
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.cycle_limits(false, cx);
+                                    })),
+                            ),
+                    ),
+            )
             .child(
                 v_flex()
                     .id("dm-items-list")
@@ -185,6 +318,16 @@ fn render_download_item(item: DownloadItem, cx: &mut Context<DownloadManagerView
     let theme = cx.theme();
     let label = item.label();
     let is_downloading = matches!(item.status, DownloadStatus::Downloading { .. });
+    let is_paused = matches!(item.status, DownloadStatus::Paused { .. });
+    let is_queued = matches!(item.status, DownloadStatus::Queued);
+    // Anything still queued can be held back, reordered or dropped. Once
+    // running, only engine downloads can be paused; template clones run to
+    // completion.
+    let engine = matches!(
+        item.kind,
+        DownloadKind::EngineVersion { .. } | DownloadKind::EngineModules { .. }
+    );
+    let controllable = is_queued || (engine && (is_downloading || is_paused));
     let progress = item.progress_fraction();
     let downloaded_text = item.downloaded_display();
     let speed_text = item.speed_display();
@@ -206,7 +349,8 @@ fn render_download_item(item: DownloadItem, cx: &mut Context<DownloadManagerView
                         .items_center()
                         .child(
                             Icon::new(match &item.kind {
-                                DownloadKind::EngineVersion { .. } => IconName::Package,
+                                DownloadKind::EngineVersion { .. }
+                                | DownloadKind::EngineModules { .. } => IconName::Package,
                                 DownloadKind::TemplateClone { .. } => IconName::Folder,
                             })
                             .size(px(14.))
@@ -235,6 +379,21 @@ fn render_download_item(item: DownloadItem, cx: &mut Context<DownloadManagerView
                                 .into_any_element()
                         }
                     }
+                    DownloadStatus::Queued => div()
+                        .text_xs()
+                        .text_color(theme.muted_foreground)
+                        .child("Queued")
+                        .into_any_element(),
+                    DownloadStatus::Paused { .. } => div()
+                        .text_xs()
+                        .text_color(theme.muted_foreground)
+                        .child("Paused")
+                        .into_any_element(),
+                    DownloadStatus::Cancelled => div()
+                        .text_xs()
+                        .text_color(theme.muted_foreground)
+                        .child("Cancelled")
+                        .into_any_element(),
                     DownloadStatus::Complete => {
                         h_flex()
                             .gap_1()
@@ -257,21 +416,25 @@ fn render_download_item(item: DownloadItem, cx: &mut Context<DownloadManagerView
                             .gap_1()
                             .items_center()
                             .child(
-                                Icon::new(IconName::Close)
-                                    .size(px(12.))
-                                    .text_color(theme.danger),
+                                Icon::new(if e.is_integrity_failure() {
+                                    IconName::Shield
+                                } else {
+                                    IconName::Close
+                                })
+                                .size(px(12.))
+                                .text_color(theme.danger),
                             )
                             .child(
                                 div()
                                     .text_xs()
                                     .text_color(theme.danger)
-                                    .child(e.clone()),
+                                    .child(e.to_string()),
                             )
                             .into_any_element()
                     }
                 }),
         )
-        .when(is_downloading, |this| {
+        .when(is_downloading || is_paused, |this| {
             this.child(
                 div()
                     .text_xs()
@@ -294,4 +457,70 @@ fn render_download_item(item: DownloadItem, cx: &mut Context<DownloadManagerView
                     ),
             )
         })
+        .when(controllable, |this| {
+            let (toggle_label, toggle_action) = if is_paused {
+                ("Resume", DownloadAction::Resume)
+            } else {
+                ("Pause", DownloadAction::Pause)
+            };
+            let toggle_id = item.id.clone();
+            let cancel_id = item.id.clone();
+            let (up_id, down_id) = (item.id.clone(), item.id.clone());
+            this.child(
+                h_flex()
+                    .w_full()
+                    .justify_end()
+                    .gap_1()
+                    .when(is_queued, |this| {
+                        this.child(
+                            Button::new(SharedString::from(format!("dm-up-{}", item.id)))
+                                .icon(IconName::ArrowUp)
+                                .compact()
+                                .ghost()
+                                .on_click(cx.listener(move |_, _, _, cx| {
+                                    cx.emit(DownloadActionRequested {
+                                        id: up_id.clone(),
+                                        action: DownloadAction::MoveUp,
+                                    });
+                                })),
+                        )
+                        .child(
+                            Button::new(SharedString::from(format!("dm-down-{}", item.id)))
+                                .icon(IconName::ArrowDown)
+                                .compact()
+                                .ghost()
+                                .on_click(cx.listener(move |_, _, _, cx| {
+                                    cx.emit(DownloadActionRequested {
+                                        id: down_id.clone(),
+                                        action: DownloadAction::MoveDown,
+                                    });
+                                })),
+                        )
+                    })
+                    .child(
+                        Button::new(SharedString::from(format!("dm-toggle-{}", item.id)))
+                            .label(toggle_label)
+                            .compact()
+                            .ghost()
+                            .on_click(cx.listener(move |_, _, _, cx| {
+                                cx.emit(DownloadActionRequested {
+                                    id: toggle_id.clone(),
+                                    action: toggle_action,
+                                });
+                            })),
+                    )
+                    .child(
+                        Button::new(SharedString::from(format!("dm-cancel-{}", item.id)))
+                            .label("Cancel")
+                            .compact()
+                            .ghost()
+                            .on_click(cx.listener(move |_, _, _, cx| {
+                                cx.emit(DownloadActionRequested {
+                                    id: cancel_id.clone(),
+                                    action: DownloadAction::Cancel,
+                                });
+                            })),
+                    ),
+            )
+        })
 }
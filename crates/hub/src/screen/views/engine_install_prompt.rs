@@ -16,6 +16,25 @@ pub fn render_engine_install_prompt(
     let Some(prompt) = screen.state.ui.engine_prompt.clone() else {
         return div().into_any_element();
     };
+    let (message, question, action) = if prompt.reinstall {
+        (
+            format!(
+                "“{}” is locked to a different build of engine “{}” than the one installed.",
+                prompt.project_name, prompt.required
+            ),
+            "Would you like to reinstall it?",
+            "Reinstall",
+        )
+    } else {
+        (
+            format!(
+                "“{}” requires engine “{}”, which isn’t installed.",
+                prompt.project_name, prompt.required
+            ),
+            "Would you like to install it automatically?",
+            "Install",
+        )
+    };
 
     render_modal(
         h_flex()
@@ -33,16 +52,13 @@ pub fn render_engine_install_prompt(
                 div()
                     .text_sm()
                     .text_color(theme.foreground)
-                    .child(format!(
-                        "“{}” requires engine “{}”, which isn’t installed.",
-                        prompt.project_name, prompt.required
-                    )),
+                    .child(message),
             )
             .child(
                 div()
                     .text_sm()
                     .text_color(theme.muted_foreground)
-                    .child("Would you like to install it automatically?"),
+                    .child(question),
             )
             .child(
                 h_flex()
@@ -60,7 +76,7 @@ pub fn render_engine_install_prompt(
                     )
                     .child(
                         Button::new("engine-prompt-install")
-                            .label("Install")
+                            .label(action)
                             .primary()
                             .compact()
                             .on_click(cx.listener(|this, _, _, cx| {
@@ -0,0 +1,193 @@
+use gpui::prelude::*;
+use gpui::*;
+use ui::{
+    button::{Button, ButtonVariants as _},
+    h_flex, v_flex, ActiveTheme as _, Icon, IconName,
+};
+
+use crate::core::types::{format_bytes, HubUpdateState};
+use crate::screen::EntryScreen;
+
+/// Strip under the title bar offering, and then tracking, an update of the
+/// hub itself. Renders nothing while there is no update to show.
+pub fn render_hub_update_banner(
+    screen: &mut EntryScreen,
+    cx: &mut Context<EntryScreen>,
+) -> AnyElement {
+    let theme = cx.theme();
+    let state = screen.state.ui.hub_update.clone();
+    let Some(info) = state.info().cloned() else {
+        return div().into_any_element();
+    };
+
+    let (icon, icon_color, message) = match &state {
+        HubUpdateState::Idle => return div().into_any_element(),
+        HubUpdateState::Available(info) => (
+            IconName::ArrowUp,
+            theme.accent,
+            format!(
+                "Pulsar Hub v{} is available (you have v{}) · {} download",
+                info.latest_version,
+                info.current_version,
+                format_bytes(info.download_bytes)
+            ),
+        ),
+        HubUpdateState::Installing { info, relaunch, .. } => (
+            IconName::Download,
+            theme.accent,
+            if *relaunch {
+                format!("Updating to v{}; the hub will restart when done…", info.latest_version)
+            } else {
+                format!("Downloading v{} in the background…", info.latest_version)
+            },
+        ),
+        HubUpdateState::Staged(info) => (
+            IconName::Check,
+            theme.success,
+            format!("v{} is installed and will be used the next time you open the hub.", info.latest_version),
+        ),
+        HubUpdateState::Failed { info, message } => (
+            IconName::WarningTriangle,
+            theme.danger,
+            format!("Updating to v{} failed: {}", info.latest_version, message),
+        ),
+    };
+
+    let progress_bar = match &state {
+        HubUpdateState::Installing { progress, .. } => {
+            let p = progress.lock().clone();
+            let fraction = if p.total_bytes > 0 {
+                (p.bytes_downloaded as f32 / p.total_bytes as f32).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            Some(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .items_center()
+                    .child(
+                        div()
+                            .flex_1()
+                            .h(px(3.))
+                            .rounded_full()
+                            .bg(theme.border)
+                            .overflow_hidden()
+                            .child(
+                                div()
+                                    .h_full()
+                                    .rounded_full()
+                                    .bg(theme.accent)
+                                    .w(relative(fraction)),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.muted_foreground)
+                            .child(format!(
+                                "{} / {}",
+                                format_bytes(p.bytes_downloaded),
+                                format_bytes(p.total_bytes)
+                            )),
+                    ),
+            )
+        }
+        _ => None,
+    };
+
+    let can_install = matches!(
+        state,
+        HubUpdateState::Available(_) | HubUpdateState::Failed { .. }
+    );
+    let can_dismiss = !matches!(state, HubUpdateState::Installing { .. });
+
+    let mut actions = h_flex().gap_2().items_center().child(
+        Button::new("hub-update-notes")
+            .label("Release notes")
+            .compact()
+            .ghost()
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.open_hub_release_notes(&info, cx);
+            })),
+    );
+    if can_install {
+        actions = actions
+            .child(
+                Button::new("hub-update-skip")
+                    .label("Skip this version")
+                    .compact()
+                    .ghost()
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.skip_hub_update(cx);
+                    })),
+            )
+            .child(
+                Button::new("hub-update-later")
+                    .label("On next launch")
+                    .compact()
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.start_hub_update(false, cx);
+                    })),
+            )
+            .child(
+                Button::new("hub-update-now")
+                    .label(if matches!(state, HubUpdateState::Failed { .. }) {
+                        "Retry"
+                    } else {
+                        "Update now"
+                    })
+                    .primary()
+                    .compact()
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.start_hub_update(true, cx);
+                    })),
+            );
+    }
+    if can_dismiss {
+        actions = actions.child(
+            Button::new("hub-update-dismiss")
+                .icon(IconName::Close)
+                .compact()
+                .ghost()
+                .tooltip("Dismiss")
+                .on_click(cx.listener(|this, _, _, cx| {
+                    this.dismiss_hub_update(cx);
+                })),
+        );
+    }
+
+    v_flex()
+        .id("hub-update-banner")
+        .w_full()
+        .px_4()
+        .py_2()
+        .gap_1p5()
+        .border_b_1()
+        .border_color(theme.border)
+        .bg(theme.secondary.opacity(0.3))
+        .child(
+            h_flex()
+                .w_full()
+                .gap_3()
+                .items_center()
+                .justify_between()
+                .child(
+                    h_flex()
+                        .flex_1()
+                        .min_w_0()
+                        .gap_2()
+                        .items_center()
+                        .child(Icon::new(icon).size(px(14.)).text_color(icon_color))
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(theme.foreground)
+                                .child(message),
+                        ),
+                )
+                .child(actions),
+        )
+        .children(progress_bar)
+        .into_any_element()
+}
@@ -0,0 +1,172 @@
+use gpui::prelude::*;
+use gpui::*;
+use ui::{
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    spinner::Spinner,
+    v_flex, ActiveTheme as _, Icon, IconName,
+};
+
+use crate::component::render_modal;
+use crate::core::types::{format_bytes, InstallCheckStage};
+use crate::screen::EntryScreen;
+use crate::service::installer_service::InstallReport;
+
+/// Broken files listed before the rest are summarised as "and N more".
+const MAX_LISTED_FILES: usize = 8;
+
+/// The "Verify / Repair" modal of an installed engine version.
+pub fn render_install_check(
+    screen: &mut EntryScreen,
+    cx: &mut Context<EntryScreen>,
+) -> gpui::AnyElement {
+    let theme = cx.theme();
+    let Some(check) = screen.state.ui.install_check.clone() else {
+        return div().into_any_element();
+    };
+
+    let busy = |label: String| {
+        h_flex()
+            .w_full()
+            .gap_2()
+            .items_center()
+            .justify_center()
+            .py_6()
+            .child(Spinner::new().color(theme.muted_foreground))
+            .child(div().text_sm().text_color(theme.muted_foreground).child(label))
+            .into_any_element()
+    };
+    let report_view = |report: &InstallReport, repaired: bool| {
+        if report.is_intact() {
+            return h_flex()
+                .gap_2()
+                .items_center()
+                .child(Icon::new(IconName::Check).size(px(14.)).text_color(theme.success))
+                .child(div().text_sm().text_color(theme.foreground).child(if repaired {
+                    format!("Repaired. All {} files match the release.", report.checked)
+                } else {
+                    format!("All {} files match the release.", report.checked)
+                }))
+                .into_any_element();
+        }
+        let broken: Vec<String> = report
+            .missing
+            .iter()
+            .map(|f| format!("Missing: {}", f))
+            .chain(report.modified.iter().map(|f| format!("Modified: {}", f)))
+            .collect();
+        v_flex()
+            .gap_2()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(
+                        Icon::new(IconName::WarningTriangle)
+                            .size(px(14.))
+                            .text_color(theme.warning),
+                    )
+                    .child(div().text_sm().text_color(theme.warning).child(format!(
+                        "{}{} of {} files are missing or modified.",
+                        if repaired { "Repair incomplete: " } else { "" },
+                        broken.len(),
+                        report.checked
+                    ))),
+            )
+            .child(
+                v_flex()
+                    .gap_0p5()
+                    .children(broken.iter().take(MAX_LISTED_FILES).map(|line| {
+                        div()
+                            .text_xs()
+                            .text_color(theme.muted_foreground)
+                            .overflow_hidden()
+                            .text_ellipsis()
+                            .child(line.clone())
+                    }))
+                    .when(broken.len() > MAX_LISTED_FILES, |this| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(theme.muted_foreground)
+                                .child(format!("…and {} more", broken.len() - MAX_LISTED_FILES)),
+                        )
+                    }),
+            )
+            .into_any_element()
+    };
+
+    let (body, can_repair, can_close) = match &check.stage {
+        InstallCheckStage::Verifying => (busy("Checking files…".to_string()), false, false),
+        InstallCheckStage::Verified(report) => (report_view(report, false), !report.is_intact(), true),
+        InstallCheckStage::Repairing(progress) => {
+            let p = progress.lock().clone();
+            let label = if p.total_bytes > 0 {
+                format!(
+                    "Downloading {} of {}…",
+                    format_bytes(p.bytes_downloaded),
+                    format_bytes(p.total_bytes)
+                )
+            } else {
+                "Repairing…".to_string()
+            };
+            (busy(label), false, false)
+        }
+        InstallCheckStage::Repaired(report) => (report_view(report, true), false, true),
+        InstallCheckStage::Failed(error) => (
+            div()
+                .text_sm()
+                .text_color(theme.danger)
+                .child(error.clone())
+                .into_any_element(),
+            false,
+            true,
+        ),
+    };
+
+    render_modal(
+        h_flex()
+            .gap_2()
+            .items_center()
+            .child(Icon::new(IconName::Shield).size(px(18.)).text_color(theme.accent))
+            .child(div().child(format!("Verify · v{}", check.version))),
+        v_flex()
+            .gap_4()
+            .child(body)
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("install-check-close")
+                            .label("Close")
+                            .compact()
+                            .ghost()
+                            .disabled(!can_close)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.close_install_check(cx);
+                            })),
+                    )
+                    .when(can_repair, |this| {
+                        this.child(
+                            Button::new("install-check-repair")
+                                .label("Repair")
+                                .icon(IconName::Download)
+                                .primary()
+                                .compact()
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.repair_engine(cx);
+                                })),
+                        )
+                    }),
+            ),
+        can_close.then(|| -> Box<dyn Fn(&mut EntryScreen, &mut Window, &mut Context<EntryScreen>)> {
+            Box::new(|this, _, cx| {
+                this.close_install_check(cx);
+            })
+        }),
+        cx,
+    )
+    .into_any_element()
+}
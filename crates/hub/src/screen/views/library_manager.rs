@@ -0,0 +1,213 @@
+use gpui::prelude::*;
+use gpui::*;
+use ui::{
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    spinner::Spinner,
+    v_flex, ActiveTheme as _, Icon, IconName,
+};
+
+use crate::component::render_modal;
+use crate::screen::EntryScreen;
+use crate::service::engine_library_service::same_folder;
+
+/// The "Libraries" modal: the folders engines are installed into, or the
+/// choice of library when moving an install.
+pub fn render_library_manager(
+    screen: &mut EntryScreen,
+    cx: &mut Context<EntryScreen>,
+) -> gpui::AnyElement {
+    let theme = cx.theme();
+    let Some(manager) = screen.state.ui.library_manager.clone() else {
+        return div().into_any_element();
+    };
+    let moving = manager.moving.clone();
+    let busy = moving.as_ref().is_some_and(|m| m.in_progress);
+    let libraries = manager.libraries.clone();
+
+    let folders: Vec<_> = libraries
+        .all()
+        .into_iter()
+        .filter(|folder| {
+            // An install can't move into the library it's already in.
+            moving.as_ref().is_none_or(|m| {
+                m.install_path
+                    .parent()
+                    .is_none_or(|current| !same_folder(current, folder))
+            })
+        })
+        .collect();
+
+    let rows = v_flex()
+        .w_full()
+        .gap_1()
+        .when(folders.is_empty(), |this| {
+            this.child(
+                div()
+                    .text_sm()
+                    .text_color(theme.muted_foreground)
+                    .child("No other library. Add a folder first."),
+            )
+        })
+        .children(folders.into_iter().enumerate().map(|(idx, folder)| {
+            let is_default = libraries.is_default(&folder);
+            let is_custom = libraries.is_custom(&folder);
+            let actions = if moving.is_some() {
+                let f = folder.clone();
+                h_flex().child(
+                    Button::new(format!("library-move-{}", idx))
+                        .label("Move here")
+                        .compact()
+                        .primary()
+                        .disabled(busy)
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.move_install_to(f.clone(), cx);
+                        })),
+                )
+            } else {
+                h_flex()
+                    .gap_1()
+                    .when(is_default, |this| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .px_2()
+                                .py_0p5()
+                                .rounded_md()
+                                .bg(theme.accent.opacity(0.12))
+                                .text_color(theme.accent)
+                                .child("Default"),
+                        )
+                    })
+                    .when(!is_default, |this| {
+                        // The platform folder is the default when none is set.
+                        let f = is_custom.then(|| folder.clone());
+                        this.child(
+                            Button::new(format!("library-default-{}", idx))
+                                .label("Set default")
+                                .compact()
+                                .ghost()
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.set_default_library(f.clone(), cx);
+                                })),
+                        )
+                    })
+                    .when(is_custom, |this| {
+                        let f = folder.clone();
+                        this.child(
+                            Button::new(format!("library-remove-{}", idx))
+                                .icon(IconName::Trash)
+                                .compact()
+                                .ghost()
+                                .tooltip("Stop using this folder. Engines in it stay on disk.")
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.remove_library(f.clone(), cx);
+                                })),
+                        )
+                    })
+            };
+            h_flex()
+                .w_full()
+                .gap_2()
+                .items_center()
+                .justify_between()
+                .py_1()
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .items_center()
+                        .min_w_0()
+                        .child(
+                            Icon::new(IconName::FolderClosed)
+                                .size(px(14.))
+                                .text_color(theme.muted_foreground),
+                        )
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(if folder.exists() {
+                                    theme.foreground
+                                } else {
+                                    theme.muted_foreground
+                                })
+                                .overflow_hidden()
+                                .text_ellipsis()
+                                .child(folder.display().to_string()),
+                        ),
+                )
+                .child(actions)
+        }));
+
+    let title = match &moving {
+        Some(m) => format!("Move v{}", m.version),
+        None => "Engine Libraries".to_string(),
+    };
+    let description = if moving.is_some() {
+        "Choose the library to move this engine to."
+    } else {
+        "Engines are found in all of these folders. New ones are installed into the default."
+    };
+
+    render_modal(
+        h_flex()
+            .gap_2()
+            .items_center()
+            .child(Icon::new(IconName::HardDrive).size(px(18.)).text_color(theme.accent))
+            .child(div().child(title)),
+        v_flex()
+            .gap_4()
+            .child(div().text_sm().text_color(theme.muted_foreground).child(description))
+            .child(rows)
+            .when_some(manager.error.clone(), |this, error| {
+                this.child(div().text_sm().text_color(theme.danger).child(error))
+            })
+            .when(busy, |this| {
+                this.child(
+                    h_flex()
+                        .gap_2()
+                        .items_center()
+                        .child(Spinner::new().color(theme.muted_foreground))
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(theme.muted_foreground)
+                                .child("Moving files…"),
+                        ),
+                )
+            })
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("library-close")
+                            .label("Close")
+                            .compact()
+                            .ghost()
+                            .disabled(busy)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.close_library_manager(cx);
+                            })),
+                    )
+                    .when(!busy, |this| {
+                        this.child(
+                            Button::new("library-add")
+                                .label("Add folder…")
+                                .icon(IconName::FolderOpen)
+                                .compact()
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.prompt_add_library(cx);
+                                })),
+                        )
+                    }),
+            ),
+        (!busy).then(|| -> Box<dyn Fn(&mut EntryScreen, &mut Window, &mut Context<EntryScreen>)> {
+            Box::new(|this, _, cx| {
+                this.close_library_manager(cx);
+            })
+        }),
+        cx,
+    )
+    .into_any_element()
+}
@@ -1,3 +1,4 @@
+pub mod archive_import;
 pub mod channel_menu;
 pub mod clone_git;
 pub mod cloud_intro_modal;
@@ -5,6 +6,10 @@ pub mod cloud_projects;
 pub mod dependency_setup;
 pub mod download_manager;
 pub mod engine_install_prompt;
+pub mod hub_update_banner;
+pub mod install_check;
+pub mod library_manager;
+pub mod module_picker;
 pub mod new_project;
 pub mod onboarding;
 pub mod project_settings;
@@ -23,6 +28,7 @@ pub use cloud_intro_modal::render_cloud_intro_modal;
 pub use cloud_projects::render_cloud_projects;
 pub use dependency_setup::render_dependency_setup;
 pub use download_manager::DownloadManagerView;
+pub use hub_update_banner::render_hub_update_banner;
 pub use new_project::render_new_project_modal;
 pub use onboarding::render_onboarding;
 pub use project_settings::*;
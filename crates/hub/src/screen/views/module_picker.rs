@@ -0,0 +1,140 @@
+use gpui::prelude::*;
+use gpui::*;
+use ui::{
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
+    h_flex,
+    spinner::Spinner,
+    v_flex, ActiveTheme as _, Icon, IconName,
+};
+
+use crate::component::render_modal;
+use crate::screen::EntryScreen;
+
+/// Checklist of the modules of a release, shown before installing it or
+/// when changing the modules of an existing install.
+pub fn render_module_picker(
+    screen: &mut EntryScreen,
+    cx: &mut Context<EntryScreen>,
+) -> gpui::AnyElement {
+    let theme = cx.theme();
+    let Some(picker) = screen.state.ui.module_picker.clone() else {
+        return div().into_any_element();
+    };
+    let changing = picker.install_path.is_some();
+    let loading = picker.release.is_none() && picker.error.is_none();
+
+    let body = if let Some(error) = picker.error.clone() {
+        h_flex()
+            .w_full()
+            .gap_2()
+            .items_center()
+            .py_2()
+            .px_3()
+            .rounded_md()
+            .bg(theme.warning.opacity(0.12))
+            .child(
+                Icon::new(IconName::WarningTriangle)
+                    .size(px(14.))
+                    .text_color(theme.warning),
+            )
+            .child(div().text_sm().text_color(theme.warning).child(error))
+            .into_any_element()
+    } else if loading {
+        h_flex()
+            .w_full()
+            .gap_2()
+            .items_center()
+            .justify_center()
+            .py_6()
+            .child(Spinner::new().color(theme.muted_foreground))
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(theme.muted_foreground)
+                    .child("Loading modules…"),
+            )
+            .into_any_element()
+    } else {
+        v_flex()
+            .w_full()
+            .gap_1()
+            .children(picker.modules.iter().map(|module| {
+                let id = module.id.clone();
+                let checked = module.is_core() || picker.selected.contains(&module.id);
+                v_flex()
+                    .w_full()
+                    .py_1()
+                    .child(
+                        Checkbox::new(format!("module-check-{}", module.id))
+                            .label(module.name.clone())
+                            .checked(checked)
+                            .disabled(module.is_core())
+                            .on_click(cx.listener(move |this, selected: &bool, _, cx| {
+                                this.toggle_picker_module(id.clone(), *selected, cx);
+                            })),
+                    )
+                    .when(!module.description.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .pl_6()
+                                .text_xs()
+                                .text_color(theme.muted_foreground)
+                                .child(module.description.clone()),
+                        )
+                    })
+            }))
+            .into_any_element()
+    };
+
+    render_modal(
+        h_flex()
+            .gap_2()
+            .items_center()
+            .child(Icon::new(IconName::Box).size(px(18.)).text_color(theme.accent))
+            .child(div().child(format!("Modules · {}", picker.version))),
+        v_flex()
+            .gap_4()
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(theme.muted_foreground)
+                    .child(if changing {
+                        "Choose the modules this install should have. Unticked modules are removed."
+                    } else {
+                        "Choose what to install. Modules can be added or removed later."
+                    }),
+            )
+            .child(body)
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("module-picker-cancel")
+                            .label("Cancel")
+                            .compact()
+                            .ghost()
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.close_module_picker(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("module-picker-confirm")
+                            .label(if changing { "Apply" } else { "Install" })
+                            .primary()
+                            .compact()
+                            .disabled(picker.release.is_none())
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.confirm_module_picker(cx);
+                            })),
+                    ),
+            ),
+        Some(Box::new(|this, _, cx| {
+            this.close_module_picker(cx);
+        })),
+        cx,
+    )
+    .into_any_element()
+}
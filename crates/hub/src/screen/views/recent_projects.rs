@@ -154,6 +154,7 @@ fn render_project_card(
     let path_open = path.clone();
     let path_git = path.clone();
     let path_settings = path.clone();
+    let path_upgrade = path.clone();
     let path_remove = path.clone();
     let name = project.name.clone();
     let normalized = normalize_project_path(&path);
@@ -263,6 +264,19 @@ fn render_project_card(
                                     );
                                 })),
                         )
+                        .child(
+                            Button::new(SharedString::from(format!("upgrade-engine-{}", path)))
+                                .icon(IconName::ArrowUp)
+                                .compact()
+                                .ghost()
+                                .tooltip("Upgrade engine")
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.upgrade_project_engine(
+                                        std::path::PathBuf::from(&path_upgrade),
+                                        cx,
+                                    );
+                                })),
+                        )
                         .child(
                             Button::new(SharedString::from(format!("remove-{}", path)))
                                 .icon(IconName::Close)
@@ -8,6 +8,7 @@ use ui::{
     h_flex, v_flex, ActiveTheme as _, Icon, IconName, Selectable, StyledExt as _, text::TextView,
 };
 
+use crate::service::github_service::GitHubService;
 use crate::service::installer_service::{self, GitHubRelease};
 use crate::EntryScreen;
 
@@ -80,7 +81,7 @@ impl ReleaseListDelegate {
         let tag = release.tag_name.clone();
         let name = release.name.clone();
         let body = release.body.clone();
-        let has_asset = installer_service::find_platform_asset(&release).is_some();
+        let has_asset = installer_service::has_platform_build(&release);
         let already_installed = installed.contains(tag.trim_start_matches('v'));
         let weak = self.screen.clone();
         let details_view = self
@@ -144,7 +145,7 @@ impl ReleaseListDelegate {
                                         let weak = weak.clone();
                                         if let Some(e) = weak.upgrade() {
                                             let _ = e.update(cx, |this, cx| {
-                                                this.install_release_by_tag(install_tag.clone(), cx);
+                                                this.request_release_install(install_tag.clone(), cx);
                                             });
                                         }
                                     })
@@ -271,9 +272,14 @@ impl ListDelegate for ReleaseListDelegate {
                     .text_sm()
                     .text_color(cx.theme().muted_foreground)
                     .child(if fetching {
-                        "Fetching releases..."
+                        "Fetching releases...".to_string()
+                    } else if let Some(until) = GitHubService::rate_limited_until() {
+                        format!(
+                            "{}. Click Refresh after that to try again.",
+                            GitHubService::rate_limit_message(until)
+                        )
                     } else {
-                        "Could not load releases. Click Refresh to try again."
+                        "Could not load releases. Click Refresh to try again.".to_string()
                     }),
             )
     }
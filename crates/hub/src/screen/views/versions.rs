@@ -7,6 +7,7 @@ use ui::{
 };
 
 use crate::core::types::format_bytes;
+use crate::service::github_service::GitHubService;
 use crate::service::installer_service;
 use crate::EntryScreen;
 
@@ -68,6 +69,16 @@ fn render_installed_grid(
                                     this.refresh_versions(cx);
                                 })),
                         )
+                        .child(
+                            Button::new("btn-libraries")
+                                .label("Libraries")
+                                .icon(IconName::HardDrive)
+                                .ghost()
+                                .tooltip("Choose the folders engines are installed into")
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.open_library_manager(cx);
+                                })),
+                        )
                         .child(
                             Button::new("btn-install")
                                 .label("Install")
@@ -80,6 +91,16 @@ fn render_installed_grid(
                                     cx.notify();
                                 })),
                         )
+                        .child(
+                            Button::new("btn-import-archive")
+                                .label("Import archive…")
+                                .icon(IconName::Package)
+                                .ghost()
+                                .tooltip("Install an engine from a .zip or .tar.gz downloaded by hand")
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.prompt_import_archive(window, cx);
+                                })),
+                        )
                         .child(
                             Button::new("btn-add-src")
                                 .label("Add src")
@@ -124,9 +145,18 @@ fn render_installed_grid(
                         .children(installed.iter().enumerate().map(|(idx, ver)| {
                     let version = ver.metadata.version.clone();
                     let date = ver.metadata.install_date.clone();
-                    let size = format_bytes(ver.disk_size_bytes);
+                    let size = if ver.shared_bytes > 0 {
+                        format!(
+                            "{} unique · {} shared",
+                            format_bytes(ver.unique_bytes()),
+                            format_bytes(ver.shared_bytes)
+                        )
+                    } else {
+                        format_bytes(ver.disk_size_bytes)
+                    };
                     let path = ver.metadata.install_path.clone();
                     let path_clone = path.clone();
+                    let modules = ver.metadata.modules.clone();
 
                     v_flex()
                         .id(format!("version-card-{}", idx))
@@ -236,6 +266,54 @@ fn render_installed_grid(
                                                     installer_service::open_install_dir(&p);
                                                 })
                                         })
+                                        .when(!version.eq_ignore_ascii_case("src"), |this| {
+                                            let v = version.clone();
+                                            let p = path_clone.clone();
+                                            this.child(
+                                                Button::new(format!("verify-{}", idx))
+                                                    .icon(IconName::Shield)
+                                                    .compact()
+                                                    .ghost()
+                                                    .tooltip("Verify / Repair")
+                                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                                        this.verify_engine(v.clone(), p.clone(), cx);
+                                                    })),
+                                            )
+                                        })
+                                        .when(!version.eq_ignore_ascii_case("src"), |this| {
+                                            let v = version.clone();
+                                            let p = path_clone.clone();
+                                            this.child(
+                                                Button::new(format!("move-{}", idx))
+                                                    .icon(IconName::HardDrive)
+                                                    .compact()
+                                                    .ghost()
+                                                    .tooltip("Move to another library")
+                                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                                        this.open_move_install(v.clone(), p.clone(), cx);
+                                                    })),
+                                            )
+                                        })
+                                        .when(!modules.is_empty(), |this| {
+                                            let v = version.clone();
+                                            let p = path_clone.clone();
+                                            let modules = modules.clone();
+                                            this.child(
+                                                Button::new(format!("modules-{}", idx))
+                                                    .icon(IconName::Box)
+                                                    .compact()
+                                                    .ghost()
+                                                    .tooltip("Add or remove modules")
+                                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                                        this.manage_engine_modules(
+                                                            v.clone(),
+                                                            p.clone(),
+                                                            modules.clone(),
+                                                            cx,
+                                                        );
+                                                    })),
+                                            )
+                                        })
                                         .child({
                                             let v = version.clone();
                                             Button::new(format!("remove-{}", idx))
@@ -355,6 +433,32 @@ fn render_install_modal(
                 ),
         )
         .child(div().w_full().h(px(1.0)).bg(theme.border))
+        .when_some(GitHubService::rate_limited_until(), |this, until| {
+            this.child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .items_center()
+                    .py_2()
+                    .px_3()
+                    .rounded_md()
+                    .bg(theme.warning.opacity(0.12))
+                    .child(
+                        Icon::new(IconName::WarningTriangle)
+                            .size(px(14.))
+                            .text_color(theme.warning),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(theme.warning)
+                            .child(format!(
+                                "{}. Sign in or set GITHUB_TOKEN for a higher limit.",
+                                GitHubService::rate_limit_message(until)
+                            )),
+                    ),
+            )
+        })
         .child(
             v_flex()
                 .id("install-releases-list")
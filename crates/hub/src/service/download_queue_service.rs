@@ -0,0 +1,226 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::service::installer_service::GitHubRelease;
+
+/// Downloads allowed to run at once unless the user picks otherwise.
+pub const DEFAULT_MAX_CONCURRENT: usize = 2;
+
+/// What a queue entry downloads. Carries everything needed to start it again
+/// after a restart, without waiting for release lists to load.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum QueuedJob {
+    EngineVersion {
+        release: GitHubRelease,
+        /// Modules picked for a release that ships in parts; empty for its
+        /// defaults or a single-archive release.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        modules: Vec<String>,
+        /// Where it installs to, fixed when queued so changing the default
+        /// library doesn't split a started download. Absent in queues saved
+        /// by older hubs, which install into the default library.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        install_path: Option<PathBuf>,
+    },
+    /// Add or remove modules of an existing install.
+    EngineModules {
+        release: GitHubRelease,
+        install_path: PathBuf,
+        modules: Vec<String>,
+    },
+    TemplateClone {
+        name: String,
+        repo_url: String,
+        target: PathBuf,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueuedDownload {
+    /// Same id as the download manager item.
+    pub id: String,
+    pub job: QueuedJob,
+    /// Held back by the user; skipped until resumed.
+    #[serde(default)]
+    pub paused: bool,
+    /// Has been started at least once, so partial data may be on disk.
+    #[serde(default)]
+    pub started: bool,
+}
+
+/// Pending, paused and in-progress downloads, in the order they start,
+/// persisted so they survive a restart of the hub.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DownloadQueue {
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: usize,
+    /// Bandwidth cap applied to each engine download, in bytes per second.
+    /// Zero means unlimited.
+    #[serde(default)]
+    pub max_bps_per_download: u64,
+    #[serde(default)]
+    pub entries: Vec<QueuedDownload>,
+}
+
+fn default_max_concurrent() -> usize {
+    DEFAULT_MAX_CONCURRENT
+}
+
+impl Default for DownloadQueue {
+    fn default() -> Self {
+        Self {
+            max_concurrent: DEFAULT_MAX_CONCURRENT,
+            max_bps_per_download: 0,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl DownloadQueue {
+    fn path() -> PathBuf {
+        directories::ProjectDirs::from("com", "Pulsar", "Pulsar_Engine")
+            .map(|d| d.data_dir().join("download_queue.json"))
+            .unwrap_or_else(|| PathBuf::from("download_queue.json"))
+    }
+
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            let _ = std::fs::write(path, json);
+        }
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.entries.iter().any(|e| e.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut QueuedDownload> {
+        self.entries.iter_mut().find(|e| e.id == id)
+    }
+
+    /// Append a download to the end of the queue. Returns false if an entry
+    /// with the same id is already queued.
+    pub fn push(&mut self, id: String, job: QueuedJob) -> bool {
+        if self.contains(&id) {
+            return false;
+        }
+        self.entries.push(QueuedDownload {
+            id,
+            job,
+            paused: false,
+            started: false,
+        });
+        true
+    }
+
+    pub fn remove(&mut self, id: &str) -> Option<QueuedDownload> {
+        let index = self.entries.iter().position(|e| e.id == id)?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Swap an entry with its neighbour, towards the front of the queue when
+    /// `up` is set. Returns false if it can't move that way.
+    pub fn move_entry(&mut self, id: &str, up: bool) -> bool {
+        let Some(index) = self.entries.iter().position(|e| e.id == id) else {
+            return false;
+        };
+        let other = if up {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1).filter(|i| *i < self.entries.len())
+        };
+        match other {
+            Some(other) => {
+                self.entries.swap(index, other);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Entries to start now, in queue order, given whether each entry is
+    /// already running. Template clones share a single progress slot, so at
+    /// most one runs at a time.
+    pub fn ready(&self, is_running: impl Fn(&QueuedDownload) -> bool) -> Vec<QueuedDownload> {
+        let running: Vec<_> = self.entries.iter().filter(|e| is_running(e)).collect();
+        let mut clone_running = running
+            .iter()
+            .any(|e| matches!(e.job, QueuedJob::TemplateClone { .. }));
+        let mut free = self.max_concurrent.max(1).saturating_sub(running.len());
+
+        let mut out = Vec::new();
+        for entry in &self.entries {
+            if free == 0 {
+                break;
+            }
+            if entry.paused || is_running(entry) {
+                continue;
+            }
+            if matches!(entry.job, QueuedJob::TemplateClone { .. }) {
+                if clone_running {
+                    continue;
+                }
+                clone_running = true;
+            }
+            out.push(entry.clone());
+            free -= 1;
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clone_job(name: &str) -> QueuedJob {
+        QueuedJob::TemplateClone {
+            name: name.to_string(),
+            repo_url: format!("https://example.com/{}.git", name),
+            target: PathBuf::from(name),
+        }
+    }
+
+    #[test]
+    fn ready_respects_limit_order_and_single_clone() {
+        let mut queue = DownloadQueue {
+            max_concurrent: 2,
+            ..Default::default()
+        };
+        for id in ["a", "b", "c", "d"] {
+            assert!(queue.push(id.to_string(), clone_job(id)));
+        }
+        assert!(!queue.push("a".to_string(), clone_job("a")));
+
+        // Only one clone may run at a time.
+        let ids = |v: Vec<QueuedDownload>| v.into_iter().map(|e| e.id).collect::<Vec<_>>();
+        assert_eq!(ids(queue.ready(|_| false)), ["a"]);
+        assert!(queue.ready(|e| e.id == "a").is_empty());
+
+        queue.get_mut("a").unwrap().paused = true;
+        assert!(queue.move_entry("d", true));
+        assert!(!queue.move_entry("a", true));
+        assert_eq!(ids(queue.ready(|_| false)), ["b"]);
+        assert_eq!(
+            queue.entries.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(),
+            ["a", "b", "d", "c"]
+        );
+
+        queue.max_concurrent = 1;
+        assert!(queue.ready(|e| e.id == "b").is_empty());
+        assert_eq!(queue.remove("b").map(|e| e.id).as_deref(), Some("b"));
+        assert_eq!(ids(queue.ready(|_| false)), ["d"]);
+    }
+}
@@ -0,0 +1,122 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Folders holding installed engine versions, one per subdirectory.
+///
+/// The platform's own locations are always searched; users can add more
+/// (e.g. on a separate drive) and pick where new engines are installed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EngineLibraries {
+    /// Library folders added by the user, in the order they were added.
+    #[serde(default)]
+    pub folders: Vec<PathBuf>,
+    /// Where new engines are installed. The platform default when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<PathBuf>,
+}
+
+impl EngineLibraries {
+    fn path() -> PathBuf {
+        directories::ProjectDirs::from("com", "Pulsar", "Pulsar_Engine")
+            .map(|d| d.data_dir().join("engine_libraries.json"))
+            .unwrap_or_else(|| PathBuf::from("engine_libraries.json"))
+    }
+
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json).map_err(|e| e.to_string())
+    }
+
+    /// Add `folder` as a library. Returns false if it already is one.
+    pub fn add(&mut self, folder: PathBuf) -> bool {
+        if self.all().iter().any(|f| same_folder(f, &folder)) {
+            return false;
+        }
+        self.folders.push(folder);
+        true
+    }
+
+    /// Stop searching `folder`. Engines in it stay on disk. New installs go
+    /// back to the platform default if it was the default library.
+    pub fn remove(&mut self, folder: &Path) {
+        self.folders.retain(|f| !same_folder(f, folder));
+        if self.default.as_deref().is_some_and(|d| same_folder(d, folder)) {
+            self.default = None;
+        }
+    }
+
+    /// Install new engines into `folder`, or the platform default for `None`.
+    pub fn set_default(&mut self, folder: Option<PathBuf>) {
+        self.default = folder;
+    }
+
+    /// Where new engines are installed.
+    pub fn default_folder(&self) -> PathBuf {
+        self.default.clone().unwrap_or_else(platform_default_folder)
+    }
+
+    /// Whether new engines are installed into `folder`.
+    pub fn is_default(&self, folder: &Path) -> bool {
+        same_folder(&self.default_folder(), folder)
+    }
+
+    /// Whether `folder` is one the user added, as opposed to a platform one.
+    pub fn is_custom(&self, folder: &Path) -> bool {
+        self.folders.iter().any(|f| same_folder(f, folder))
+    }
+
+    /// Every library folder: the platform ones, then the user's.
+    pub fn all(&self) -> Vec<PathBuf> {
+        let mut all = crate::service::installer_service::platform_search_roots();
+        for folder in &self.folders {
+            if !all.iter().any(|f| same_folder(f, folder)) {
+                all.push(folder.clone());
+            }
+        }
+        all
+    }
+}
+
+fn platform_default_folder() -> PathBuf {
+    crate::service::installer_service::platform_default_install_path()
+}
+
+/// Whether `a` and `b` name the same folder, following symlinks.
+pub fn same_folder(a: &Path, b: &Path) -> bool {
+    let canonical = |p: &Path| std::fs::canonicalize(p).unwrap_or_else(|_| p.to_path_buf());
+    a == b || canonical(a) == canonical(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removing_the_default_library_falls_back_to_the_platform_one() {
+        let ssd = PathBuf::from("/mnt/ssd/engines");
+        let mut libraries = EngineLibraries::default();
+        assert!(libraries.add(ssd.clone()));
+        assert!(!libraries.add(ssd.clone()));
+        libraries.set_default(Some(ssd.clone()));
+        assert_eq!(libraries.default_folder(), ssd);
+        assert!(libraries.is_default(&ssd));
+        assert!(libraries.is_custom(&ssd));
+        assert_eq!(libraries.all().last(), Some(&ssd));
+
+        libraries.remove(&ssd);
+        assert!(libraries.folders.is_empty());
+        assert_eq!(libraries.default_folder(), platform_default_folder());
+    }
+}
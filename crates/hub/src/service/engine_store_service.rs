@@ -0,0 +1,239 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::service::installer_service::{recorded_file_hashes, sha256_file};
+
+/// Per-install record of which files were linked to which store blobs.
+pub const STORE_MANIFEST_FILE: &str = ".pulsar-store.json";
+
+/// Files smaller than this stay private to their install. They make up a
+/// tiny share of an engine's size, and keeping them unlinked means an edited
+/// config file can never leak into other versions.
+const MIN_SHARED_SIZE: u64 = 64 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredFile {
+    sha256: String,
+    size: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoreManifest {
+    /// Install-relative path (with `/` separators) to its blob.
+    files: BTreeMap<String, StoredFile>,
+}
+
+impl StoreManifest {
+    fn load(install_dir: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(install_dir.join(STORE_MANIFEST_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+}
+
+/// Content-addressed store shared by every installed engine version.
+///
+/// Large files are hashed after an install finishes and hard-linked to a blob
+/// under `blobs/<sha256>`, so identical files across versions take up disk
+/// space once. Blobs are dropped once no install's manifest mentions them;
+/// since installs hold their own links, that never touches a live file.
+pub struct EngineStoreService;
+
+impl EngineStoreService {
+    fn dir() -> PathBuf {
+        directories::ProjectDirs::from("com", "Pulsar", "Pulsar_Engine")
+            .map(|d| d.data_dir().join("engine_store"))
+            .unwrap_or_else(|| PathBuf::from("engine_store"))
+    }
+
+    fn blob_path(store: &Path, sha256: &str) -> PathBuf {
+        store.join("blobs").join(&sha256[..2]).join(sha256)
+    }
+
+    /// Move the large files of the install at `install_dir` into the store,
+    /// replacing each with a hard link to its blob. Files the filesystem
+    /// can't link (e.g. when the store is on another drive) stay as they are.
+    /// Returns how many bytes now share a blob with another install.
+    pub fn adopt(install_dir: &Path) -> Result<u64, String> {
+        Self::adopt_into(&Self::dir(), install_dir)
+    }
+
+    fn adopt_into(store: &Path, install_dir: &Path) -> Result<u64, String> {
+        // Hashes recorded at install time save reading everything twice.
+        let recorded = recorded_file_hashes(install_dir).unwrap_or_default();
+        let mut manifest = StoreManifest::default();
+        let mut shared = 0;
+        for entry in WalkDir::new(install_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.len() < MIN_SHARED_SIZE {
+                continue;
+            }
+            let Some(rel) = entry
+                .path()
+                .strip_prefix(install_dir)
+                .ok()
+                .and_then(|p| p.to_str())
+                .map(|p| p.replace('\\', "/"))
+            else {
+                continue;
+            };
+            let sha256 = match recorded.get(&rel) {
+                Some(sha256) => sha256.clone(),
+                None => sha256_file(entry.path()).map_err(|e| e.to_string())?,
+            };
+            match Self::link(store, entry.path(), &sha256, &meta) {
+                Ok(existed) => {
+                    if existed {
+                        shared += meta.len();
+                    }
+                    manifest.files.insert(rel, StoredFile { sha256, size: meta.len() });
+                }
+                Err(e) => tracing::debug!("Not sharing {}: {}", entry.path().display(), e),
+            }
+        }
+
+        let json = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+        std::fs::write(install_dir.join(STORE_MANIFEST_FILE), json).map_err(|e| e.to_string())?;
+        Ok(shared)
+    }
+
+    /// Make `file` a link to the blob for `sha256`, creating the blob from
+    /// `file` if it's new. Returns whether the blob already existed.
+    fn link(store: &Path, file: &Path, sha256: &str, meta: &std::fs::Metadata) -> std::io::Result<bool> {
+        let blob = Self::blob_path(store, sha256);
+        if let Ok(blob_meta) = std::fs::metadata(&blob) {
+            // A link shares its permissions, so an executable and a plain
+            // copy of the same bytes have to stay separate files.
+            if blob_meta.permissions() != meta.permissions() {
+                return Err(std::io::Error::other("permissions differ from the stored copy"));
+            }
+            let tmp = file.with_file_name(format!(
+                ".{}.pulsar-link",
+                file.file_name().unwrap_or_default().to_string_lossy()
+            ));
+            let _ = std::fs::remove_file(&tmp);
+            std::fs::hard_link(&blob, &tmp)?;
+            if let Err(e) = std::fs::rename(&tmp, file) {
+                let _ = std::fs::remove_file(&tmp);
+                return Err(e);
+            }
+            return Ok(true);
+        }
+        if let Some(parent) = blob.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::hard_link(file, &blob)?;
+        Ok(false)
+    }
+
+    /// Drop the blobs for `sha256s`, e.g. because their contents were found
+    /// damaged. Installs linking to them keep their own copies.
+    pub fn evict(sha256s: &[&str]) {
+        let store = Self::dir();
+        for sha256 in sha256s.iter().filter(|s| s.len() > 2) {
+            let _ = std::fs::remove_file(Self::blob_path(&store, sha256));
+        }
+    }
+
+    /// Bytes of each install in `install_dirs` that share a blob with at
+    /// least one other of them, in the same order.
+    pub fn shared_bytes(install_dirs: &[&Path]) -> Vec<u64> {
+        let manifests: Vec<Option<StoreManifest>> =
+            install_dirs.iter().map(|dir| StoreManifest::load(dir)).collect();
+        let mut users: HashMap<&str, usize> = HashMap::new();
+        for manifest in manifests.iter().flatten() {
+            let blobs: HashSet<&str> = manifest.files.values().map(|f| f.sha256.as_str()).collect();
+            for blob in blobs {
+                *users.entry(blob).or_default() += 1;
+            }
+        }
+        manifests
+            .iter()
+            .map(|manifest| {
+                manifest.as_ref().map_or(0, |m| {
+                    m.files
+                        .values()
+                        .filter(|f| users.get(f.sha256.as_str()).copied().unwrap_or(0) > 1)
+                        .map(|f| f.size)
+                        .sum()
+                })
+            })
+            .collect()
+    }
+
+    /// Delete every blob not referenced by one of `install_dirs`.
+    pub fn collect_garbage(install_dirs: &[&Path]) -> Result<(), String> {
+        Self::collect_garbage_in(&Self::dir(), install_dirs)
+    }
+
+    fn collect_garbage_in(store: &Path, install_dirs: &[&Path]) -> Result<(), String> {
+        let referenced: HashSet<String> = install_dirs
+            .iter()
+            .filter_map(|dir| StoreManifest::load(dir))
+            .flat_map(|m| m.files.into_values().map(|f| f.sha256))
+            .collect();
+        let blobs = store.join("blobs");
+        if !blobs.exists() {
+            return Ok(());
+        }
+        for entry in WalkDir::new(&blobs)
+            .min_depth(2)
+            .max_depth(2)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let name = entry.file_name().to_string_lossy();
+            if !referenced.contains(name.as_ref()) {
+                std::fs::remove_file(entry.path()).map_err(|e| e.to_string())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_files_share_a_blob_until_the_last_install_is_gone() {
+        let root = std::env::temp_dir().join(format!("pulsar-store-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let store = root.join("store");
+        let (a, b) = (root.join("a"), root.join("b"));
+        let big = vec![7u8; MIN_SHARED_SIZE as usize];
+        for dir in [&a, &b] {
+            std::fs::create_dir_all(dir.join("lib")).unwrap();
+            std::fs::write(dir.join("lib").join("engine.pak"), &big).unwrap();
+            std::fs::write(dir.join("config.toml"), "small").unwrap();
+        }
+
+        assert_eq!(EngineStoreService::adopt_into(&store, &a).unwrap(), 0);
+        assert_eq!(EngineStoreService::adopt_into(&store, &b).unwrap(), big.len() as u64);
+        assert_eq!(
+            EngineStoreService::shared_bytes(&[&a, &b]),
+            vec![big.len() as u64, big.len() as u64]
+        );
+        let blob_count = || WalkDir::new(store.join("blobs")).min_depth(2).into_iter().count();
+        assert_eq!(blob_count(), 1);
+
+        std::fs::remove_dir_all(&a).unwrap();
+        EngineStoreService::collect_garbage_in(&store, &[&b]).unwrap();
+        assert_eq!(blob_count(), 1);
+        assert_eq!(std::fs::read(b.join("lib").join("engine.pak")).unwrap(), big);
+
+        std::fs::remove_dir_all(&b).unwrap();
+        EngineStoreService::collect_garbage_in(&store, &[]).unwrap();
+        assert_eq!(blob_count(), 0);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local, TimeZone, Utc};
+use parking_lot::Mutex;
+
+/// Hosts that get the user's GitHub token. Release asset downloads redirect
+/// to other hosts and never need it.
+const AUTHENTICATED_HOSTS: [&str; 2] = ["api.github.com", "raw.githubusercontent.com"];
+
+/// Rate limits that reset within this long (typically secondary limits with
+/// a short `Retry-After`) are waited out instead of reported.
+const MAX_INLINE_WAIT: Duration = Duration::from_secs(30);
+
+/// When the current GitHub API rate limit resets, while one is in effect.
+static LIMITED_UNTIL: Mutex<Option<DateTime<Utc>>> = Mutex::new(None);
+
+pub struct GitHubService;
+
+impl GitHubService {
+    /// Token for GitHub requests: `GITHUB_TOKEN` if set, otherwise the token
+    /// of the signed-in profile.
+    pub fn token() -> Option<String> {
+        std::env::var("GITHUB_TOKEN")
+            .ok()
+            .filter(|t| !t.trim().is_empty())
+            .or_else(|| pulsar_auth::load_access_token().ok().flatten())
+    }
+
+    /// When the GitHub API will accept requests again, if it is currently
+    /// rate limiting us.
+    pub fn rate_limited_until() -> Option<DateTime<Local>> {
+        let mut limited = LIMITED_UNTIL.lock();
+        match *limited {
+            Some(until) if until > Utc::now() => Some(until.with_timezone(&Local)),
+            Some(_) => {
+                *limited = None;
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// The error shown while rate limited.
+    pub fn rate_limit_message(until: DateTime<Local>) -> String {
+        format!("GitHub rate limited until {}", until.format("%H:%M"))
+    }
+
+    /// GET `url`, authenticated when it is a GitHub API or raw content URL.
+    /// Fails fast with [`Self::rate_limit_message`] while the API is rate
+    /// limiting us instead of spending more requests.
+    pub fn get(
+        client: &reqwest::blocking::Client,
+        url: &str,
+    ) -> Result<reqwest::blocking::Response, String> {
+        Self::get_with_headers(client, url, reqwest::header::HeaderMap::new())
+    }
+
+    /// [`Self::get`] with extra request headers.
+    pub fn get_with_headers(
+        client: &reqwest::blocking::Client,
+        url: &str,
+        headers: reqwest::header::HeaderMap,
+    ) -> Result<reqwest::blocking::Response, String> {
+        let github = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| AUTHENTICATED_HOSTS.contains(&h)))
+            .unwrap_or(false);
+        if !github {
+            return client.get(url).headers(headers).send().map_err(|e| e.to_string());
+        }
+        if let Some(until) = Self::rate_limited_until() {
+            return Err(Self::rate_limit_message(until));
+        }
+
+        let mut token = Self::token();
+        let mut waited = false;
+        loop {
+            let mut request = client.get(url).headers(headers.clone());
+            if let Some(token) = &token {
+                request = request.bearer_auth(token);
+            }
+            let resp = request.send().map_err(|e| e.to_string())?;
+
+            if resp.status() == reqwest::StatusCode::UNAUTHORIZED && token.is_some() {
+                // Expired or revoked token: anonymous access still works.
+                tracing::warn!("GitHub rejected the stored token; retrying anonymously");
+                token = None;
+                continue;
+            }
+            let Some(until) = limited_until(&resp) else {
+                if resp.status().is_success() {
+                    *LIMITED_UNTIL.lock() = None;
+                }
+                return Ok(resp);
+            };
+            let wait = (until - Utc::now()).to_std().unwrap_or_default();
+            if !waited && wait <= MAX_INLINE_WAIT {
+                std::thread::sleep(wait);
+                waited = true;
+                continue;
+            }
+            tracing::warn!("GitHub API rate limit reached until {}", until);
+            *LIMITED_UNTIL.lock() = Some(until);
+            return Err(Self::rate_limit_message(until.with_timezone(&Local)));
+        }
+    }
+}
+
+/// When a rate-limited response says to try again, or `None` if `resp`
+/// wasn't rejected for rate limiting.
+fn limited_until(resp: &reqwest::blocking::Response) -> Option<DateTime<Utc>> {
+    let status = resp.status();
+    if status != reqwest::StatusCode::FORBIDDEN && status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+    let header = |name: &str| {
+        resp.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<i64>().ok())
+    };
+    if let Some(secs) = header("retry-after") {
+        return Some(Utc::now() + chrono::Duration::seconds(secs.max(1)));
+    }
+    if header("x-ratelimit-remaining") == Some(0) {
+        return header("x-ratelimit-reset")
+            .and_then(|reset| Utc.timestamp_opt(reset, 0).single())
+            .or_else(|| Some(Utc::now() + chrono::Duration::minutes(1)));
+    }
+    // Secondary limits without a Retry-After: GitHub asks for at least a
+    // minute between attempts.
+    (status == reqwest::StatusCode::TOO_MANY_REQUESTS)
+        .then(|| Utc::now() + chrono::Duration::minutes(1))
+}
@@ -0,0 +1,157 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::service::github_service::GitHubService;
+
+/// Validators and age of a cached body, stored next to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    #[serde(default)]
+    etag: Option<String>,
+    #[serde(default)]
+    last_modified: Option<String>,
+    /// Unix time the body was last confirmed current.
+    fetched_at: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub status: StatusCode,
+    pub body: String,
+    /// Served from the cache because the server couldn't be reached or
+    /// wouldn't answer.
+    pub stale: bool,
+}
+
+/// On-disk cache of GET responses keyed by URL, revalidated with
+/// `ETag`/`Last-Modified` and served stale when offline or rate limited.
+pub struct HttpCacheService;
+
+impl HttpCacheService {
+    fn dir() -> PathBuf {
+        directories::ProjectDirs::from("com", "Pulsar", "Pulsar_Engine")
+            .map(|d| d.cache_dir().join("http"))
+            .unwrap_or_else(|| PathBuf::from("http_cache"))
+    }
+
+    fn paths(url: &str) -> (PathBuf, PathBuf) {
+        use sha2::{Digest, Sha256};
+
+        let key = format!("{:x}", Sha256::digest(url.as_bytes()));
+        let dir = Self::dir();
+        (dir.join(format!("{}.json", key)), dir.join(format!("{}.body", key)))
+    }
+
+    fn load(url: &str) -> Option<(CacheEntry, String)> {
+        let (meta, body) = Self::paths(url);
+        let entry: CacheEntry = serde_json::from_str(&std::fs::read_to_string(meta).ok()?).ok()?;
+        // Guard against hash collisions, however unlikely.
+        if entry.url != url {
+            return None;
+        }
+        Some((entry, std::fs::read_to_string(body).ok()?))
+    }
+
+    fn store(entry: &CacheEntry, body: Option<&str>) {
+        let (meta, body_path) = Self::paths(&entry.url);
+        let _ = std::fs::create_dir_all(Self::dir());
+        if let Some(body) = body {
+            if std::fs::write(&body_path, body).is_err() {
+                return;
+            }
+        }
+        if let Ok(json) = serde_json::to_string(entry) {
+            let _ = std::fs::write(meta, json);
+        }
+    }
+
+    /// The last body cached for `url`, however old, without any request.
+    pub fn cached(url: &str) -> Option<String> {
+        Self::load(url).map(|(_, body)| body)
+    }
+
+    /// GET `url` as text through the cache. A cached body younger than
+    /// `max_age` is returned without a request; an older one is revalidated,
+    /// and served as-is if the request fails. Only successful responses are
+    /// cached.
+    pub fn get(
+        client: &reqwest::blocking::Client,
+        url: &str,
+        max_age: Duration,
+    ) -> Result<CachedResponse, String> {
+        let now = chrono::Utc::now().timestamp();
+        let cached = Self::load(url);
+        let hit = |body: &str, stale| CachedResponse {
+            status: StatusCode::OK,
+            body: body.to_string(),
+            stale,
+        };
+        let stale_or = |e: String| match &cached {
+            Some((_, body)) => {
+                tracing::warn!("Serving cached {} ({})", url, e);
+                Ok(hit(body, true))
+            }
+            None => Err(e),
+        };
+
+        let mut headers = HeaderMap::new();
+        if let Some((entry, body)) = &cached {
+            if now.saturating_sub(entry.fetched_at) < max_age.as_secs() as i64 {
+                return Ok(hit(body, false));
+            }
+            let validators = [(IF_NONE_MATCH, &entry.etag), (IF_MODIFIED_SINCE, &entry.last_modified)];
+            for (name, value) in validators {
+                if let Some(value) = value.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+                    headers.insert(name, value);
+                }
+            }
+        }
+
+        let resp = match GitHubService::get_with_headers(client, url, headers) {
+            Ok(resp) => resp,
+            Err(e) => return stale_or(e),
+        };
+        let status = resp.status();
+        if status == StatusCode::NOT_MODIFIED {
+            if let Some((mut entry, body)) = cached.clone() {
+                entry.fetched_at = now;
+                Self::store(&entry, None);
+                return Ok(hit(&body, false));
+            }
+        }
+        if status.is_server_error() {
+            return stale_or(format!("HTTP {}", status));
+        }
+
+        let header = |name| {
+            resp.headers()
+                .get(name)
+                .and_then(|v: &HeaderValue| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let (etag, last_modified) = (header(ETAG), header(LAST_MODIFIED));
+        let body = match resp.text() {
+            Ok(body) => body,
+            Err(e) => return stale_or(e.to_string()),
+        };
+        if status.is_success() {
+            let entry = CacheEntry {
+                url: url.to_string(),
+                etag,
+                last_modified,
+                fetched_at: now,
+            };
+            Self::store(&entry, Some(&body));
+        }
+        Ok(CachedResponse {
+            status,
+            body,
+            stale: false,
+        })
+    }
+}
@@ -0,0 +1,148 @@
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+/// An update of the hub itself, as reported by the host binary's updater.
+#[derive(Clone, Debug)]
+pub struct HubUpdateInfo {
+    pub current_version: String,
+    pub latest_version: String,
+    /// Markdown release notes for `latest_version` (may be empty).
+    pub release_notes: String,
+    /// Bytes that will be downloaded to apply the update.
+    pub download_bytes: u64,
+}
+
+/// Live progress of a hub update being downloaded and applied.
+#[derive(Clone, Debug, Default)]
+pub struct HubUpdateProgress {
+    pub bytes_downloaded: u64,
+    pub total_bytes: u64,
+    pub done: bool,
+    pub error: Option<String>,
+}
+
+/// Self-update hooks provided by the binary hosting the hub. The hub crate
+/// can't depend on the installer, so the installer registers an implementation
+/// with [`set_hub_updater`] at startup. All methods block and are called from
+/// background threads.
+pub trait HubUpdater: Send + Sync {
+    /// Look for a newer hub release. `Ok(None)` means up to date.
+    fn check(&self) -> Result<Option<HubUpdateInfo>, String>;
+
+    /// Download `update` and swap it in for the running executable, reporting
+    /// into `progress`. The new build takes effect on the next launch.
+    fn install(
+        &self,
+        update: &HubUpdateInfo,
+        progress: Arc<Mutex<HubUpdateProgress>>,
+    ) -> Result<(), String>;
+
+    /// Restart into the freshly installed build. Does not return.
+    fn relaunch(&self);
+}
+
+static HUB_UPDATER: OnceLock<Arc<dyn HubUpdater>> = OnceLock::new();
+
+/// Register the host binary's self-updater. Only the first call has an effect.
+pub fn set_hub_updater(updater: Arc<dyn HubUpdater>) {
+    let _ = HUB_UPDATER.set(updater);
+}
+
+pub fn hub_updater() -> Option<Arc<dyn HubUpdater>> {
+    HUB_UPDATER.get().cloned()
+}
+
+/// Run [`HubUpdater::install`], making sure `progress` ends up `done` however
+/// it finishes: an early error return or a panic included. The UI polls for
+/// `done`, so an install that never set it would spin forever.
+pub fn install_hub_update(
+    updater: &dyn HubUpdater,
+    update: &HubUpdateInfo,
+    progress: Arc<Mutex<HubUpdateProgress>>,
+) -> Result<(), String> {
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        updater.install(update, progress.clone())
+    }))
+    .unwrap_or_else(|_| Err("The updater crashed".to_string()));
+
+    let mut p = progress.lock();
+    p.done = true;
+    if let Err(e) = &result {
+        p.error.get_or_insert_with(|| e.clone());
+    }
+    result
+}
+
+/// User choices about hub updates, persisted across launches.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct HubUpdatePrefs {
+    /// A release the user chose to skip; no banner is shown for it.
+    #[serde(default)]
+    pub skipped_version: Option<String>,
+}
+
+impl HubUpdatePrefs {
+    fn path() -> PathBuf {
+        directories::ProjectDirs::from("com", "Pulsar", "Pulsar_Engine")
+            .map(|d| d.data_dir().join("hub_update.json"))
+            .unwrap_or_else(|| PathBuf::from("hub_update.json"))
+    }
+
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            let _ = std::fs::write(path, json);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FailingUpdater;
+
+    impl HubUpdater for FailingUpdater {
+        fn check(&self) -> Result<Option<HubUpdateInfo>, String> {
+            Ok(None)
+        }
+
+        fn install(
+            &self,
+            _update: &HubUpdateInfo,
+            _progress: Arc<Mutex<HubUpdateProgress>>,
+        ) -> Result<(), String> {
+            panic!("install thread died")
+        }
+
+        fn relaunch(&self) {}
+    }
+
+    #[test]
+    fn a_crashed_install_still_finishes_the_progress() {
+        let progress = Arc::new(Mutex::new(HubUpdateProgress::default()));
+        let info = HubUpdateInfo {
+            current_version: "0.1.7".into(),
+            latest_version: "0.1.8".into(),
+            release_notes: String::new(),
+            download_bytes: 0,
+        };
+        assert!(install_hub_update(&FailingUpdater, &info, progress.clone()).is_err());
+        let p = progress.lock();
+        assert!(p.done);
+        assert!(p.error.is_some());
+    }
+}
@@ -5,6 +5,12 @@ use std::sync::Arc;
 use std::time::Instant;
 use walkdir::WalkDir;
 
+use crate::service::engine_library_service::EngineLibraries;
+use crate::service::engine_store_service::EngineStoreService;
+use crate::service::github_service::GitHubService;
+use crate::service::http_cache_service::HttpCacheService;
+use crate::service::release_source_service::{ReleaseSource, ReleaseSourcesConfig};
+
 // ── Data Types ──────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,15 +18,38 @@ pub struct PulsarInstallMetadata {
     pub version: String,
     pub install_date: String,
     pub install_path: PathBuf,
+    /// SHA256 of the release's archive, recorded when it was checked
+    /// against a published checksum or signature. Delta installs record the
+    /// full platform archive they reproduce, not the delta.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_sha256: Option<String>,
+    /// Name of the release asset `archive_sha256` is the digest of: the full
+    /// platform archive, or the core archive of a module install.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_name: Option<String>,
+    /// Ids of the [`EngineModule`]s installed, for releases that publish a
+    /// module manifest. Empty for a single-archive install of everything.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modules: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct InstalledVersion {
     pub metadata: PulsarInstallMetadata,
     pub disk_size_bytes: u64,
+    /// Part of `disk_size_bytes` stored once and shared with other installed
+    /// versions through the engine store.
+    pub shared_bytes: u64,
     pub update_available: bool,
 }
 
+impl InstalledVersion {
+    /// Bytes that removing this version would free.
+    pub fn unique_bytes(&self) -> u64 {
+        self.disk_size_bytes.saturating_sub(self.shared_bytes)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitHubRelease {
     pub tag_name: String,
@@ -40,67 +69,72 @@ pub struct GitHubAsset {
     pub size: u64,
 }
 
-/// A release channel a user can opt into.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum ReleaseChannel {
-    /// Anything `>= 1.0.0` from the main Pulsar-Native repo.
+/// Which releases of its source a channel shows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelFilter {
+    /// Every release.
+    #[default]
+    All,
+    /// Anything `>= 1.0.0`.
     Stable,
-    /// Anything `< 1.0.0` from the main Pulsar-Native repo.
+    /// Anything `< 1.0.0`.
     Alpha,
-    /// Anything from the dedicated Nightly repo.
-    Nightly,
 }
 
-impl ReleaseChannel {
-    pub const ALL: [ReleaseChannel; 3] = [
-        ReleaseChannel::Stable,
-        ReleaseChannel::Alpha,
-        ReleaseChannel::Nightly,
-    ];
+/// A release channel a user can opt into: a named, filtered view of one
+/// release source. Channels are configured in `release_sources.json` (see
+/// [`crate::service::release_source_service::ReleaseSourcesConfig`]).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReleaseChannel {
+    pub name: String,
+    /// Id of the release source this channel lists.
+    pub source: String,
+    #[serde(default)]
+    pub filter: ChannelFilter,
+    /// Selected when the hub starts.
+    #[serde(default)]
+    pub enabled_by_default: bool,
+}
 
-    pub fn label(&self) -> &'static str {
-        match self {
-            ReleaseChannel::Stable => "Stable",
-            ReleaseChannel::Alpha => "Alpha",
-            ReleaseChannel::Nightly => "Nightly",
-        }
+impl ReleaseChannel {
+    pub fn label(&self) -> &str {
+        &self.name
     }
 
-    pub fn repo(&self) -> &'static str {
-        match self {
-            ReleaseChannel::Stable | ReleaseChannel::Alpha => "Far-Beyond-Pulsar/Pulsar-Native",
-            ReleaseChannel::Nightly => "Far-Beyond-Pulsar/Nightly",
-        }
+    /// Whether this channel would show `release` from its source.
+    pub fn includes(&self, release: &GitHubRelease) -> bool {
+        self.includes_tag(&release.tag_name)
     }
 
-    /// Whether this channel would show `release` (repo + version scoped).
-    pub fn includes(&self, release: &GitHubRelease) -> bool {
-        match self {
-            ReleaseChannel::Nightly => true,
-            ReleaseChannel::Stable => version_major(&release.tag_name).map(|m| m >= 1).unwrap_or(false),
-            ReleaseChannel::Alpha => version_major(&release.tag_name)
+    /// Whether this channel would show a release tagged `tag`.
+    pub fn includes_tag(&self, tag: &str) -> bool {
+        match self.filter {
+            ChannelFilter::All => true,
+            ChannelFilter::Stable => version_major(tag).map(|m| m >= 1).unwrap_or(false),
+            ChannelFilter::Alpha => version_major(tag)
                 .map(|m| m >= 0 && m < 1)
                 .unwrap_or(false),
         }
     }
 }
 
-/// Pagination/loading state for a single source repo being paged through.
+/// Pagination/loading state for a single release source being paged through.
 #[derive(Debug, Clone)]
 pub struct ChannelSource {
-    pub repo: &'static str,
+    pub source: Arc<dyn ReleaseSource>,
     pub page: u32,
     pub has_more: bool,
     pub loading: bool,
     pub error: Option<String>,
-    /// Releases fetched so far for this repo (unfiltered by channel).
+    /// Releases fetched so far from this source (unfiltered by channel).
     pub fetched: Vec<GitHubRelease>,
 }
 
 impl ChannelSource {
-    pub fn new(repo: &'static str) -> Self {
+    pub fn new(source: Arc<dyn ReleaseSource>) -> Self {
         Self {
-            repo,
+            source,
             page: 0,
             has_more: true,
             loading: false,
@@ -108,14 +142,10 @@ impl ChannelSource {
             fetched: Vec::new(),
         }
     }
-}
 
-/// The canonical set of repos used as release sources.
-pub fn default_channel_sources() -> Vec<ChannelSource> {
-    vec![
-        ChannelSource::new(ReleaseChannel::Stable.repo()),
-        ChannelSource::new(ReleaseChannel::Nightly.repo()),
-    ]
+    pub fn id(&self) -> &str {
+        self.source.id()
+    }
 }
 
 /// Parse the leading numeric major/minor from a release tag for version checks.
@@ -131,47 +161,124 @@ fn version_major(tag: &str) -> Option<i64> {
     }
 }
 
-/// Parse a leading `x.y.z` from a release tag / version string.
-pub fn parse_version(tag: &str) -> Option<(u64, u64, u64)> {
-    let t = tag
-        .trim()
-        .trim_start_matches(['v', 'V', '>', '<', '=', ' ']);
-    let first: &str = t.split_whitespace().next().unwrap_or(t);
-    let mut parts = first
-        .split('.')
-        .map(|p| p.trim_end_matches(|c: char| !c.is_ascii_digit()));
-    let major = parts.next()?.parse().ok()?;
-    let minor = parts.next().unwrap_or("0").parse().unwrap_or(0);
-    let patch = parts.next().unwrap_or("0").parse().unwrap_or(0);
-    Some((major, minor, patch))
-}
-
-/// Parse the minimum version a project requires out of an `engine_version`
-/// string. `">0.1.0"` → `0.1.0`; a `nightly-…` tag yields `None`.
-pub fn required_min_version(required: &str) -> Option<(u64, u64, u64)> {
-    let t = required.trim();
-    if t.to_lowercase().starts_with("nightly-") {
-        return None;
+// ── Engine Version Requirements ─────────────────────────────────────────────
+
+/// Parse a release tag / version string as semver. A `v` prefix is ignored
+/// and missing minor/patch numbers count as zero (`v0.1` → `0.1.0`).
+pub fn parse_version(tag: &str) -> Option<semver::Version> {
+    let t = tag.trim().trim_start_matches(['v', 'V']);
+    if let Ok(v) = semver::Version::parse(t) {
+        return Some(v);
     }
-    parse_version(t)
+    // Pad `x` / `x.y`, keeping any pre-release or build suffix.
+    let split = t.find(['-', '+']).unwrap_or(t.len());
+    let (core, suffix) = t.split_at(split);
+    let padded = match core.split('.').count() {
+        1 => format!("{}.0.0{}", core, suffix),
+        2 => format!("{}.0{}", core, suffix),
+        _ => return None,
+    };
+    semver::Version::parse(&padded).ok()
 }
 
-/// Whether `installed_version` satisfies a project's `required` engine version.
-///
-/// Exact `nightly-…` tags must match exactly; everything else is treated as a
-/// minimum `x.y.z` requirement.
-pub fn installed_satisfies(installed_version: &str, required: &str) -> bool {
-    let req = required.trim();
-    if req.eq_ignore_ascii_case("src") {
-        return installed_version.trim().eq_ignore_ascii_case("src");
+/// A project's `[project].engine_version` requirement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EngineRequirement {
+    /// The local source checkout registered as the `src` engine version.
+    Src,
+    /// Exactly one nightly build, by tag (`nightly-…`).
+    Nightly(String),
+    /// Semver ranges, any of which may match: `^0.1`, `~0.1.3`,
+    /// `>=0.1.20, <0.2`, `>=0.1 <0.2`, `0.1.*`, `^0.1 || ^1`. A bare version
+    /// such as `0.1.23` means that version or newer, as it always has.
+    Range(Vec<semver::VersionReq>),
+}
+
+impl EngineRequirement {
+    pub fn parse(required: &str) -> Result<Self, String> {
+        let t = required.trim();
+        if t.eq_ignore_ascii_case("src") {
+            return Ok(EngineRequirement::Src);
+        }
+        if t.to_lowercase().starts_with("nightly-") {
+            return Ok(EngineRequirement::Nightly(t.to_string()));
+        }
+        if t.is_empty() {
+            return Err("empty engine version requirement".to_string());
+        }
+        t.split("||")
+            .map(|alternative| {
+                let normalized = normalize_range(alternative);
+                semver::VersionReq::parse(&normalized).map_err(|e| {
+                    format!("invalid engine version requirement '{}': {}", required.trim(), e)
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(EngineRequirement::Range)
     }
-    if req.to_lowercase().starts_with("nightly-") {
-        return installed_version.trim() == req;
+
+    /// Whether an engine tagged/versioned `version` satisfies this requirement.
+    pub fn matches(&self, version: &str) -> bool {
+        match self {
+            EngineRequirement::Src => version.trim().eq_ignore_ascii_case("src"),
+            EngineRequirement::Nightly(tag) => version.trim().eq_ignore_ascii_case(tag),
+            EngineRequirement::Range(reqs) => parse_version(version)
+                .map(|v| reqs.iter().any(|req| req.matches(&v)))
+                .unwrap_or(false),
+        }
     }
-    let Some(min) = required_min_version(req) else {
-        return false;
-    };
-    parse_version(installed_version).map(|v| v >= min).unwrap_or(false)
+
+    /// The highest of `versions` satisfying this requirement. Nightlies have
+    /// no order, so the exact tag is the only candidate.
+    pub fn best_match<'a>(&self, versions: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+        let mut matching = versions.into_iter().filter(|v| self.matches(v));
+        match self {
+            EngineRequirement::Range(_) => matching.max_by_key(|v| parse_version(v)),
+            _ => matching.next(),
+        }
+    }
+}
+
+/// Rewrite one `||` alternative into the comma-separated form the `semver`
+/// crate parses: whitespace-separated comparators are joined with commas,
+/// `v` prefixes dropped, and bare versions turned into minimums.
+fn normalize_range(range: &str) -> String {
+    let mut comparators: Vec<String> = Vec::new();
+    let mut pending_op = String::new();
+    for token in range.split([',', ' ', '\t']).filter(|t| !t.is_empty()) {
+        let op_len = token
+            .find(|c: char| !matches!(c, '<' | '>' | '=' | '~' | '^'))
+            .unwrap_or(token.len());
+        let (op, version) = token.split_at(op_len);
+        pending_op.push_str(op);
+        if version.is_empty() {
+            // An operator written apart from its version: `>= 0.1`.
+            continue;
+        }
+        let version = version.trim_start_matches(['v', 'V']);
+        let wildcard = version.contains(['*', 'x', 'X']);
+        let op = if pending_op.is_empty() && !wildcard {
+            ">="
+        } else {
+            pending_op.as_str()
+        };
+        comparators.push(format!("{}{}", op, version));
+        pending_op.clear();
+    }
+    if !pending_op.is_empty() {
+        // Dangling operator; let the semver parser report it.
+        comparators.push(pending_op);
+    }
+    comparators.join(", ")
+}
+
+/// Whether `installed_version` satisfies a project's `required` engine
+/// version (see [`EngineRequirement`]). Unparseable requirements match
+/// nothing.
+pub fn installed_satisfies(installed_version: &str, required: &str) -> bool {
+    EngineRequirement::parse(required)
+        .map(|req| req.matches(installed_version))
+        .unwrap_or(false)
 }
 
 /// Whether an installed set contains at least one version satisfying `required`.
@@ -187,8 +294,12 @@ pub fn installed_versions_with_src(src: Option<&std::path::Path>) -> Vec<Install
                 version: "src".to_string(),
                 install_date: chrono::Utc::now().to_rfc3339(),
                 install_path: src.to_path_buf(),
+                archive_sha256: None,
+                archive_name: None,
+                modules: Vec::new(),
             },
             disk_size_bytes: 0,
+            shared_bytes: 0,
             update_available: false,
         });
         versions.sort_by(|a, b| b.metadata.install_date.cmp(&a.metadata.install_date));
@@ -239,7 +350,7 @@ pub fn scan_installed_versions() -> Vec<InstalledVersion> {
     let mut results = Vec::new();
     let mut seen = std::collections::HashSet::new();
 
-    for root in platform_search_roots() {
+    for root in EngineLibraries::load().all() {
         if !root.exists() {
             continue;
         }
@@ -268,18 +379,55 @@ pub fn scan_installed_versions() -> Vec<InstalledVersion> {
         }
     }
 
+    let dirs: Vec<&Path> = results.iter().map(|v| v.metadata.install_path.as_path()).collect();
+    let shared = EngineStoreService::shared_bytes(&dirs);
+    for (ver, shared) in results.iter_mut().zip(shared) {
+        ver.shared_bytes = shared;
+    }
+
     results.sort_by(|a, b| b.metadata.install_date.cmp(&a.metadata.install_date));
     results
 }
 
+/// Share the files of the finished install at `dir` with other versions
+/// through the engine store. Failing to do so only costs disk space.
+fn share_install_files(dir: &Path) {
+    if let Err(e) = EngineStoreService::adopt(dir) {
+        tracing::warn!("Couldn't deduplicate {}: {}", dir.display(), e);
+    }
+}
+
 pub fn write_metadata(dir: &Path, version: &str) -> std::io::Result<()> {
+    write_metadata_with_digest(dir, version, None, None)
+}
+
+pub fn write_metadata_with_digest(
+    dir: &Path,
+    version: &str,
+    archive_name: Option<&str>,
+    archive_sha256: Option<&str>,
+) -> std::io::Result<()> {
     let metadata = PulsarInstallMetadata {
         version: version.to_string(),
         install_date: chrono::Utc::now().to_rfc3339(),
         install_path: dir.to_path_buf(),
+        archive_sha256: archive_sha256.map(str::to_string),
+        archive_name: archive_sha256.and(archive_name).map(str::to_string),
+        modules: Vec::new(),
     };
+    write_file_manifest(dir)?;
+    save_metadata(dir, &metadata)
+}
+
+/// The `.pulsar-install.json` of the install at `dir`, if it has one.
+pub fn read_metadata(dir: &Path) -> Option<PulsarInstallMetadata> {
+    let content = std::fs::read_to_string(dir.join(".pulsar-install.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_metadata(dir: &Path, metadata: &PulsarInstallMetadata) -> std::io::Result<()> {
     let json =
-        serde_json::to_string_pretty(&metadata).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        serde_json::to_string_pretty(metadata).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
     std::fs::write(dir.join(".pulsar-install.json"), json)
 }
 
@@ -312,23 +460,22 @@ fn try_load_from_dir(dir: &Path) -> Option<InstalledVersion> {
                 .to_string(),
             install_date: String::new(),
             install_path: dir.to_path_buf(),
+            archive_sha256: None,
+            archive_name: None,
+            modules: Vec::new(),
         }
     };
     let disk_size_bytes = dir_size(dir);
     Some(InstalledVersion {
         metadata,
         disk_size_bytes,
+        shared_bytes: 0,
         update_available: false,
     })
 }
 
 fn looks_like_install_dir(dir: &Path) -> bool {
-    dir.join("pulsar").is_file()
-        || dir.join("pulsar.exe").is_file()
-        || dir.join("pulsar_engine").is_file()
-        || dir.join("pulsar_engine.exe").is_file()
-        || dir.join("Contents").join("Info.plist").exists()
-        || dir.join(".pulsar-install.json").is_file()
+    has_engine_binary(dir) || dir.join(".pulsar-install.json").is_file()
 }
 
 // ── GitHub Releases ─────────────────────────────────────────────────────────
@@ -338,6 +485,23 @@ const GITHUB_API: &str = "https://api.github.com/repos";
 /// Number of releases returned per page by the GitHub releases API.
 pub const RELEASES_PER_PAGE: u32 = 30;
 
+/// How long a cached single release (notes, assets) is used without asking
+/// GitHub again. Release pages are always revalidated, which is cheap and
+/// doesn't count against the rate limit when nothing changed.
+const RELEASE_CACHE_MAX_AGE: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+fn repo_releases_url(repo: &str, page: u32) -> String {
+    format!(
+        "{}/{}/releases?page={}&per_page={}",
+        GITHUB_API, repo, page, RELEASES_PER_PAGE
+    )
+}
+
+/// The last fetched copy of a page of releases, without any request.
+pub fn cached_repo_releases(repo: &str, page: u32) -> Option<Vec<GitHubRelease>> {
+    serde_json::from_str(&HttpCacheService::cached(&repo_releases_url(repo, page))?).ok()
+}
+
 /// Fetch one page of releases from a given `owner/repo` slug.
 pub fn fetch_repo_releases_blocking(
     repo: &str,
@@ -352,23 +516,22 @@ pub fn fetch_repo_releases_blocking(
         .build()
         .map_err(|e| e.to_string())?;
 
-    let url = format!(
-        "{}/{}/releases?page={}&per_page={}",
-        GITHUB_API, repo, page, RELEASES_PER_PAGE
-    );
+    let url = repo_releases_url(repo, page);
     let mut last_err = String::new();
     for attempt in 1..=3 {
-        match client.get(&url).send() {
-            Ok(resp) if resp.status().is_success() => {
-                return resp.json::<Vec<GitHubRelease>>().map_err(|e| e.to_string());
+        match HttpCacheService::get(&client, &url, Duration::ZERO) {
+            Ok(resp) if resp.status.is_success() => {
+                return serde_json::from_str::<Vec<GitHubRelease>>(&resp.body)
+                    .map_err(|e| e.to_string());
             }
-            Ok(resp) if resp.status().is_server_error() => {
-                last_err = format!("HTTP {} (attempt {}/3)", resp.status(), attempt);
+            Ok(resp) if resp.status.is_server_error() => {
+                last_err = format!("HTTP {} (attempt {}/3)", resp.status, attempt);
                 std::thread::sleep(Duration::from_secs(2 * attempt as u64));
             }
             Ok(resp) => {
-                return Err(format!("HTTP {}", resp.status()));
+                return Err(format!("HTTP {}", resp.status));
             }
+            Err(e) if GitHubService::rate_limited_until().is_some() => return Err(e),
             Err(e) => {
                 last_err = format!("{} (attempt {}/3)", e, attempt);
                 std::thread::sleep(Duration::from_secs(2 * attempt as u64));
@@ -378,49 +541,53 @@ pub fn fetch_repo_releases_blocking(
     Err(last_err)
 }
 
-/// The `(repo, tag)` GitHub identifiers for a given installed engine version.
-fn repo_tag_for_version(version: &str) -> (&'static str, String) {
-    if version.to_lowercase().starts_with("nightly-") {
-        ("Far-Beyond-Pulsar/Nightly", version.to_string())
-    } else {
-        let t = if version.starts_with(['v', 'V']) {
-            version.to_string()
-        } else {
-            format!("v{}", version)
-        };
-        ("Far-Beyond-Pulsar/Pulsar-Native", t)
-    }
-}
-
-/// Fetch the GitHub **release notes** (release `body`) for a given installed
-/// engine version. Falls back to a short message when unavailable.
-pub fn release_notes_for_version(version: &str) -> String {
-    let (repo, tag) = repo_tag_for_version(version);
+/// Fetch the release tagged `tag` from a given `owner/repo` slug, or
+/// `Ok(None)` if the repo has no such release.
+pub fn fetch_repo_release_by_tag_blocking(
+    repo: &str,
+    tag: &str,
+) -> Result<Option<GitHubRelease>, String> {
     let url = format!(
         "{}/{}/releases/tags/{}",
         GITHUB_API, repo, tag
     );
 
-    let client = match reqwest::blocking::Client::builder()
+    let client = reqwest::blocking::Client::builder()
         .user_agent("Pulsar-Hub/1.0")
         .timeout(std::time::Duration::from_secs(15))
         .build()
-    {
-        Ok(c) => c,
-        Err(_) => return format!("Could not load release notes for **{}**.", version),
-    };
+        .map_err(|e| e.to_string())?;
 
-    let resp = match client.get(&url).send() {
-        Ok(r) if r.status().is_success() => r,
-        _ => {
-            return format!(
-                "No release notes are available for engine version **{}**.",
-                version
-            )
+    let resp = HttpCacheService::get(&client, &url, RELEASE_CACHE_MAX_AGE)?;
+    if resp.status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !resp.status.is_success() {
+        return Err(format!("HTTP {}", resp.status));
+    }
+    serde_json::from_str::<GitHubRelease>(&resp.body)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+/// Fetch the release for an engine version or tag (`0.1.23`, `v0.1.23`,
+/// `nightly-…`) from whichever configured release source publishes it.
+pub fn fetch_release_by_tag_blocking(version: &str) -> Result<GitHubRelease, String> {
+    let mut last_err = None;
+    for source in ReleaseSourcesConfig::load().sources_for_tag(version) {
+        match source.fetch_release(version) {
+            Ok(Some(release)) => return Ok(release),
+            Ok(None) => {}
+            Err(e) => last_err = Some(format!("{}: {}", source.id(), e)),
         }
-    };
+    }
+    Err(last_err.unwrap_or_else(|| format!("No release {} found in any release source", version)))
+}
 
-    match resp.json::<GitHubRelease>() {
+/// Fetch the GitHub **release notes** (release `body`) for a given installed
+/// engine version. Falls back to a short message when unavailable.
+pub fn release_notes_for_version(version: &str) -> String {
+    match fetch_release_by_tag_blocking(version) {
         Ok(release) if !release.body.trim().is_empty() => release.body,
         _ => format!(
             "No release notes are available for engine version **{}**.",
@@ -489,109 +656,83 @@ fn nightly_platform_token(os: &str, arch: &str) -> Option<String> {
     }
 }
 
-pub fn download_and_extract_blocking(
-    url: &str,
-    dest_dir: &Path,
-    version: &str,
-    progress_cb: impl Fn(f32),
-) -> Result<(), String> {
-    progress_cb(0.0);
-
-    let client = reqwest::blocking::Client::builder()
-        .user_agent("Pulsar-Hub/1.0")
-        .build()
-        .map_err(|e| e.to_string())?;
+// ── Download Progress ──────────────────────────────────────────────────────
 
-    let resp = client.get(url).send().map_err(|e| e.to_string())?;
-    if !resp.status().is_success() {
-        return Err(format!("Download failed: HTTP {}", resp.status()));
-    }
+#[derive(Clone, Debug)]
+pub struct DownloadProgress {
+    pub bytes_downloaded: u64,
+    pub total_bytes: u64,
+    pub speed_bps: u64,
+    pub done: bool,
+    pub error: Option<DownloadFailure>,
+    /// Set by the UI; the download thread checks it between reads.
+    pub control: DownloadControl,
+    /// The thread stopped because of [`DownloadControl::Pause`]; the partial
+    /// file is kept and the next attempt resumes from it.
+    pub paused: bool,
+    /// The thread stopped because of [`DownloadControl::Cancel`] and removed
+    /// the partial file.
+    pub cancelled: bool,
+    /// Bandwidth cap in bytes per second, 0 for unlimited. Read between
+    /// chunks, so it can be changed while the download runs.
+    pub max_bps: u64,
+}
 
-    let total = resp.content_length().unwrap_or(0);
-    let mut downloaded: u64 = 0;
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DownloadControl {
+    #[default]
+    Run,
+    Pause,
+    Cancel,
+}
 
-    std::fs::create_dir_all(dest_dir).map_err(|e| e.to_string())?;
+/// Why an engine download failed. Integrity failures are kept apart from
+/// ordinary errors so a corrupt or tampered download isn't mistaken for a
+/// flaky connection.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DownloadFailure {
+    Error(String),
+    ChecksumMismatch {
+        asset: String,
+        expected: String,
+        actual: String,
+    },
+    BadSignature {
+        asset: String,
+        reason: String,
+    },
+}
 
-    let (_, _, ext) = platform_info();
-    let is_zip = url.to_lowercase().ends_with(".zip");
-    let archive_path = if is_zip || ext != "exe" {
-        let name = url
-            .rsplit('/')
-            .next()
-            .filter(|n| !n.is_empty())
-            .map(|n| n.to_string())
-            .unwrap_or_else(|| {
-                if is_zip {
-                    format!("pulsar-{}.zip", version)
-                } else {
-                    format!("pulsar-{}.tar.gz", version)
-                }
-            });
-        Some(dest_dir.parent().unwrap_or(dest_dir).join(name))
-    } else {
-        None
-    };
+impl DownloadFailure {
+    pub fn is_integrity_failure(&self) -> bool {
+        !matches!(self, DownloadFailure::Error(_))
+    }
+}
 
-    if let Some(archive_path) = &archive_path {
-        let mut file = std::fs::File::create(archive_path).map_err(|e| e.to_string())?;
-        use std::io::Read;
-        let mut reader = resp;
-        let mut buf = [0u8; 8192];
-        loop {
-            let n = reader.read(&mut buf).map_err(|e| e.to_string())?;
-            if n == 0 {
-                break;
-            }
-            std::io::Write::write_all(&mut file, &buf[..n]).map_err(|e| e.to_string())?;
-            downloaded += n as u64;
-            if total > 0 {
-                progress_cb((downloaded as f32 / total as f32) * 80.0);
-            }
-        }
-        progress_cb(80.0);
-        if is_zip {
-            extract_zip(archive_path, dest_dir).map_err(|e| e.to_string())?;
-            flatten_archive_root(dest_dir);
-            place_engine_binary_at_root(dest_dir);
-        } else {
-            extract_tar_gz(archive_path, dest_dir)
-                .map_err(|e| e.to_string())?;
-        }
-        let _ = std::fs::remove_file(archive_path);
-        write_metadata(dest_dir, version).map_err(|e| e.to_string())?;
-    } else {
-        let exe_path = dest_dir.join("pulsar.exe");
-        let mut file = std::fs::File::create(&exe_path).map_err(|e| e.to_string())?;
-        use std::io::Read;
-        let mut reader = resp;
-        let mut buf = [0u8; 8192];
-        loop {
-            let n = reader.read(&mut buf).map_err(|e| e.to_string())?;
-            if n == 0 {
-                break;
-            }
-            std::io::Write::write_all(&mut file, &buf[..n]).map_err(|e| e.to_string())?;
-            downloaded += n as u64;
-            if total > 0 {
-                progress_cb((downloaded as f32 / total as f32) * 80.0);
+impl std::fmt::Display for DownloadFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DownloadFailure::Error(e) => f.write_str(e),
+            DownloadFailure::ChecksumMismatch {
+                asset,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Checksum mismatch for {}: expected {}, got {}",
+                asset, expected, actual
+            ),
+            DownloadFailure::BadSignature { asset, reason } => {
+                write!(f, "Signature check failed for {}: {}", asset, reason)
             }
         }
-        write_metadata(dest_dir, version).map_err(|e| e.to_string())?;
     }
-
-    progress_cb(100.0);
-    Ok(())
 }
 
-// ── Download Progress ──────────────────────────────────────────────────────
-
-#[derive(Clone, Debug)]
-pub struct DownloadProgress {
-    pub bytes_downloaded: u64,
-    pub total_bytes: u64,
-    pub speed_bps: u64,
-    pub done: bool,
-    pub error: Option<String>,
+impl From<String> for DownloadFailure {
+    fn from(e: String) -> Self {
+        DownloadFailure::Error(e)
+    }
 }
 
 impl Default for DownloadProgress {
@@ -602,6 +743,10 @@ impl Default for DownloadProgress {
             speed_bps: 0,
             done: false,
             error: None,
+            control: DownloadControl::Run,
+            paused: false,
+            cancelled: false,
+            max_bps: 0,
         }
     }
 }
@@ -610,199 +755,1220 @@ pub fn download_and_extract_with_progress(
     url: &str,
     dest_dir: &Path,
     version: &str,
+    integrity: &AssetIntegrity,
     progress: Arc<Mutex<DownloadProgress>>,
 ) {
-    let client = match reqwest::blocking::Client::builder()
+    let result = download_verified(url, dest_dir, version, integrity, &progress).and_then(|downloaded| {
+        let Some((download_path, digest)) = downloaded else {
+            return Ok(Transfer::Stopped);
+        };
+        let kind = AssetKind::for_url(url);
+        let result = install_downloaded_asset(
+            &download_path,
+            kind,
+            dest_dir,
+            version,
+            &integrity.asset_name,
+            digest.as_deref(),
+        );
+        let _ = std::fs::remove_file(&download_path);
+        result?;
+        share_install_files(dest_dir);
+        Ok(Transfer::Finished)
+    });
+    finish_transfer(&progress, result);
+}
+
+/// Record how an install ended on `progress`. Stopped transfers were
+/// already recorded by [`finish_stopped`].
+fn finish_transfer(progress: &Arc<Mutex<DownloadProgress>>, result: Result<Transfer, DownloadFailure>) {
+    let mut p = progress.lock();
+    match result {
+        Ok(Transfer::Stopped) => return,
+        Ok(Transfer::Finished) => p.bytes_downloaded = p.total_bytes,
+        Err(e) => p.error = Some(e),
+    }
+    p.done = true;
+}
+
+/// Download `url` next to `dest_dir` and check it against `integrity`.
+/// Returns the downloaded file and its verified digest, or `None` if the
+/// download was paused or cancelled.
+fn download_verified(
+    url: &str,
+    dest_dir: &Path,
+    version: &str,
+    integrity: &AssetIntegrity,
+    progress: &Arc<Mutex<DownloadProgress>>,
+) -> Result<Option<(PathBuf, Option<String>)>, DownloadFailure> {
+    let client = reqwest::blocking::Client::builder()
         .user_agent("Pulsar-Hub/1.0")
         .connect_timeout(std::time::Duration::from_secs(10))
         .build()
-    {
-        Ok(c) => c,
-        Err(e) => {
-            let mut p = progress.lock();
-            p.error = Some(e.to_string());
-            p.done = true;
-            return;
-        }
-    };
+        .map_err(|e| e.to_string())?;
 
-    let resp = match client.get(url).send() {
-        Ok(r) => r,
-        Err(e) => {
-            let mut p = progress.lock();
-            p.error = Some(e.to_string());
-            p.done = true;
-            return;
-        }
-    };
-    if !resp.status().is_success() {
-        let mut p = progress.lock();
-        p.error = Some(format!("HTTP {}", resp.status()));
-        p.done = true;
-        return;
+    let download_path = download_path_for(url, dest_dir, AssetKind::for_url(url), version);
+    if let Some(parent) = download_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    if download_file_with_progress(&client, url, download_path.clone(), progress)? == Transfer::Stopped {
+        finish_stopped(progress, &download_path, dest_dir);
+        return Ok(None);
     }
+    progress.lock().speed_bps = 0;
 
-    let total = resp.content_length().unwrap_or(0);
-    {
-        let mut p = progress.lock();
-        p.total_bytes = total;
+    match integrity.verify(&download_path) {
+        Ok(digest) => Ok(Some((download_path, digest))),
+        Err(failure) => {
+            let _ = std::fs::remove_file(&download_path);
+            Err(failure)
+        }
     }
+}
 
-    if let Err(e) = std::fs::create_dir_all(dest_dir) {
-        let mut p = progress.lock();
-        p.error = Some(e.to_string());
-        p.done = true;
-        return;
+// ── Download Verification ───────────────────────────────────────────────────
+
+/// minisign public key engine releases are signed with, baked in at build
+/// time. Without it `.minisig` assets are ignored and only checksums apply.
+pub const ENGINE_MINISIGN_KEY: Option<&str> = option_env!("PULSAR_ENGINE_MINISIGN_KEY");
+
+/// Checksum and signature published for one release asset, found by
+/// [`fetch_asset_integrity`].
+#[derive(Debug, Clone, Default)]
+pub struct AssetIntegrity {
+    pub asset_name: String,
+    /// Lowercase hex SHA256 from `<asset>.sha256` or a `SHA256SUMS` asset.
+    pub sha256: Option<String>,
+    /// Contents of `<asset>.minisig`, when a signing key is configured.
+    pub minisig: Option<String>,
+}
+
+impl AssetIntegrity {
+    /// Whether the release published anything to check `asset_name` against.
+    pub fn is_empty(&self) -> bool {
+        self.sha256.is_none() && self.minisig.is_none()
     }
 
-    let (_, _, ext) = platform_info();
-    // Nightly assets are `.zip` archives — even on Windows, where `ext` is
-    // "exe" — so decide on the URL's extension before falling back to the
-    // raw-exe / tar.gz paths.
-    let is_zip = url.to_lowercase().ends_with(".zip");
-    let write_result = if is_zip {
-        let archive_name = url
-            .rsplit('/')
-            .next()
-            .filter(|n| !n.is_empty())
-            .unwrap_or(&format!("pulsar-{}.zip", version))
-            .to_string();
-        let archive_path = dest_dir.parent().unwrap_or(dest_dir).join(&archive_name);
-        let r = download_file_with_progress(resp, archive_path.clone(), &progress);
-        if r.is_ok() {
-            {
-                let mut p = progress.lock();
-                p.speed_bps = 0;
-            }
-            if let Err(e) = extract_zip(&archive_path, dest_dir) {
-                let mut p = progress.lock();
-                p.error = Some(e);
-                p.done = true;
-                return;
-            }
-            flatten_archive_root(dest_dir);
-            place_engine_binary_at_root(dest_dir);
-            let _ = std::fs::remove_file(&archive_path);
+    /// Check a downloaded copy of the asset. Returns its SHA256 when a
+    /// checksum or signature vouched for it, `None` when nothing was
+    /// published to check against.
+    pub fn verify(&self, path: &Path) -> Result<Option<String>, DownloadFailure> {
+        if self.is_empty() {
+            tracing::warn!("No checksum or signature published for {}; installing unverified", self.asset_name);
+            return Ok(None);
         }
-        r
-    } else if ext == "exe" {
-        download_file_with_progress(resp, dest_dir.join("pulsar.exe"), &progress)
-    } else {
-        let archive_name = url
-            .rsplit('/')
-            .next()
-            .filter(|n| !n.is_empty())
-            .unwrap_or(&format!("pulsar-{}.tar.gz", version))
-            .to_string();
-        let archive_path = dest_dir.parent().unwrap_or(dest_dir).join(&archive_name);
-        let r = download_file_with_progress(resp, archive_path.clone(), &progress);
-        if r.is_ok() {
-            {
-                let mut p = progress.lock();
-                p.speed_bps = 0;
-            }
-            if let Err(e) = extract_tar_gz(&archive_path, dest_dir) {
-                let mut p = progress.lock();
-                p.error = Some(e);
-                p.done = true;
-                return;
+        let actual = sha256_file(path).map_err(|e| DownloadFailure::Error(e.to_string()))?;
+        if let Some(expected) = &self.sha256 {
+            if *expected != actual {
+                return Err(DownloadFailure::ChecksumMismatch {
+                    asset: self.asset_name.clone(),
+                    expected: expected.clone(),
+                    actual,
+                });
             }
-            let _ = std::fs::remove_file(&archive_path);
         }
-        r
+        if let (Some(signature), Some(key)) = (&self.minisig, ENGINE_MINISIGN_KEY) {
+            verify_minisign(path, signature, key).map_err(|reason| DownloadFailure::BadSignature {
+                asset: self.asset_name.clone(),
+                reason,
+            })?;
+        }
+        Ok(Some(actual))
+    }
+}
+
+/// Look up the checksum and signature assets published next to `asset` in
+/// `release` and download them.
+pub fn fetch_asset_integrity(release: &GitHubRelease, asset: &GitHubAsset) -> Result<AssetIntegrity, String> {
+    let find = |name: &str| {
+        release
+            .assets
+            .iter()
+            .find(|a| a.name.eq_ignore_ascii_case(name))
+    };
+    let mut integrity = AssetIntegrity {
+        asset_name: asset.name.clone(),
+        ..Default::default()
     };
 
-    match write_result {
-        Ok(()) => {
-            let _ = write_metadata(dest_dir, version);
-            let mut p = progress.lock();
-            p.bytes_downloaded = p.total_bytes;
-            p.done = true;
+    if let Some(sums) = find(&format!("{}.sha256", asset.name)) {
+        let text = fetch_text_blocking(&sums.browser_download_url)?;
+        // Either a bare digest or `sha256sum` output for this one file.
+        integrity.sha256 = text.split_whitespace().next().and_then(normalize_sha256);
+        if integrity.sha256.is_none() {
+            return Err(format!("{} does not contain a SHA256 digest", sums.name));
         }
-        Err(e) => {
-            let mut p = progress.lock();
-            p.error = Some(e);
-            p.done = true;
+    } else if let Some(sums) = find("SHA256SUMS").or_else(|| find("SHA256SUMS.txt")) {
+        let text = fetch_text_blocking(&sums.browser_download_url)?;
+        integrity.sha256 = parse_sha256sums(&text, &asset.name);
+        if integrity.sha256.is_none() {
+            return Err(format!("{} has no entry for {}", sums.name, asset.name));
+        }
+    }
+
+    if let Some(sig) = find(&format!("{}.minisig", asset.name)) {
+        if ENGINE_MINISIGN_KEY.is_some() {
+            integrity.minisig = Some(fetch_text_blocking(&sig.browser_download_url)?);
+        } else {
+            tracing::warn!("Ignoring {}: this build has no engine signing key", sig.name);
         }
     }
+    Ok(integrity)
 }
 
-/// Many archives (e.g. Nightly zips) wrap everything in a single top-level
-/// folder. If `dest` contains exactly one directory and no root files, hoist
-/// that folder's contents up into `dest` so the engine binary ends up at the
-/// install root.
-fn flatten_archive_root(dest: &Path) {
-    let mut root_files = 0;
-    let mut subdirs: Vec<PathBuf> = Vec::new();
-    let dirs = std::fs::read_dir(dest);
-    if let Ok(entries) = dirs {
-        for entry in entries.filter_map(|e| e.ok()) {
-            let kind = entry.file_type().ok();
-            if kind.map(|k| k.is_dir()).unwrap_or(false) {
-                subdirs.push(entry.path());
-            } else {
-                root_files += 1;
-            }
+/// Find `name`'s digest in `sha256sum`-style output (`<hex>  <name>`, with
+/// `*<name>` for binary mode).
+fn parse_sha256sums(text: &str, name: &str) -> Option<String> {
+    text.lines().find_map(|line| {
+        let (hash, file) = line.trim().split_once(char::is_whitespace)?;
+        let file = file.trim().trim_start_matches('*');
+        let file = file.rsplit('/').next().unwrap_or(file);
+        (file == name).then(|| normalize_sha256(hash)).flatten()
+    })
+}
+
+fn normalize_sha256(hash: &str) -> Option<String> {
+    let hash = hash.trim().to_ascii_lowercase();
+    (hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit())).then_some(hash)
+}
+
+fn fetch_text_blocking(url: &str) -> Result<String, String> {
+    if let Some(path) = local_file_path(url) {
+        return std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e));
+    }
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("Pulsar-Hub/1.0")
+        .timeout(std::time::Duration::from_secs(15))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let resp = client.get(url).send().map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("HTTP {} fetching {}", resp.status(), url));
+    }
+    resp.text().map_err(|e| e.to_string())
+}
+
+pub(crate) fn sha256_file(path: &Path) -> std::io::Result<String> {
+    use sha2::{Digest, Sha256};
+
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Verify a (prehashed, as `minisign -S` writes by default) minisign
+/// signature without reading the whole file into memory.
+fn verify_minisign(path: &Path, signature: &str, public_key: &str) -> Result<(), String> {
+    use std::io::Read;
+
+    let public_key = minisign_verify::PublicKey::from_base64(public_key.trim())
+        .map_err(|e| format!("invalid engine signing key: {}", e))?;
+    let signature = minisign_verify::Signature::decode(signature).map_err(|e| e.to_string())?;
+    let mut verifier = public_key.verify_stream(&signature).map_err(|e| e.to_string())?;
+
+    let mut file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).map_err(|e| e.to_string())?;
+        if n == 0 {
+            break;
+        }
+        verifier.update(&buf[..n]);
+    }
+    verifier.finalize().map_err(|e| e.to_string())
+}
+
+// ── Delta Updates ───────────────────────────────────────────────────────────
+
+/// The cheapest delta `release` publishes against one of the `installed`
+/// versions, along with that version. See `pulsar_patch_tool::engine_delta`.
+pub fn find_delta_asset<'a>(
+    release: &'a GitHubRelease,
+    installed: &'a [InstalledVersion],
+) -> Option<(&'a GitHubAsset, &'a InstalledVersion)> {
+    let full = find_platform_asset(release)?;
+    let target = release.tag_name.trim_start_matches('v');
+    installed
+        .iter()
+        .filter(|v| v.metadata.version.trim_start_matches('v') != target)
+        // Deltas rebuild a complete install; a base missing modules can't
+        // produce one.
+        .filter(|v| v.metadata.modules.is_empty())
+        .filter_map(|v| {
+            let name = pulsar_patch_tool::engine_delta::delta_asset_name(&full.name, &v.metadata.version);
+            let asset = release.assets.iter().find(|a| a.name == name)?;
+            Some((asset, v))
+        })
+        .min_by_key(|(asset, _)| asset.size)
+}
+
+/// Install `release` into `dest_dir`, from a delta against an installed
+/// version when the release publishes one, otherwise (or if applying the
+/// delta fails) from the full platform archive. Releases with a module
+/// manifest are installed module by module instead: `modules`, or the
+/// manifest's defaults when it's empty.
+pub fn install_release_with_progress(
+    release: &GitHubRelease,
+    dest_dir: &Path,
+    modules: &[String],
+    progress: Arc<Mutex<DownloadProgress>>,
+) {
+    if has_module_manifest(release) {
+        let result = install_modules(release, dest_dir, modules, &progress);
+        finish_transfer(&progress, result);
+        return;
+    }
+
+    let Some(full) = find_platform_asset(release) else {
+        let mut p = progress.lock();
+        p.error = Some(format!("Release {} has no build for this platform", release.tag_name).into());
+        p.done = true;
+        return;
+    };
+
+    let integrity = match fetch_asset_integrity(release, full) {
+        Ok(integrity) => integrity,
+        Err(e) => {
+            let mut p = progress.lock();
+            p.error = Some(format!("Couldn't fetch checksums for {}: {}", full.name, e).into());
+            p.done = true;
+            return;
+        }
+    };
+
+    let installed = scan_installed_versions();
+    if let Some((delta, base)) = find_delta_asset(release, &installed) {
+        tracing::info!(
+            "Installing {} as a delta from {} ({} bytes instead of {})",
+            release.tag_name,
+            base.metadata.version,
+            delta.size,
+            full.size
+        );
+        let delta_result = fetch_asset_integrity(release, delta).and_then(|delta_integrity| {
+            install_delta(
+                delta,
+                &delta_integrity,
+                &integrity,
+                &base.metadata.install_path,
+                dest_dir,
+                &release.tag_name,
+                &progress,
+            )
+        });
+        match delta_result {
+            Ok(Transfer::Finished) => {
+                let mut p = progress.lock();
+                p.bytes_downloaded = p.total_bytes;
+                p.done = true;
+                return;
+            }
+            Ok(Transfer::Stopped) => return,
+            Err(e) => {
+                tracing::warn!("Delta install of {} failed, downloading full archive: {}", release.tag_name, e);
+                let mut p = progress.lock();
+                *p = DownloadProgress {
+                    control: p.control,
+                    max_bps: p.max_bps,
+                    ..Default::default()
+                };
+            }
+        }
+    }
+
+    download_and_extract_with_progress(
+        &full.browser_download_url,
+        dest_dir,
+        &release.tag_name,
+        &integrity,
+        progress,
+    );
+}
+
+/// Install `version` by patching `base_dir` with the delta `asset`.
+/// `archive` describes the full platform archive the patched tree
+/// reproduces; its published digest is what gets recorded, so the install
+/// looks the same as one downloaded in full.
+fn install_delta(
+    asset: &GitHubAsset,
+    integrity: &AssetIntegrity,
+    archive: &AssetIntegrity,
+    base_dir: &Path,
+    dest_dir: &Path,
+    version: &str,
+    progress: &Arc<Mutex<DownloadProgress>>,
+) -> Result<Transfer, String> {
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("Pulsar-Hub/1.0")
+        .connect_timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let parent = dest_dir.parent().unwrap_or(dest_dir);
+    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    let tar_path = parent.join(&asset.name);
+    if download_file_with_progress(&client, &asset.browser_download_url, tar_path.clone(), progress)?
+        == Transfer::Stopped
+    {
+        finish_stopped(progress, &tar_path, dest_dir);
+        return Ok(Transfer::Stopped);
+    }
+
+    let result = install_downloaded_delta(&tar_path, integrity, archive, base_dir, dest_dir, version);
+    let _ = std::fs::remove_file(&tar_path);
+    if result.is_ok() {
+        share_install_files(dest_dir);
+    }
+    result.map(|()| Transfer::Finished)
+}
+
+/// The rest of [`install_delta`] once the delta tar is at `tar_path`.
+fn install_downloaded_delta(
+    tar_path: &Path,
+    integrity: &AssetIntegrity,
+    archive: &AssetIntegrity,
+    base_dir: &Path,
+    dest_dir: &Path,
+    version: &str,
+) -> Result<(), String> {
+    let parent = dest_dir.parent().unwrap_or(dest_dir);
+    let unpacked = parent.join(format!(".delta-{}", version.trim_start_matches('v')));
+    let staging = sibling_dir(dest_dir, "staging");
+    let result = integrity
+        .verify(tar_path)
+        .map_err(|e| e.to_string())
+        .and_then(|_| {
+            let _ = std::fs::remove_dir_all(&unpacked);
+            let file = std::fs::File::open(tar_path).map_err(|e| e.to_string())?;
+            tar::Archive::new(file)
+                .unpack(&unpacked)
+                .map_err(|e| format!("Extract failed: {}", e))
+        })
+        .and_then(|()| {
+            // Patch into a staging copy and swap it in, like a full install,
+            // so a failed delta leaves whatever is at `dest_dir` untouched.
+            let _ = std::fs::remove_dir_all(&staging);
+            pulsar_patch_tool::engine_delta::apply_engine_delta(base_dir, &unpacked, &staging)
+                .map_err(|e| format!("{:#}", e))?;
+            replace_dir(&staging, dest_dir)
+        })
+        .and_then(|()| {
+            write_metadata_with_digest(dest_dir, version, Some(&archive.asset_name), archive.sha256.as_deref())
+                .map_err(|e| e.to_string())
+        });
+
+    let _ = std::fs::remove_dir_all(&unpacked);
+    let _ = std::fs::remove_dir_all(&staging);
+    result
+}
+
+/// Many archives (e.g. Nightly zips) wrap everything in a single top-level
+/// folder. If `dest` contains exactly one directory and no root files, hoist
+/// that folder's contents up into `dest` so the engine binary ends up at the
+/// install root.
+fn flatten_archive_root(dest: &Path) -> Result<(), String> {
+    let mut root_files = 0;
+    let mut subdirs: Vec<PathBuf> = Vec::new();
+    let dirs = std::fs::read_dir(dest);
+    if let Ok(entries) = dirs {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let kind = entry.file_type().ok();
+            if kind.map(|k| k.is_dir()).unwrap_or(false) {
+                subdirs.push(entry.path());
+            } else {
+                root_files += 1;
+            }
+        }
+    }
+    // If there are already files at the root (e.g. pulsar.exe) or more than
+    // one subdirectory, don't try to flatten.
+    if root_files > 0 || subdirs.len() != 1 {
+        return Ok(());
+    }
+    let inner = &subdirs[0];
+    let entries = std::fs::read_dir(inner).map_err(|e| e.to_string())?;
+    for entry in entries {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let name = entry.file_name();
+        if name.to_str().map(|n| n.ends_with('/')).unwrap_or(false) {
+            continue;
+        }
+        std::fs::rename(entry.path(), dest.join(&name)).map_err(|e| {
+            format!("Couldn't move {} to the install root: {}", entry.path().display(), e)
+        })?;
+    }
+    std::fs::remove_dir_all(inner).map_err(|e| e.to_string())
+}
+
+/// Ensure the engine executable sits at the install root under the canonical
+/// name (`pulsar.exe` on Windows, `pulsar` elsewhere). Nightly archives ship
+/// the binary as `pulsar_engine.exe`, possibly inside subdirectories, so we
+/// locate it and rename it into place.
+fn place_engine_binary_at_root(dest: &Path) -> Result<(), String> {
+    let canonical = if cfg!(windows) { "pulsar.exe" } else { "pulsar" };
+    let root_target = dest.join(canonical);
+    if root_target.exists() {
+        return Ok(());
+    }
+    #[cfg(windows)]
+    let matches_binary = |name: &str| {
+        let lower = name.to_ascii_lowercase();
+        lower.starts_with("pulsar") && lower.ends_with(".exe")
+    };
+    #[cfg(not(windows))]
+    let matches_binary = |name: &str| {
+        let lower = name.to_ascii_lowercase();
+        lower == "pulsar" || (lower.starts_with("pulsar_") && !lower.contains('.'))
+    };
+    for entry in WalkDir::new(dest)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let name = entry.file_name().to_string_lossy();
+        if matches_binary(name.as_ref()) {
+            return std::fs::rename(entry.path(), &root_target).map_err(|e| {
+                format!("Couldn't move {} to {}: {}", entry.path().display(), canonical, e)
+            });
+        }
+    }
+    Ok(())
+}
+
+// ── Engine Modules ──────────────────────────────────────────────────────────
+
+/// Release asset listing the separately installable parts of a release.
+pub const MODULE_MANIFEST_ASSET: &str = "pulsar-modules.json";
+/// The module every install needs: the engine itself.
+pub const CORE_MODULE: &str = "core";
+/// Directory in an install holding the file list of each added module, so
+/// it can be removed again.
+const MODULE_FILES_DIR: &str = ".pulsar-modules";
+
+/// One installable part of a release, e.g. debug symbols or the export
+/// templates for a target platform.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EngineModule {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Ticked when the user first picks modules.
+    #[serde(default)]
+    pub default: bool,
+    /// Asset name per `<os>-<arch>` (e.g. `linux-x86_64`), or under `any`
+    /// for platform-independent modules.
+    pub assets: std::collections::BTreeMap<String, String>,
+}
+
+impl EngineModule {
+    pub fn is_core(&self) -> bool {
+        self.id == CORE_MODULE
+    }
+
+    /// The archive of this module for the current platform.
+    pub fn asset<'a>(&self, release: &'a GitHubRelease) -> Option<&'a GitHubAsset> {
+        let (os, arch, _) = platform_info();
+        let name = self
+            .assets
+            .get(&format!("{}-{}", os, arch))
+            .or_else(|| self.assets.get("any"))?;
+        release.assets.iter().find(|a| &a.name == name)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModuleManifest {
+    pub modules: Vec<EngineModule>,
+}
+
+impl ModuleManifest {
+    /// Modules `release` has a build of for this platform, core first.
+    pub fn available(&self, release: &GitHubRelease) -> Vec<EngineModule> {
+        let mut modules: Vec<EngineModule> = self
+            .modules
+            .iter()
+            .filter(|m| m.asset(release).is_some())
+            .cloned()
+            .collect();
+        modules.sort_by_key(|m| !m.is_core());
+        modules
+    }
+
+    /// `requested` plus the core module, or the defaults if nothing was
+    /// requested. Fails if one of them can't be installed on this platform.
+    fn selection(&self, release: &GitHubRelease, requested: &[String]) -> Result<Vec<EngineModule>, String> {
+        let available = self.available(release);
+        if !available.iter().any(|m| m.is_core()) {
+            return Err(format!("Release {} has no build for this platform", release.tag_name));
+        }
+        if let Some(missing) = requested.iter().find(|id| !available.iter().any(|m| &m.id == *id)) {
+            return Err(format!("Module '{}' isn't available for this platform", missing));
+        }
+        Ok(available
+            .into_iter()
+            .filter(|m| {
+                m.is_core()
+                    || if requested.is_empty() {
+                        m.default
+                    } else {
+                        requested.contains(&m.id)
+                    }
+            })
+            .collect())
+    }
+}
+
+fn module_manifest_asset(release: &GitHubRelease) -> Option<&GitHubAsset> {
+    release.assets.iter().find(|a| a.name == MODULE_MANIFEST_ASSET)
+}
+
+/// Whether `release` ships in modules the user picks from.
+pub fn has_module_manifest(release: &GitHubRelease) -> bool {
+    module_manifest_asset(release).is_some()
+}
+
+/// Whether `release` can be installed on this platform, as far as can be
+/// told without fetching its module manifest.
+pub fn has_platform_build(release: &GitHubRelease) -> bool {
+    has_module_manifest(release) || find_platform_asset(release).is_some()
+}
+
+/// The module manifest of `release`, or `None` if it ships as a single
+/// archive.
+pub fn fetch_module_manifest(release: &GitHubRelease) -> Result<Option<ModuleManifest>, String> {
+    let Some(asset) = module_manifest_asset(release) else {
+        return Ok(None);
+    };
+    let text = fetch_text_blocking(&asset.browser_download_url)?;
+    serde_json::from_str(&text)
+        .map(Some)
+        .map_err(|e| format!("Invalid {}: {}", MODULE_MANIFEST_ASSET, e))
+}
+
+/// Install `release` into `dest_dir` as the core module plus the selected
+/// ones from [`ModuleManifest::selection`].
+fn install_modules(
+    release: &GitHubRelease,
+    dest_dir: &Path,
+    requested: &[String],
+    progress: &Arc<Mutex<DownloadProgress>>,
+) -> Result<Transfer, DownloadFailure> {
+    let manifest = fetch_module_manifest(release)?.unwrap_or_default();
+    let selection = manifest.selection(release, requested)?;
+    let version = release.tag_name.as_str();
+
+    // Resuming after a pause: keep the modules that already made it.
+    let mut installed = read_metadata(dest_dir)
+        .filter(|m| m.version == version)
+        .map(|m| m.modules)
+        .unwrap_or_default();
+    if add_modules(release, dest_dir, &selection, &mut installed, progress)? == Transfer::Stopped {
+        return Ok(Transfer::Stopped);
+    }
+    write_file_manifest(dest_dir).map_err(|e| e.to_string())?;
+    share_install_files(dest_dir);
+    Ok(Transfer::Finished)
+}
+
+/// Download and unpack each of `modules` not yet in `installed` into
+/// `dest_dir`, recording each in the install's metadata as it lands.
+fn add_modules(
+    release: &GitHubRelease,
+    dest_dir: &Path,
+    modules: &[EngineModule],
+    installed: &mut Vec<String>,
+    progress: &Arc<Mutex<DownloadProgress>>,
+) -> Result<Transfer, DownloadFailure> {
+    let version = release.tag_name.as_str();
+    for module in modules {
+        if installed.contains(&module.id) {
+            continue;
+        }
+        let Some(asset) = module.asset(release) else {
+            continue;
+        };
+        let integrity = fetch_asset_integrity(release, asset)
+            .map_err(|e| format!("Couldn't fetch checksums for {}: {}", asset.name, e))?;
+        let Some((download, digest)) =
+            download_verified(&asset.browser_download_url, dest_dir, version, &integrity, progress)?
+        else {
+            return Ok(Transfer::Stopped);
+        };
+        let result = if module.is_core() {
+            let kind = AssetKind::for_url(&asset.browser_download_url);
+            install_downloaded_asset(&download, kind, dest_dir, version, &asset.name, digest.as_deref())
+        } else {
+            add_module_files(&download, dest_dir, &module.id)
+        };
+        let _ = std::fs::remove_file(&download);
+        result?;
+        installed.push(module.id.clone());
+        set_installed_modules(dest_dir, installed).map_err(|e| e.to_string())?;
+    }
+    Ok(Transfer::Finished)
+}
+
+/// Add and remove modules of the existing install of `release` at
+/// `install_dir` so it ends up with exactly `modules` (plus core).
+pub fn change_modules_with_progress(
+    release: &GitHubRelease,
+    install_dir: &Path,
+    modules: &[String],
+    progress: Arc<Mutex<DownloadProgress>>,
+) {
+    let result = change_modules(release, install_dir, modules, &progress);
+    finish_transfer(&progress, result);
+}
+
+fn change_modules(
+    release: &GitHubRelease,
+    install_dir: &Path,
+    modules: &[String],
+    progress: &Arc<Mutex<DownloadProgress>>,
+) -> Result<Transfer, DownloadFailure> {
+    let metadata = read_metadata(install_dir)
+        .ok_or_else(|| format!("{} isn't a Pulsar install", install_dir.display()))?;
+    if metadata.modules.is_empty() {
+        return Err(format!("Engine {} was installed without modules", metadata.version).into());
+    }
+    let manifest = fetch_module_manifest(release)?
+        .ok_or_else(|| format!("Release {} has no module manifest", release.tag_name))?;
+    let wanted = manifest.selection(release, modules)?;
+
+    let mut installed = metadata.modules.clone();
+    for id in &metadata.modules {
+        if id != CORE_MODULE && !wanted.iter().any(|m| &m.id == id) {
+            remove_module_files(install_dir, id)?;
+            installed.retain(|i| i != id);
+            set_installed_modules(install_dir, &installed).map_err(|e| e.to_string())?;
+        }
+    }
+    if add_modules(release, install_dir, &wanted, &mut installed, progress)? == Transfer::Stopped {
+        return Ok(Transfer::Stopped);
+    }
+    write_file_manifest(install_dir).map_err(|e| e.to_string())?;
+    share_install_files(install_dir);
+    Ok(Transfer::Finished)
+}
+
+fn set_installed_modules(dir: &Path, modules: &[String]) -> std::io::Result<()> {
+    let mut metadata = read_metadata(dir)
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "missing .pulsar-install.json"))?;
+    metadata.modules = modules.to_vec();
+    save_metadata(dir, &metadata)
+}
+
+fn module_files_list(install_dir: &Path, id: &str) -> PathBuf {
+    install_dir.join(MODULE_FILES_DIR).join(format!("{}.files", id))
+}
+
+/// Unpack the module archive at `archive` over the install at
+/// `install_dir`, recording the files it added. Module archives are laid
+/// out relative to the install root, so unlike full archives they're never
+/// flattened.
+fn add_module_files(archive: &Path, install_dir: &Path, id: &str) -> Result<(), String> {
+    let staging = sibling_dir(install_dir, "staging");
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::create_dir_all(&staging).map_err(|e| e.to_string())?;
+    let result = if archive.to_string_lossy().to_lowercase().ends_with(".zip") {
+        extract_zip(archive, &staging)
+    } else {
+        extract_tar_gz(archive, &staging)
+    }
+    .and_then(|()| {
+        let mut files = Vec::new();
+        for entry in WalkDir::new(&staging)
+            .min_depth(1)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| !e.file_type().is_dir())
+        {
+            let rel = entry.path().strip_prefix(&staging).map_err(|e| e.to_string())?;
+            let target = install_dir.join(rel);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            std::fs::rename(entry.path(), &target)
+                .map_err(|e| format!("Couldn't move {} into place: {}", rel.display(), e))?;
+            files.push(rel.to_string_lossy().replace('\\', "/"));
+        }
+        let list = module_files_list(install_dir, id);
+        if let Some(parent) = list.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(&list, files.join("\n")).map_err(|e| e.to_string())
+    });
+    let _ = std::fs::remove_dir_all(&staging);
+    result
+}
+
+/// Delete the files module `id` added to the install at `install_dir`,
+/// along with directories it leaves empty.
+fn remove_module_files(install_dir: &Path, id: &str) -> Result<(), String> {
+    let list = module_files_list(install_dir, id);
+    let files = std::fs::read_to_string(&list)
+        .map_err(|e| format!("No file list for module '{}': {}", id, e))?;
+    for rel in files.lines().filter_map(|l| safe_relative_path(Path::new(l))) {
+        let path = install_dir.join(&rel);
+        if let Err(e) = std::fs::remove_file(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                return Err(format!("Couldn't remove {}: {}", path.display(), e));
+            }
+        }
+        for dir in path.ancestors().skip(1).take_while(|d| *d != install_dir) {
+            if std::fs::remove_dir(dir).is_err() {
+                break;
+            }
+        }
+    }
+    std::fs::remove_file(&list).map_err(|e| e.to_string())
+}
+
+// ── Install Verification ────────────────────────────────────────────────────
+
+/// Hash of every file in an install, written with its metadata.
+const FILE_MANIFEST: &str = ".pulsar-files.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FileManifest {
+    /// Install-relative path (with `/` separators) to lowercase hex SHA256.
+    files: std::collections::BTreeMap<String, String>,
+}
+
+/// Hash every engine file under `dir` into its `.pulsar-files.json`.
+pub fn write_file_manifest(dir: &Path) -> std::io::Result<()> {
+    let mut manifest = FileManifest::default();
+    for entry in WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let Ok(rel) = entry.path().strip_prefix(dir) else {
+            continue;
+        };
+        let rel = rel.to_string_lossy().replace('\\', "/");
+        if pulsar_patch_tool::engine_delta::is_hub_file(&rel) {
+            continue;
+        }
+        manifest.files.insert(rel, sha256_file(entry.path())?);
+    }
+    let json = serde_json::to_string(&manifest).map_err(std::io::Error::other)?;
+    std::fs::write(dir.join(FILE_MANIFEST), json)
+}
+
+/// The file hashes recorded for the install at `dir`, if any.
+pub(crate) fn recorded_file_hashes(dir: &Path) -> Option<std::collections::BTreeMap<String, String>> {
+    let content = std::fs::read_to_string(dir.join(FILE_MANIFEST)).ok()?;
+    serde_json::from_str::<FileManifest>(&content).ok().map(|m| m.files)
+}
+
+/// What [`verify_install`] found.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InstallReport {
+    pub checked: usize,
+    /// Install-relative paths of files that are gone.
+    pub missing: Vec<String>,
+    /// Install-relative paths of files whose contents changed.
+    pub modified: Vec<String>,
+}
+
+impl InstallReport {
+    pub fn is_intact(&self) -> bool {
+        self.missing.is_empty() && self.modified.is_empty()
+    }
+
+    pub fn broken(&self) -> impl Iterator<Item = &String> {
+        self.missing.iter().chain(&self.modified)
+    }
+}
+
+/// Re-hash the files of the install at `dir` against the manifest recorded
+/// when it was installed.
+pub fn verify_install(dir: &Path) -> Result<InstallReport, String> {
+    let files = recorded_file_hashes(dir).ok_or_else(|| {
+        "This install predates integrity checks; reinstall it to enable them".to_string()
+    })?;
+    let mut report = InstallReport {
+        checked: files.len(),
+        ..Default::default()
+    };
+    for (rel, expected) in files {
+        match sha256_file(&dir.join(&rel)) {
+            Ok(actual) if actual == expected => {}
+            Ok(_) => report.modified.push(rel),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => report.missing.push(rel),
+            Err(_) => report.modified.push(rel),
+        }
+    }
+    Ok(report)
+}
+
+/// Restore the files `report` found broken in the install at `dir` by
+/// downloading the archives that contain them again (the full archive, or
+/// just the affected modules) and copying only those files over. Returns
+/// the install's state afterwards.
+pub fn repair_install(
+    dir: &Path,
+    report: &InstallReport,
+    progress: &Arc<Mutex<DownloadProgress>>,
+) -> Result<InstallReport, DownloadFailure> {
+    if report.is_intact() {
+        return Ok(report.clone());
+    }
+    let metadata = read_metadata(dir).ok_or_else(|| format!("{} isn't a Pulsar install", dir.display()))?;
+    let expected = recorded_file_hashes(dir).unwrap_or_default();
+    let release = fetch_release_by_tag_blocking(&metadata.version)?;
+    let mut pending: std::collections::BTreeSet<&str> = report.broken().map(String::as_str).collect();
+
+    // (asset, whether it's a module archive laid out relative to the root)
+    let mut archives: Vec<(&GitHubAsset, bool)> = Vec::new();
+    if metadata.modules.is_empty() {
+        let asset = find_platform_asset(&release)
+            .ok_or_else(|| format!("Release {} has no build for this platform", release.tag_name))?;
+        archives.push((asset, false));
+    } else {
+        let manifest = fetch_module_manifest(&release)?
+            .ok_or_else(|| format!("Release {} has no module manifest", release.tag_name))?;
+        let mut needs_core = pending.clone();
+        for id in metadata.modules.iter().filter(|id| *id != CORE_MODULE) {
+            let listed = std::fs::read_to_string(module_files_list(dir, id)).unwrap_or_default();
+            let hit = listed.lines().filter(|file| needs_core.remove(*file)).count() > 0;
+            let module = manifest.modules.iter().find(|m| &m.id == id);
+            if let (true, Some(asset)) = (hit, module.and_then(|m| m.asset(&release))) {
+                archives.push((asset, true));
+            }
+        }
+        if !needs_core.is_empty() {
+            let core = manifest
+                .modules
+                .iter()
+                .find(|m| m.is_core())
+                .and_then(|m| m.asset(&release))
+                .ok_or_else(|| format!("Release {} has no core module for this platform", release.tag_name))?;
+            archives.insert(0, (core, false));
+        }
+    }
+
+    let mut restored = Vec::new();
+    for (asset, is_module) in archives {
+        let integrity = fetch_asset_integrity(&release, asset)
+            .map_err(|e| format!("Couldn't fetch checksums for {}: {}", asset.name, e))?;
+        let Some((download, _)) =
+            download_verified(&asset.browser_download_url, dir, &release.tag_name, &integrity, progress)?
+        else {
+            return Err("Repair was cancelled".to_string().into());
+        };
+        let staging = sibling_dir(dir, "repair");
+        let _ = std::fs::remove_dir_all(&staging);
+        let result = std::fs::create_dir_all(&staging)
+            .map_err(|e| e.to_string())
+            .and_then(|()| {
+                let name = asset.name.to_lowercase();
+                if !is_module {
+                    unpack_asset(&download, AssetKind::for_url(&asset.browser_download_url), &staging)
+                } else if name.ends_with(".zip") {
+                    extract_zip(&download, &staging)
+                } else {
+                    extract_tar_gz(&download, &staging)
+                }
+            })
+            .map(|()| {
+                pending.retain(|rel| match restore_file(&staging, dir, rel, expected.get(*rel)) {
+                    Ok(true) => {
+                        restored.push(rel.to_string());
+                        false
+                    }
+                    Ok(false) => true,
+                    Err(e) => {
+                        tracing::warn!("Couldn't restore {}: {}", rel, e);
+                        true
+                    }
+                });
+            });
+        let _ = std::fs::remove_dir_all(&staging);
+        let _ = std::fs::remove_file(&download);
+        result?;
+    }
+
+    // The damaged copies may be store blobs other installs link to; never
+    // link this install back to them.
+    let damaged: Vec<&str> = restored
+        .iter()
+        .filter_map(|rel| expected.get(rel).map(String::as_str))
+        .collect();
+    EngineStoreService::evict(&damaged);
+    // Files still broken don't match their recorded hashes, which sharing
+    // would trust.
+    if pending.is_empty() {
+        share_install_files(dir);
+    }
+
+    verify_install(dir).map_err(DownloadFailure::from)
+}
+
+/// Copy `rel` from the unpacked archive at `staging` over the install at
+/// `dir`, if the archive has it with the `expected` contents. Replaces
+/// rather than writes through the old file, which may be hard-linked into
+/// the engine store.
+fn restore_file(staging: &Path, dir: &Path, rel: &str, expected: Option<&String>) -> Result<bool, String> {
+    let Some(rel_path) = safe_relative_path(Path::new(rel)) else {
+        return Ok(false);
+    };
+    let source = staging.join(&rel_path);
+    if !source.is_file() {
+        return Ok(false);
+    }
+    let actual = sha256_file(&source).map_err(|e| e.to_string())?;
+    if expected.is_some_and(|e| *e != actual) {
+        return Err("the release archive has different contents".to_string());
+    }
+    let target = dir.join(&rel_path);
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let _ = std::fs::remove_file(&target);
+    std::fs::rename(&source, &target).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+// ── Staged Extraction ───────────────────────────────────────────────────────
+
+/// How a downloaded engine asset becomes an install directory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AssetKind {
+    Zip,
+    TarGz,
+    /// A bare Windows executable.
+    Exe,
+}
+
+impl AssetKind {
+    fn for_url(url: &str) -> Self {
+        // Nightly assets are `.zip` archives — even on Windows, where `ext` is
+        // "exe" — so decide on the URL's extension before falling back to the
+        // raw-exe / tar.gz paths.
+        let (_, _, ext) = platform_info();
+        if url.to_lowercase().ends_with(".zip") {
+            AssetKind::Zip
+        } else if ext == "exe" {
+            AssetKind::Exe
+        } else {
+            AssetKind::TarGz
+        }
+    }
+}
+
+/// Where the asset at `url` is downloaded to: next to `dest_dir`, under the
+/// asset's own name so an interrupted download can be resumed.
+fn download_path_for(url: &str, dest_dir: &Path, kind: AssetKind, version: &str) -> PathBuf {
+    let name = url
+        .rsplit('/')
+        .next()
+        .filter(|n| !n.is_empty())
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| match kind {
+            AssetKind::Zip => format!("pulsar-{}.zip", version),
+            AssetKind::TarGz => format!("pulsar-{}.tar.gz", version),
+            AssetKind::Exe => format!("pulsar-{}.exe", version),
+        });
+    dest_dir.parent().unwrap_or(dest_dir).join(name)
+}
+
+/// Hidden sibling of `dest_dir` named after it with `suffix`.
+fn sibling_dir(dest_dir: &Path, suffix: &str) -> PathBuf {
+    let name = dest_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    dest_dir.with_file_name(format!(".{}.{}", name, suffix))
+}
+
+/// Whether `dir` holds something the hub can launch.
+fn has_engine_binary(dir: &Path) -> bool {
+    ["pulsar", "pulsar.exe", "pulsar_engine", "pulsar_engine.exe", "Contents/MacOS/pulsar"]
+        .iter()
+        .any(|name| dir.join(name).is_file())
+        || dir.join("pulsar.app").is_dir()
+        || dir.join("Contents").join("Info.plist").exists()
+}
+
+/// Turn the verified download at `download` into the install at `dest_dir`.
+///
+/// The asset is unpacked into a staging directory next to `dest_dir`, which
+/// must end up containing the engine binary before it's renamed into place.
+/// Metadata is written last, so a directory with `.pulsar-install.json` is
+/// always a complete install. On failure `dest_dir` is left as it was.
+fn install_downloaded_asset(
+    download: &Path,
+    kind: AssetKind,
+    dest_dir: &Path,
+    version: &str,
+    asset_name: &str,
+    digest: Option<&str>,
+) -> Result<(), String> {
+    let staging = sibling_dir(dest_dir, "staging");
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::create_dir_all(&staging).map_err(|e| e.to_string())?;
+
+    let staged = unpack_asset(download, kind, &staging)
+        .and_then(|()| {
+            if has_engine_binary(&staging) {
+                Ok(())
+            } else {
+                Err("The download doesn't contain the Pulsar engine binary".to_string())
+            }
+        })
+        .and_then(|()| replace_dir(&staging, dest_dir));
+    if let Err(e) = staged {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    write_metadata_with_digest(dest_dir, version, Some(asset_name), digest).map_err(|e| e.to_string())
+}
+
+/// Unpack a full engine asset into the empty directory `staging`, laid out
+/// as an install.
+fn unpack_asset(download: &Path, kind: AssetKind, staging: &Path) -> Result<(), String> {
+    match kind {
+        AssetKind::Zip => extract_zip(download, staging)
+            .and_then(|()| flatten_archive_root(staging))
+            .and_then(|()| place_engine_binary_at_root(staging)),
+        AssetKind::TarGz => extract_tar_gz(download, staging),
+        AssetKind::Exe => std::fs::copy(download, staging.join("pulsar.exe"))
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// Rename `staging` to `dest`. Anything already at `dest` is moved aside
+/// first and restored if the rename fails.
+fn replace_dir(staging: &Path, dest: &Path) -> Result<(), String> {
+    let backup = sibling_dir(dest, "old");
+    let existing = dest.exists();
+    if existing {
+        let _ = std::fs::remove_dir_all(&backup);
+        std::fs::rename(dest, &backup)
+            .map_err(|e| format!("Couldn't move {} aside: {}", dest.display(), e))?;
+    }
+    if let Err(e) = std::fs::rename(staging, dest) {
+        if existing {
+            let _ = std::fs::rename(&backup, dest);
         }
+        return Err(format!("Couldn't move the new install into {}: {}", dest.display(), e));
     }
-    // If there are already files at the root (e.g. pulsar.exe) or more than
-    // one subdirectory, don't try to flatten.
-    if root_files > 0 || subdirs.len() != 1 {
-        return;
+    if existing {
+        let _ = std::fs::remove_dir_all(&backup);
     }
-    let inner = &subdirs[0];
-    if let Ok(entries) = std::fs::read_dir(inner) {
-        for entry in entries.filter_map(|e| e.ok()) {
-            let name = entry.file_name();
-            let target = dest.join(&name);
-            let _ = if name.to_str().map(|n| n.ends_with('/')).unwrap_or(false) {
-                Ok(())
-            } else {
-                std::fs::rename(entry.path(), target)
-            };
+    Ok(())
+}
+
+/// `path` with `.` components dropped, or `None` if it's absolute or climbs
+/// out of the directory it's relative to.
+fn safe_relative_path(path: &Path) -> Option<PathBuf> {
+    use std::path::Component;
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => out.push(part),
+            Component::CurDir => {}
+            _ => return None,
         }
     }
-    let _ = std::fs::remove_dir_all(inner);
+    (!out.as_os_str().is_empty()).then_some(out)
 }
 
-/// Ensure the engine executable sits at the install root under the canonical
-/// name (`pulsar.exe` on Windows, `pulsar` elsewhere). Nightly archives ship
-/// the binary as `pulsar_engine.exe`, possibly inside subdirectories, so we
-/// locate it and rename it into place.
-fn place_engine_binary_at_root(dest: &Path) {
-    let canonical = if cfg!(windows) { "pulsar.exe" } else { "pulsar" };
-    let root_target = dest.join(canonical);
-    if root_target.exists() {
-        return;
+/// Whether a symlink at `link` (relative to the extraction root) pointing at
+/// `target` resolves to somewhere inside the root.
+fn link_stays_inside(link: &Path, target: &Path) -> bool {
+    use std::path::Component;
+    let mut depth = link.parent().map_or(0, |p| p.components().count());
+    for component in target.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => depth -= 1,
+            _ => return false,
+        }
     }
-    #[cfg(windows)]
-    let matches_binary = |name: &str| {
-        let lower = name.to_ascii_lowercase();
-        lower.starts_with("pulsar") && lower.ends_with(".exe")
-    };
-    #[cfg(not(windows))]
-    let matches_binary = |name: &str| {
-        let lower = name.to_ascii_lowercase();
-        lower == "pulsar" || (lower.starts_with("pulsar_") && !lower.contains('.'))
-    };
-    for entry in WalkDir::new(dest)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-    {
-        let name = entry.file_name().to_string_lossy();
-        if matches_binary(name.as_ref()) {
-            let _ = std::fs::rename(entry.path(), &root_target);
-            return;
+    true
+}
+
+/// Create the directory `rel` below the canonical extraction root `root` one
+/// component at a time, resolving links already extracted there, and return
+/// its canonical path. Fails if it resolves outside `root`, so a chain of
+/// links that each look harmless can't redirect later entries.
+fn create_dir_inside(root: &Path, rel: &Path) -> Result<PathBuf, String> {
+    let mut dir = root.to_path_buf();
+    for component in rel.components() {
+        let next = dir.join(component);
+        if std::fs::symlink_metadata(&next).is_err() {
+            std::fs::create_dir(&next).map_err(|e| format!("{}: {}", next.display(), e))?;
+        }
+        dir = next
+            .canonicalize()
+            .map_err(|e| format!("{}: {}", next.display(), e))?;
+        if !dir.starts_with(root) {
+            return Err(format!(
+                "Archive entry escapes the install directory through a link: {}",
+                rel.display()
+            ));
         }
     }
+    Ok(dir)
 }
 
+/// Extract a `.tar.gz` archive into `dest_dir`, refusing entries that would
+/// land outside it, directly or through a link.
 fn extract_tar_gz(archive_path: &Path, dest_dir: &Path) -> Result<(), String> {
     let file = std::fs::File::open(archive_path).map_err(|e| e.to_string())?;
     let dec = flate2::read::GzDecoder::new(file);
     let mut ar = tar::Archive::new(dec);
-    ar.unpack(dest_dir).map_err(|e| format!("Extract failed: {}", e))
+    let entries = ar.entries().map_err(|e| format!("Extract failed: {}", e))?;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("Extract failed: {}", e))?;
+        let path = entry
+            .path()
+            .map_err(|e| format!("Extract failed: {}", e))?
+            .into_owned();
+        if path.components().all(|c| c == std::path::Component::CurDir) {
+            continue;
+        }
+        let rel = safe_relative_path(&path)
+            .ok_or_else(|| format!("Archive entry escapes the install directory: {}", path.display()))?;
+
+        let kind = entry.header().entry_type();
+        if kind.is_symlink() || kind.is_hard_link() {
+            let target = entry
+                .link_name()
+                .map_err(|e| format!("Extract failed: {}", e))?
+                .ok_or_else(|| format!("Archive link without a target: {}", path.display()))?;
+            // Hard link targets are relative to the archive root, symlink
+            // targets to the link's own directory.
+            let inside = if kind.is_hard_link() {
+                safe_relative_path(&target).is_some()
+            } else {
+                link_stays_inside(&rel, &target)
+            };
+            if !inside {
+                return Err(format!(
+                    "Archive link points outside the install directory: {} -> {}",
+                    path.display(),
+                    target.display()
+                ));
+            }
+        }
+
+        if !entry
+            .unpack_in(dest_dir)
+            .map_err(|e| format!("Extract failed: {}", e))?
+        {
+            return Err(format!("Archive entry escapes the install directory: {}", path.display()));
+        }
+    }
+    Ok(())
 }
 
 /// Extract a `.zip` archive (e.g. a Nightly build containing the binary and
@@ -812,26 +1978,53 @@ fn extract_zip(archive_path: &Path, dest_dir: &Path) -> Result<(), String> {
 
     let file = std::fs::File::open(archive_path).map_err(|e| e.to_string())?;
     let mut zip = zip::ZipArchive::new(file).map_err(|e| format!("Invalid zip: {}", e))?;
+    std::fs::create_dir_all(dest_dir).map_err(|e| e.to_string())?;
+    let root = dest_dir.canonicalize().map_err(|e| e.to_string())?;
 
     for i in 0..zip.len() {
         let mut entry = zip
             .by_index(i)
             .map_err(|e| format!("Read zip entry: {}", e))?;
         let Some(rel) = entry.enclosed_name() else {
+            return Err(format!("Archive entry escapes the install directory: {}", entry.name()));
+        };
+        if entry.is_dir() {
+            create_dir_inside(&root, &rel)?;
+            continue;
+        }
+        // Every write goes to a canonical parent inside the root, never
+        // through a link; and never onto an entry that is itself a link.
+        let parent = create_dir_inside(&root, rel.parent().unwrap_or(Path::new("")))?;
+        let Some(name) = rel.file_name() else {
             continue;
         };
-        let out = dest_dir.join(rel);
+        let out = parent.join(name);
+        if std::fs::symlink_metadata(&out).is_ok_and(|m| m.file_type().is_symlink()) {
+            return Err(format!("Archive entry replaces a link: {}", rel.display()));
+        }
 
-        if entry.is_dir() {
-            std::fs::create_dir_all(&out).map_err(|e| e.to_string())?;
-        } else {
-            if let Some(parent) = out.parent() {
-                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        // Symlinks are stored as a file holding the link target.
+        let is_symlink = entry.unix_mode().is_some_and(|mode| mode & 0o170000 == 0o120000);
+        if is_symlink {
+            let mut target = String::new();
+            entry
+                .read_to_string(&mut target)
+                .map_err(|e| format!("Read zip entry: {}", e))?;
+            if !link_stays_inside(&rel, Path::new(&target)) {
+                return Err(format!(
+                    "Archive link points outside the install directory: {} -> {}",
+                    rel.display(),
+                    target
+                ));
             }
-            std::io::copy(&mut entry, &mut std::fs::File::create(&out).map_err(|e| e.to_string())?)
-                .map_err(|e| e.to_string())?;
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(&target, &out).map_err(|e| e.to_string())?;
+            continue;
         }
 
+        std::io::copy(&mut entry, &mut std::fs::File::create(&out).map_err(|e| e.to_string())?)
+            .map_err(|e| e.to_string())?;
+
         #[cfg(unix)]
         {
             use std::os::unix::fs::PermissionsExt;
@@ -846,48 +2039,281 @@ fn extract_zip(archive_path: &Path, dest_dir: &Path) -> Result<(), String> {
     Ok(())
 }
 
+/// How a call to [`download_file_with_progress`] ended without an error.
+#[derive(Debug, PartialEq, Eq)]
+enum Transfer {
+    Finished,
+    /// Paused or cancelled through [`DownloadProgress::control`].
+    Stopped,
+}
+
+/// How many times a dropped connection is resumed before giving up.
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 5;
+
+/// The path behind a `file://` URL, as published by local release sources.
+fn local_file_path(url: &str) -> Option<PathBuf> {
+    reqwest::Url::parse(url)
+        .ok()
+        .filter(|u| u.scheme() == "file")
+        .and_then(|u| u.to_file_path().ok())
+}
+
+/// Download `url` to `dest`, continuing from a partial `dest` left by an
+/// earlier paused or interrupted attempt with an HTTP Range request.
+/// `file://` URLs are copied, resuming the same way.
 fn download_file_with_progress(
-    mut reader: reqwest::blocking::Response,
+    client: &reqwest::blocking::Client,
+    url: &str,
     dest: PathBuf,
     progress: &Arc<Mutex<DownloadProgress>>,
-) -> Result<(), String> {
-    let mut file = std::fs::File::create(&dest).map_err(|e| e.to_string())?;
-    let mut downloaded: u64 = 0;
-    let mut last_bytes: u64 = 0;
-    let mut last_time = Instant::now();
+) -> Result<Transfer, String> {
     use std::io::Read;
-    let mut buf = [0u8; 8192];
+
+    let mut attempt = 0;
     loop {
-        let n = reader.read(&mut buf).map_err(|e| e.to_string())?;
-        if n == 0 {
-            break;
+        attempt += 1;
+        let existing = std::fs::metadata(&dest).map(|m| m.len()).unwrap_or(0);
+        let (mut reader, resumed, remaining): (Box<dyn Read>, bool, Option<u64>) =
+            if let Some(path) = local_file_path(url) {
+                // Release sources on disk or a network share.
+                use std::io::{Seek, SeekFrom};
+                let mut file = std::fs::File::open(&path)
+                    .map_err(|e| format!("{}: {}", path.display(), e))?;
+                let len = file.metadata().map_err(|e| e.to_string())?.len();
+                let resumed = existing > 0 && existing <= len;
+                if resumed {
+                    file.seek(SeekFrom::Start(existing)).map_err(|e| e.to_string())?;
+                }
+                (Box::new(file), resumed, Some(len - if resumed { existing } else { 0 }))
+            } else {
+                let mut request = client.get(url);
+                if existing > 0 {
+                    request = request.header(reqwest::header::RANGE, format!("bytes={}-", existing));
+                }
+                let resp = match request.send() {
+                    Ok(resp) => resp,
+                    Err(e) if attempt < MAX_DOWNLOAD_ATTEMPTS => {
+                        tracing::warn!("Download of {} failed (attempt {}): {}", url, attempt, e);
+                        if wait_before_retry(attempt, progress) {
+                            return Ok(Transfer::Stopped);
+                        }
+                        continue;
+                    }
+                    Err(e) => return Err(e.to_string()),
+                };
+                let status = resp.status();
+                if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE && existing > 0 {
+                    // Nothing left to fetch; the integrity check catches a partial
+                    // file that is actually corrupt.
+                    let mut p = progress.lock();
+                    p.bytes_downloaded = existing;
+                    p.total_bytes = p.total_bytes.max(existing);
+                    return Ok(Transfer::Finished);
+                }
+                if status.is_server_error() && attempt < MAX_DOWNLOAD_ATTEMPTS {
+                    tracing::warn!("Download of {} failed (attempt {}): HTTP {}", url, attempt, status);
+                    if wait_before_retry(attempt, progress) {
+                        return Ok(Transfer::Stopped);
+                    }
+                    continue;
+                }
+                if !status.is_success() {
+                    return Err(format!("HTTP {}", status));
+                }
+
+                let resumed = status == reqwest::StatusCode::PARTIAL_CONTENT;
+                let remaining = resp.content_length();
+                (Box::new(resp), resumed, remaining)
+            };
+        let mut downloaded = if resumed { existing } else { 0 };
+        let mut file = if resumed {
+            std::fs::OpenOptions::new().append(true).open(&dest)
+        } else {
+            std::fs::File::create(&dest)
+        }
+        .map_err(|e| e.to_string())?;
+        if let Some(len) = remaining {
+            progress.lock().total_bytes = downloaded + len;
         }
-        std::io::Write::write_all(&mut file, &buf[..n]).map_err(|e| e.to_string())?;
-        downloaded += n as u64;
-        let now = Instant::now();
-        let elapsed = now.duration_since(last_time).as_secs_f64();
-        if elapsed >= 0.15 {
-            let bytes_since = downloaded - last_bytes;
-            let speed = (bytes_since as f64 / elapsed) as u64;
+
+        let mut last_bytes = downloaded;
+        let mut last_time = Instant::now();
+        let (throttle_start, throttle_from) = (last_time, downloaded);
+        let mut buf = vec![0u8; 64 * 1024];
+        let read_error = loop {
+            let max_bps = {
+                let mut p = progress.lock();
+                if p.control != DownloadControl::Run {
+                    p.bytes_downloaded = downloaded;
+                    return Ok(Transfer::Stopped);
+                }
+                p.max_bps
+            };
+            if max_bps > 0 {
+                // Sleep until the average rate since this request started is
+                // back under the cap.
+                let due = std::time::Duration::from_secs_f64(
+                    (downloaded - throttle_from) as f64 / max_bps as f64,
+                );
+                let elapsed = throttle_start.elapsed();
+                if due > elapsed {
+                    std::thread::sleep((due - elapsed).min(std::time::Duration::from_millis(250)));
+                    continue;
+                }
+            }
+            let n = match reader.read(&mut buf) {
+                Ok(0) => break None,
+                Ok(n) => n,
+                Err(e) => break Some(e.to_string()),
+            };
+            std::io::Write::write_all(&mut file, &buf[..n]).map_err(|e| e.to_string())?;
+            downloaded += n as u64;
+            let now = Instant::now();
+            let elapsed = now.duration_since(last_time).as_secs_f64();
+            if elapsed >= 0.15 {
+                let bytes_since = downloaded - last_bytes;
+                let speed = (bytes_since as f64 / elapsed) as u64;
+                let mut p = progress.lock();
+                p.bytes_downloaded = downloaded;
+                p.speed_bps = speed;
+                last_bytes = downloaded;
+                last_time = now;
+            }
+        };
+        {
             let mut p = progress.lock();
             p.bytes_downloaded = downloaded;
-            p.speed_bps = speed;
-            last_bytes = downloaded;
-            last_time = now;
+            p.speed_bps = 0;
+        }
+
+        match read_error {
+            None => return Ok(Transfer::Finished),
+            Some(e) if attempt < MAX_DOWNLOAD_ATTEMPTS => {
+                tracing::warn!("Connection dropped at {} bytes, resuming: {}", downloaded, e);
+                if wait_before_retry(attempt, progress) {
+                    return Ok(Transfer::Stopped);
+                }
+            }
+            Some(e) => return Err(e),
         }
     }
-    {
-        let mut p = progress.lock();
-        p.bytes_downloaded = downloaded;
-        p.speed_bps = 0;
+}
+
+/// Back off before retry `attempt`, returning early (with `true`) if the
+/// download is paused or cancelled meanwhile.
+fn wait_before_retry(attempt: u32, progress: &Arc<Mutex<DownloadProgress>>) -> bool {
+    let deadline = Instant::now() + std::time::Duration::from_secs(1 << attempt.min(5));
+    while Instant::now() < deadline {
+        if progress.lock().control != DownloadControl::Run {
+            return true;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
     }
-    Ok(())
+    false
+}
+
+/// Record a paused or cancelled download. Cancelling also removes the
+/// partial file and the (still empty) install directory.
+fn finish_stopped(progress: &Arc<Mutex<DownloadProgress>>, partial: &Path, dest_dir: &Path) {
+    let mut p = progress.lock();
+    if p.control == DownloadControl::Cancel {
+        let _ = std::fs::remove_file(partial);
+        let _ = std::fs::remove_dir(dest_dir);
+        p.cancelled = true;
+    } else {
+        p.paused = true;
+    }
+    p.speed_bps = 0;
+    p.done = true;
 }
 
+/// Discard what a paused download of `release` left behind: the partial
+/// asset or delta next to `dest_dir` and any staging directory.
+pub fn remove_partial_download(release: &GitHubRelease, dest_dir: &Path) {
+    let parent = dest_dir.parent().unwrap_or(dest_dir);
+    for asset in &release.assets {
+        let _ = std::fs::remove_file(parent.join(&asset.name));
+    }
+    let _ = std::fs::remove_dir_all(sibling_dir(dest_dir, "staging"));
+    let _ = std::fs::remove_dir(dest_dir);
+}
+
+/// Delete the install at `dir`, then any store blobs no remaining install
+/// links to.
 pub fn remove_version(dir: &Path) -> Result<(), String> {
     if dir.exists() {
         std::fs::remove_dir_all(dir).map_err(|e| e.to_string())?;
     }
+    let remaining = scan_installed_versions();
+    let dirs: Vec<&Path> = remaining.iter().map(|v| v.metadata.install_path.as_path()).collect();
+    if let Err(e) = EngineStoreService::collect_garbage(&dirs) {
+        tracing::warn!("Couldn't clean up the engine store: {}", e);
+    }
+    Ok(())
+}
+
+/// Move the install at `dir` into the library folder `library`, keeping its
+/// directory name, and point its metadata at the new location. Returns the
+/// new install directory.
+pub fn move_install(dir: &Path, library: &Path) -> Result<PathBuf, String> {
+    let name = dir
+        .file_name()
+        .ok_or_else(|| format!("{} isn't an install directory", dir.display()))?;
+    let mut metadata =
+        read_metadata(dir).ok_or_else(|| format!("{} isn't a Pulsar install", dir.display()))?;
+    let target = library.join(name);
+    if target.exists() {
+        return Err(format!("{} already exists", target.display()));
+    }
+    std::fs::create_dir_all(library).map_err(|e| format!("{}: {}", library.display(), e))?;
+
+    if std::fs::rename(dir, &target).is_err() {
+        // Another drive: copy into a staging directory there first, so an
+        // interrupted move never leaves a half-copied install behind.
+        let staging = sibling_dir(&target, "moving");
+        let _ = std::fs::remove_dir_all(&staging);
+        if let Err(e) = copy_dir_all(dir, &staging).and_then(|()| {
+            std::fs::rename(&staging, &target).map_err(|e| e.to_string())
+        }) {
+            let _ = std::fs::remove_dir_all(&staging);
+            return Err(format!("Couldn't copy the install to {}: {}", library.display(), e));
+        }
+        std::fs::remove_dir_all(dir).map_err(|e| {
+            format!("Copied to {} but couldn't remove the original: {}", target.display(), e)
+        })?;
+    }
+
+    metadata.install_path = target.clone();
+    save_metadata(&target, &metadata).map_err(|e| e.to_string())?;
+    // Links into the engine store don't survive a copy to another drive.
+    share_install_files(&target);
+    Ok(target)
+}
+
+/// Copy the directory tree at `src` to `dest`, recreating symlinks.
+fn copy_dir_all(src: &Path, dest: &Path) -> Result<(), String> {
+    for entry in WalkDir::new(src).into_iter() {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let rel = entry.path().strip_prefix(src).map_err(|e| e.to_string())?;
+        let target = dest.join(rel);
+        let kind = entry.file_type();
+        let copied = if kind.is_dir() {
+            std::fs::create_dir_all(&target)
+        } else if kind.is_symlink() {
+            #[cfg(unix)]
+            {
+                std::fs::read_link(entry.path())
+                    .and_then(|link| std::os::unix::fs::symlink(link, &target))
+            }
+            #[cfg(not(unix))]
+            {
+                std::fs::copy(entry.path(), &target).map(|_| ())
+            }
+        } else {
+            std::fs::copy(entry.path(), &target).map(|_| ())
+        };
+        copied.map_err(|e| format!("{}: {}", entry.path().display(), e))?;
+    }
     Ok(())
 }
 
@@ -895,6 +2321,156 @@ pub fn open_install_dir(dir: &Path) {
     let _ = open::that(dir);
 }
 
+// ── Archive Import ──────────────────────────────────────────────────────────
+
+/// Files at the root of an engine archive that may name its version.
+const VERSION_FILES: &[&str] = &["VERSION", "version.txt", "pulsar-version.txt"];
+
+/// An engine archive unpacked into a library, waiting for its version to be
+/// confirmed before it becomes an install.
+#[derive(Debug, Clone)]
+pub struct PendingImport {
+    pub archive: PathBuf,
+    /// The version read from the archive, or else from its file name.
+    pub version: Option<String>,
+    library: PathBuf,
+    staging: PathBuf,
+}
+
+/// Unpack the engine archive (`.zip` or `.tar.gz`) at `archive` next to the
+/// default library, laid out like a downloaded install.
+pub fn prepare_import(archive: &Path) -> Result<PendingImport, String> {
+    let name = archive
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    // ASCII-only lowering keeps byte offsets, so suffix lengths apply to `name`.
+    let lower = name.to_ascii_lowercase();
+    let (stem, extract): (&str, fn(&Path, &Path) -> Result<(), String>) = if lower.ends_with(".zip") {
+        (&name[..name.len() - 4], extract_zip)
+    } else if lower.ends_with(".tar.gz") {
+        (&name[..name.len() - 7], extract_tar_gz)
+    } else if lower.ends_with(".tgz") {
+        (&name[..name.len() - 4], extract_tar_gz)
+    } else {
+        return Err(format!("{} isn't a .zip or .tar.gz archive", name));
+    };
+
+    let library = default_install_path();
+    let staging = sibling_dir(&library.join(stem), "import");
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::create_dir_all(&staging).map_err(|e| format!("{}: {}", staging.display(), e))?;
+
+    let unpacked = extract(archive, &staging)
+        .and_then(|()| flatten_archive_root(&staging))
+        .and_then(|()| place_engine_binary_at_root(&staging))
+        .and_then(|()| {
+            if has_engine_binary(&staging) {
+                Ok(())
+            } else {
+                Err(format!("{} doesn't contain the Pulsar engine binary", name))
+            }
+        });
+    if let Err(e) = unpacked {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    Ok(PendingImport {
+        archive: archive.to_path_buf(),
+        version: embedded_version(&staging).or_else(|| version_from_archive_name(&name)),
+        library,
+        staging,
+    })
+}
+
+/// Install the unpacked archive as `version`. Returns the install directory.
+pub fn finish_import(import: &PendingImport, version: &str) -> Result<PathBuf, String> {
+    let version = normalize_import_version(version)
+        .ok_or_else(|| format!("'{}' isn't a usable version", version.trim()))?;
+    let bare = version.trim_start_matches('v');
+    let existing = scan_installed_versions()
+        .into_iter()
+        .find(|v| v.metadata.version.trim_start_matches('v').eq_ignore_ascii_case(bare));
+    if let Some(existing) = existing {
+        return Err(format!(
+            "{} is already installed at {}",
+            version,
+            existing.metadata.install_path.display()
+        ));
+    }
+    let dest = import.library.join(bare);
+    if dest.exists() {
+        return Err(format!("{} already exists", dest.display()));
+    }
+    std::fs::rename(&import.staging, &dest)
+        .map_err(|e| format!("Couldn't move the import into {}: {}", dest.display(), e))?;
+    write_metadata(&dest, &version).map_err(|e| e.to_string())?;
+    share_install_files(&dest);
+    Ok(dest)
+}
+
+/// Drop an import that wasn't confirmed.
+pub fn cancel_import(import: &PendingImport) {
+    let _ = std::fs::remove_dir_all(&import.staging);
+}
+
+/// The version named by the first line of a version file in `dir`.
+fn embedded_version(dir: &Path) -> Option<String> {
+    VERSION_FILES.iter().find_map(|file| {
+        let content = std::fs::read_to_string(dir.join(file)).ok()?;
+        normalize_import_version(content.lines().next()?)
+            .filter(|v| parse_version(v).is_some() || v.starts_with("nightly-"))
+    })
+}
+
+/// The release tag in an asset name like `pulsar-1.4.2-linux-x86_64.tar.gz`
+/// or `pulsar-nightly-2025-01-31-abc1234-windows.zip`.
+pub fn version_from_archive_name(name: &str) -> Option<String> {
+    let lower = name.to_ascii_lowercase();
+    let stem = [".zip", ".tar.gz", ".tgz"]
+        .iter()
+        .find_map(|ext| lower.strip_suffix(ext))
+        .unwrap_or(&lower);
+    if let Some(start) = stem.find("nightly-") {
+        // nightly-YYYY-MM-DD, then the commit if one follows.
+        let parts: Vec<&str> = stem[start + "nightly-".len()..].split(['-', '_']).collect();
+        let date = parts.get(..3)?;
+        if !date.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit())) {
+            return None;
+        }
+        let mut tag = format!("nightly-{}", date.join("-"));
+        if let Some(commit) = parts
+            .get(3)
+            .filter(|p| p.len() >= 7 && p.chars().all(|c| c.is_ascii_hexdigit()))
+        {
+            tag.push('-');
+            tag.push_str(commit);
+        }
+        return Some(tag);
+    }
+    stem.split(['-', '_', ' '])
+        .find(|part| part.contains('.') && parse_version(part).is_some())
+        .map(|part| format!("v{}", part.trim_start_matches('v')))
+}
+
+/// `raw` as a release tag (`v1.2.3`, `nightly-…`), or `None` if it can't
+/// name an install directory.
+fn normalize_import_version(raw: &str) -> Option<String> {
+    let version = raw.trim();
+    if version.is_empty()
+        || version.starts_with('.')
+        || version.contains(['/', '\\'])
+        || version.eq_ignore_ascii_case("src")
+    {
+        return None;
+    }
+    Some(match parse_version(version) {
+        Some(_) if !version.starts_with(['v', 'V']) => format!("v{}", version),
+        _ => version.to_string(),
+    })
+}
+
 // ── Platform Helpers ────────────────────────────────────────────────────────
 
 pub fn platform_search_roots() -> Vec<PathBuf> {
@@ -928,7 +2504,13 @@ pub fn platform_search_roots() -> Vec<PathBuf> {
     }
 }
 
+/// The library new engines are installed into.
 pub fn default_install_path() -> PathBuf {
+    EngineLibraries::load().default_folder()
+}
+
+/// The platform's own library folder, used unless the user picks another.
+pub fn platform_default_install_path() -> PathBuf {
     let home = std::env::var("USERPROFILE")
         .or_else(|_| std::env::var("HOME"))
         .map(PathBuf::from)
@@ -1371,3 +2953,394 @@ fn dir_size(path: &Path) -> u64 {
 fn canonical_or_same(path: &Path) -> PathBuf {
     std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn satisfied(required: &str, versions: &[&str]) -> Vec<String> {
+        let req = EngineRequirement::parse(required).unwrap();
+        versions
+            .iter()
+            .filter(|v| req.matches(v))
+            .map(|v| v.to_string())
+            .collect()
+    }
+
+    const VERSIONS: [&str; 7] = ["v0.0.9", "v0.1.0", "v0.1.5", "v0.1.23", "v0.2.0", "v1.0.0", "v1.4.2"];
+
+    #[test]
+    fn parses_tags_as_semver() {
+        assert_eq!(parse_version("v0.1.23"), Some(semver::Version::new(0, 1, 23)));
+        assert_eq!(parse_version(" 1.2 "), Some(semver::Version::new(1, 2, 0)));
+        assert_eq!(parse_version("V2"), Some(semver::Version::new(2, 0, 0)));
+        assert_eq!(
+            parse_version("0.2-beta.1").map(|v| v.to_string()).as_deref(),
+            Some("0.2.0-beta.1")
+        );
+        assert_eq!(parse_version("src"), None);
+        assert_eq!(parse_version("nightly-abc"), None);
+        assert!(parse_version("v1.0.0-rc.1") < parse_version("v1.0.0"));
+    }
+
+    #[test]
+    fn bare_versions_are_minimums() {
+        assert_eq!(satisfied("0.1.23", &VERSIONS), ["v0.1.23", "v0.2.0", "v1.0.0", "v1.4.2"]);
+        assert_eq!(satisfied("v0.2", &VERSIONS), ["v0.2.0", "v1.0.0", "v1.4.2"]);
+    }
+
+    #[test]
+    fn caret_and_tilde_ranges() {
+        assert_eq!(satisfied("^0.1", &VERSIONS), ["v0.1.0", "v0.1.5", "v0.1.23"]);
+        assert_eq!(satisfied("^0.1.5", &VERSIONS), ["v0.1.5", "v0.1.23"]);
+        assert_eq!(satisfied("^1", &VERSIONS), ["v1.0.0", "v1.4.2"]);
+        assert_eq!(satisfied("~0.1.5", &VERSIONS), ["v0.1.5", "v0.1.23"]);
+        assert_eq!(satisfied("~1", &VERSIONS), ["v1.0.0", "v1.4.2"]);
+    }
+
+    #[test]
+    fn comparators_and_bounds() {
+        assert_eq!(satisfied("<0.2", &VERSIONS), ["v0.0.9", "v0.1.0", "v0.1.5", "v0.1.23"]);
+        assert_eq!(satisfied(">0.1.23", &VERSIONS), ["v0.2.0", "v1.0.0", "v1.4.2"]);
+        assert_eq!(satisfied("=0.1.5", &VERSIONS), ["v0.1.5"]);
+        assert_eq!(satisfied("<=v0.1.0", &VERSIONS), ["v0.0.9", "v0.1.0"]);
+        // Comma- and whitespace-separated comparators, operators apart from
+        // their version.
+        assert_eq!(satisfied(">=0.1.5, <1", &VERSIONS), ["v0.1.5", "v0.1.23", "v0.2.0"]);
+        assert_eq!(satisfied(">=0.1.5 <1", &VERSIONS), ["v0.1.5", "v0.1.23", "v0.2.0"]);
+        assert_eq!(satisfied(">= 0.1.5 < 0.2", &VERSIONS), ["v0.1.5", "v0.1.23"]);
+    }
+
+    #[test]
+    fn wildcards_and_alternatives() {
+        assert_eq!(satisfied("0.1.*", &VERSIONS), ["v0.1.0", "v0.1.5", "v0.1.23"]);
+        assert_eq!(satisfied("1.x", &VERSIONS), ["v1.0.0", "v1.4.2"]);
+        assert_eq!(satisfied("*", &VERSIONS), VERSIONS);
+        assert_eq!(satisfied("^0.1.20 || >=1.4", &VERSIONS), ["v0.1.23", "v1.4.2"]);
+    }
+
+    #[test]
+    fn pre_releases_need_an_explicit_opt_in() {
+        let versions = ["v0.2.0-beta.1", "v0.2.0", "v0.3.0-rc.1"];
+        assert_eq!(satisfied(">=0.1", &versions), ["v0.2.0"]);
+        assert_eq!(satisfied(">=0.2.0-beta", &versions), ["v0.2.0-beta.1", "v0.2.0"]);
+        assert_eq!(satisfied("=0.3.0-rc.1", &versions), ["v0.3.0-rc.1"]);
+    }
+
+    #[test]
+    fn src_and_nightly_are_exact() {
+        assert_eq!(EngineRequirement::parse(" SRC "), Ok(EngineRequirement::Src));
+        assert!(installed_satisfies("src", "src"));
+        assert!(!installed_satisfies("v0.1.0", "src"));
+        assert!(!installed_satisfies("src", ">=0.1"));
+
+        let nightly = "nightly-2025-01-31-abc1234";
+        assert_eq!(
+            EngineRequirement::parse(nightly),
+            Ok(EngineRequirement::Nightly(nightly.to_string()))
+        );
+        assert!(installed_satisfies(nightly, nightly));
+        assert!(!installed_satisfies("nightly-2025-02-01-def5678", nightly));
+        assert!(!installed_satisfies("v1.0.0", nightly));
+    }
+
+    #[test]
+    fn rejects_invalid_requirements() {
+        for bad in ["", "   ", ">=", "^abc", "0.1.2.3", ">=0.1 ||", "latest"] {
+            assert!(EngineRequirement::parse(bad).is_err(), "accepted {:?}", bad);
+            assert!(!installed_satisfies("v0.1.0", bad));
+        }
+    }
+
+    #[test]
+    fn best_match_picks_the_highest_satisfying_version() {
+        let tags = ["v0.1.5", "v1.4.2", "v0.1.23", "v0.2.0", "nightly-abc"];
+        let best = |req: &str| EngineRequirement::parse(req).unwrap().best_match(tags).map(str::to_string);
+        assert_eq!(best("^0.1").as_deref(), Some("v0.1.23"));
+        assert_eq!(best("<1").as_deref(), Some("v0.2.0"));
+        assert_eq!(best("0.1.0").as_deref(), Some("v1.4.2"));
+        assert_eq!(best("^2"), None);
+        assert_eq!(best("nightly-abc").as_deref(), Some("nightly-abc"));
+        assert_eq!(best("src"), None);
+    }
+
+    #[test]
+    fn verifies_against_sha256sums() {
+        let sums = "\
+0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef  pulsar-linux-x86_64.tar.gz.sha256
+E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855 *dist/pulsar-linux-x86_64.tar.gz
+";
+        let expected = parse_sha256sums(sums, "pulsar-linux-x86_64.tar.gz").unwrap();
+        assert_eq!(expected, "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(parse_sha256sums(sums, "pulsar-windows-x86_64.zip"), None);
+
+        let path = std::env::temp_dir().join("pulsar_hub_checksum_test");
+        std::fs::write(&path, b"").unwrap();
+        let mut integrity = AssetIntegrity {
+            asset_name: "pulsar-linux-x86_64.tar.gz".into(),
+            sha256: Some(expected.clone()),
+            minisig: None,
+        };
+        assert_eq!(integrity.verify(&path).unwrap(), Some(expected));
+
+        integrity.sha256 = Some("0".repeat(64));
+        let failure = integrity.verify(&path).unwrap_err();
+        assert!(failure.is_integrity_failure());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    fn write_tar_gz(path: &Path, link: (&str, &str)) {
+        let file = std::fs::File::create(path).unwrap();
+        let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(file, Default::default()));
+        let mut header = tar::Header::new_gnu();
+        header.set_size(4);
+        header.set_mode(0o755);
+        tar.append_data(&mut header, "pulsar", &b"\x7fELF"[..]).unwrap();
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        tar.append_link(&mut header, link.0, link.1).unwrap();
+        tar.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[test]
+    fn staged_install_rejects_escaping_links_and_keeps_old_install() {
+        let dir = std::env::temp_dir().join("pulsar_hub_staging_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        let dest = dir.join("engines").join("1.2.3");
+        std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
+
+        let good = dir.join("good.tar.gz");
+        write_tar_gz(&good, ("lib/libpulsar.so", "libpulsar.so.1"));
+        install_downloaded_asset(&good, AssetKind::TarGz, &dest, "1.2.3", "engine.tar.gz", None).unwrap();
+        assert!(dest.join("pulsar").is_file());
+        assert!(dest.join(".pulsar-install.json").is_file());
+        assert!(!sibling_dir(&dest, "staging").exists());
+
+        let bad = dir.join("bad.tar.gz");
+        write_tar_gz(&bad, ("lib/escape", "../../../outside"));
+        let err = install_downloaded_asset(&bad, AssetKind::TarGz, &dest, "1.2.3", "engine.tar.gz", None).unwrap_err();
+        assert!(err.contains("outside the install directory"), "{}", err);
+        assert!(dest.join(".pulsar-install.json").is_file());
+        assert!(!sibling_dir(&dest, "staging").exists());
+
+        let fresh = dir.join("engines").join("2.0.0");
+        assert!(install_downloaded_asset(&bad, AssetKind::TarGz, &fresh, "2.0.0", "engine.tar.gz", None).is_err());
+        assert!(!fresh.exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn delta_and_full_installs_of_a_release_satisfy_the_same_lock() {
+        use crate::service::project_service::{EngineResolution, ProjectService};
+
+        let dir = std::env::temp_dir().join("pulsar_hub_delta_lock_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        let engines = dir.join("engines");
+        std::fs::create_dir_all(&engines).unwrap();
+        std::fs::create_dir_all(dir.join("full")).unwrap();
+
+        let base = engines.join("1.2.3");
+        let old = dir.join("old.tar.gz");
+        write_tar_gz(&old, ("lib/libpulsar.so", "libpulsar.so.1"));
+        install_downloaded_asset(&old, AssetKind::TarGz, &base, "1.2.3", "pulsar-1.2.3-linux.tar.gz", None).unwrap();
+
+        let new = dir.join("new.tar.gz");
+        {
+            let file = std::fs::File::create(&new).unwrap();
+            let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(file, Default::default()));
+            let mut header = tar::Header::new_gnu();
+            header.set_size(5);
+            header.set_mode(0o755);
+            tar.append_data(&mut header, "pulsar", &b"\x7fELF2"[..]).unwrap();
+            tar.into_inner().unwrap().finish().unwrap();
+        }
+        let archive = AssetIntegrity {
+            asset_name: "pulsar-1.2.4-linux.tar.gz".to_string(),
+            sha256: Some(sha256_file(&new).unwrap()),
+            minisig: None,
+        };
+        let full = dir.join("full").join("1.2.4");
+        let digest = archive.verify(&new).unwrap();
+        install_downloaded_asset(&new, AssetKind::TarGz, &full, "1.2.4", &archive.asset_name, digest.as_deref())
+            .unwrap();
+
+        let delta = dir.join("delta.tar");
+        pulsar_patch_tool::engine_delta::generate_engine_delta(&base, &full, "1.2.3", "1.2.4", &delta).unwrap();
+        let patched = engines.join("1.2.4");
+        install_downloaded_delta(&delta, &AssetIntegrity::default(), &archive, &base, &patched, "1.2.4").unwrap();
+        assert_eq!(std::fs::read(patched.join("pulsar")).unwrap(), b"\x7fELF2");
+
+        let installed = |dir: &Path| InstalledVersion {
+            metadata: read_metadata(dir).unwrap(),
+            disk_size_bytes: 0,
+            shared_bytes: 0,
+            update_available: false,
+        };
+        let project = dir.join("project");
+        std::fs::create_dir_all(&project).unwrap();
+        assert!(matches!(
+            ProjectService::resolve_project_engine(&project, &[installed(&full)]),
+            EngineResolution::Installed(_)
+        ));
+        assert!(matches!(
+            ProjectService::resolve_project_engine(&project, &[installed(&patched)]),
+            EngineResolution::Installed(_)
+        ));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn zip_links_chained_out_of_the_install_are_refused() {
+        let dir = std::env::temp_dir().join("pulsar_hub_zip_link_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // `a/l2` resolves to the root, so `l1` -> `a/l2/..` is the root's
+        // parent even though the text of each link stays inside.
+        let archive = dir.join("chained.zip");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&archive).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        zip.add_symlink("a/l2", "..", options).unwrap();
+        zip.add_symlink("l1", "a/l2/..", options).unwrap();
+        zip.start_file("l1/x", options).unwrap();
+        std::io::Write::write_all(&mut zip, b"escaped").unwrap();
+        zip.finish().unwrap();
+
+        let dest = dir.join("dest");
+        let err = extract_zip(&archive, &dest).unwrap_err();
+        assert!(err.contains("escapes the install directory"), "{}", err);
+        assert!(!dir.join("x").exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn module_selection_always_includes_core() {
+        let (os, arch, _) = platform_info();
+        let asset = |name: &str| GitHubAsset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.com/{}", name),
+            size: 1,
+        };
+        let module = |id: &str, default: bool, key: &str| EngineModule {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            default,
+            assets: [(key.to_string(), format!("{}.tar.gz", id))].into_iter().collect(),
+        };
+        let platform = format!("{}-{}", os, arch);
+        let release = GitHubRelease {
+            tag_name: "v1.0.0".to_string(),
+            name: String::new(),
+            body: String::new(),
+            assets: ["core.tar.gz", "docs.tar.gz", "symbols.tar.gz", "web.tar.gz"]
+                .into_iter()
+                .map(asset)
+                .collect(),
+            prerelease: false,
+            published_at: String::new(),
+        };
+        let manifest = ModuleManifest {
+            modules: vec![
+                module("docs", true, "any"),
+                module(CORE_MODULE, true, &platform),
+                module("symbols", false, &platform),
+                module("web", false, "some-other-platform"),
+            ],
+        };
+        let ids = |modules: Vec<EngineModule>| modules.into_iter().map(|m| m.id).collect::<Vec<_>>();
+
+        assert_eq!(ids(manifest.available(&release)), ["core", "docs", "symbols"]);
+        assert_eq!(ids(manifest.selection(&release, &[]).unwrap()), ["core", "docs"]);
+        assert_eq!(
+            ids(manifest.selection(&release, &["symbols".to_string()]).unwrap()),
+            ["core", "symbols"]
+        );
+        assert!(manifest.selection(&release, &["web".to_string()]).is_err());
+    }
+
+    #[test]
+    fn modules_are_removed_by_their_file_list() {
+        let dir = std::env::temp_dir().join("pulsar_hub_module_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        let dest = dir.join("engines").join("1.2.3");
+        std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
+        let core = dir.join("core.tar.gz");
+        write_tar_gz(&core, ("lib/libpulsar.so", "libpulsar.so.1"));
+        install_downloaded_asset(&core, AssetKind::TarGz, &dest, "1.2.3", "engine.tar.gz", None).unwrap();
+
+        let symbols = dir.join("symbols.tar.gz");
+        {
+            let file = std::fs::File::create(&symbols).unwrap();
+            let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(file, Default::default()));
+            let mut header = tar::Header::new_gnu();
+            header.set_size(3);
+            header.set_mode(0o644);
+            tar.append_data(&mut header, "symbols/pulsar.debug", &b"dbg"[..]).unwrap();
+            tar.into_inner().unwrap().finish().unwrap();
+        }
+        add_module_files(&symbols, &dest, "symbols").unwrap();
+        assert!(dest.join("symbols").join("pulsar.debug").is_file());
+
+        remove_module_files(&dest, "symbols").unwrap();
+        assert!(!dest.join("symbols").exists());
+        assert!(!module_files_list(&dest, "symbols").exists());
+        assert!(dest.join("pulsar").is_file());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn verify_reports_missing_and_modified_files() {
+        let dir = std::env::temp_dir().join("pulsar_hub_verify_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        let dest = dir.join("engines").join("1.2.3");
+        std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
+        let archive = dir.join("engine.tar.gz");
+        write_tar_gz(&archive, ("lib/libpulsar.so", "libpulsar.so.1"));
+        install_downloaded_asset(&archive, AssetKind::TarGz, &dest, "1.2.3", "engine.tar.gz", None).unwrap();
+        std::fs::write(dest.join("README.md"), "engine docs").unwrap();
+        write_file_manifest(&dest).unwrap();
+
+        let intact = verify_install(&dest).unwrap();
+        assert!(intact.is_intact());
+        assert_eq!(intact.checked, 2);
+
+        std::fs::write(dest.join("pulsar"), "tampered").unwrap();
+        std::fs::remove_file(dest.join("README.md")).unwrap();
+        let report = verify_install(&dest).unwrap();
+        assert_eq!(report.missing, ["README.md"]);
+        assert_eq!(report.modified, ["pulsar"]);
+
+        // Restoring only takes a file whose contents match the manifest.
+        let staging = dir.join("staging");
+        std::fs::create_dir_all(&staging).unwrap();
+        std::fs::write(staging.join("README.md"), "engine docs").unwrap();
+        std::fs::write(staging.join("pulsar"), "something else").unwrap();
+        let expected = recorded_file_hashes(&dest).unwrap();
+        assert!(restore_file(&staging, &dest, "README.md", expected.get("README.md")).unwrap());
+        assert!(restore_file(&staging, &dest, "pulsar", expected.get("pulsar")).is_err());
+        assert_eq!(verify_install(&dest).unwrap().broken().collect::<Vec<_>>(), ["pulsar"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn archive_names_give_release_tags() {
+        let name = version_from_archive_name;
+        assert_eq!(name("pulsar-1.4.2-linux-x86_64.tar.gz").as_deref(), Some("v1.4.2"));
+        assert_eq!(name("Pulsar_v0.3.zip").as_deref(), Some("v0.3"));
+        assert_eq!(
+            name("pulsar-nightly-2025-01-31-abc1234-windows.zip").as_deref(),
+            Some("nightly-2025-01-31-abc1234")
+        );
+        assert_eq!(name("engine-linux-x86_64.tgz"), None);
+        assert_eq!(normalize_import_version(" 1.2.3\n").as_deref(), Some("v1.2.3"));
+        assert_eq!(normalize_import_version("../evil"), None);
+    }
+}
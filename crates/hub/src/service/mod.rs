@@ -1,9 +1,16 @@
 pub mod auth_service;
 pub mod cloud_service;
 pub mod dependency_service;
+pub mod download_queue_service;
+pub mod engine_library_service;
+pub mod engine_store_service;
 pub mod git_service;
+pub mod github_service;
+pub mod http_cache_service;
+pub mod hub_update_service;
 pub mod integration_service;
 pub mod installer_service;
 pub mod plugin_service;
 pub mod project_service;
+pub mod release_source_service;
 pub mod thumbnail_service;
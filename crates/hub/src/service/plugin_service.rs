@@ -2,6 +2,8 @@ use std::path::Path;
 
 use crate::core::types::*;
 use crate::service::git_service::GitService;
+use crate::service::github_service::GitHubService;
+use crate::service::http_cache_service::HttpCacheService;
 
 fn registry_local_path(registries_root: &Path, url: &str) -> std::path::PathBuf {
     let slug = url
@@ -128,12 +130,18 @@ impl PluginService {
             .split_once('/')?;
         // List JSON files in the plugins/ directory via GitHub Contents API
         let list_url = format!("https://api.github.com/repos/{owner}/{repo}/contents/plugins");
-        let resp = client.get(&list_url).send().ok()?;
-        if !resp.status().is_success() {
-            tracing::warn!("GitHub API returned {} for {list_url}", resp.status());
+        let resp = match HttpCacheService::get(client, &list_url, std::time::Duration::ZERO) {
+            Ok(resp) => resp,
+            Err(e) => {
+                tracing::warn!("Fetching {list_url} failed: {e}");
+                return None;
+            }
+        };
+        if !resp.status.is_success() {
+            tracing::warn!("GitHub API returned {} for {list_url}", resp.status);
             return None;
         }
-        let files: Vec<serde_json::Value> = resp.json().ok()?;
+        let files: Vec<serde_json::Value> = serde_json::from_str(&resp.body).ok()?;
         let mut plugins = Vec::new();
         for file in files {
             let name = file.get("name")?.as_str()?;
@@ -141,12 +149,11 @@ impl PluginService {
                 continue;
             }
             let download_url = file.get("download_url")?.as_str()?;
-            let raw_resp = client.get(download_url).send().ok()?;
-            if !raw_resp.status().is_success() {
+            let raw_resp = HttpCacheService::get(client, download_url, std::time::Duration::ZERO).ok()?;
+            if !raw_resp.status.is_success() {
                 continue;
             }
-            let text: String = raw_resp.text().ok()?;
-            if let Ok(mut plugin) = serde_json::from_str::<RegistryPlugin>(&text) {
+            if let Ok(mut plugin) = serde_json::from_str::<RegistryPlugin>(&raw_resp.body) {
                 plugin.registry_url = registry_url.to_string();
                 plugins.push(plugin);
             }
@@ -179,7 +186,7 @@ impl PluginService {
             .user_agent("Pulsar-Native/1.0")
             .build()
             .map_err(|e| e.to_string())?;
-        let resp = client.get(&url).send().map_err(|e| e.to_string())?;
+        let resp = GitHubService::get(&client, &url)?;
         if !resp.status().is_success() {
             return Err(format!("GitHub API returned {}", resp.status()));
         }
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
+use crate::service::installer_service::{self, InstalledVersion};
+
 /// A single recent project entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecentProject {
@@ -40,6 +42,48 @@ impl RecentProjectsList {
     }
 }
 
+/// Lockfile next to `Pulsar.toml` pinning the engine build a project opens in.
+pub const ENGINE_LOCK_FILE: &str = "Pulsar.lock";
+
+/// The exact engine build a project was resolved to, recorded in
+/// [`ENGINE_LOCK_FILE`] so everyone opens the project in the same build.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EngineLock {
+    /// Engine version or nightly tag, as installed.
+    pub version: String,
+    /// The `engine_version` requirement it was resolved from, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requirement: Option<String>,
+    /// SHA256 of the archive the engine was installed from, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_sha256: Option<String>,
+    /// Release asset `archive_sha256` is the digest of. Digests are only
+    /// compared between installs from the same asset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EngineLockFile {
+    engine: EngineLock,
+}
+
+/// Which engine to open a project with.
+#[derive(Debug, Clone)]
+pub enum EngineResolution {
+    /// The local source checkout registered as the `src` engine.
+    Src,
+    /// This installed engine, which the project is now locked to.
+    Installed(InstalledVersion),
+    /// Nothing installed matches; this requirement has to be installed first.
+    Missing(String),
+    /// The locked version is installed, but from a different build than the
+    /// lock records; it has to be reinstalled first.
+    Mismatch(InstalledVersion),
+    /// The project has no requirement and no engine is installed.
+    NoEngine,
+}
+
 /// Pure functions for project lifecycle
 pub struct ProjectService;
 
@@ -112,6 +156,138 @@ default_scene = "scenes/main.scene"
             .filter(|s| !s.is_empty())
     }
 
+    /// The project's engine lock, unless it is missing or unreadable.
+    pub fn read_engine_lock(project_path: &Path) -> Option<EngineLock> {
+        let content = std::fs::read_to_string(project_path.join(ENGINE_LOCK_FILE)).ok()?;
+        match toml::from_str::<EngineLockFile>(&content) {
+            Ok(file) => Some(file.engine),
+            Err(e) => {
+                tracing::warn!("Ignoring invalid {} in {}: {}", ENGINE_LOCK_FILE, project_path.display(), e);
+                None
+            }
+        }
+    }
+
+    pub fn write_engine_lock(project_path: &Path, lock: &EngineLock) -> std::io::Result<()> {
+        let body = toml::to_string_pretty(&EngineLockFile {
+            engine: lock.clone(),
+        })
+        .map_err(std::io::Error::other)?;
+        let content = format!(
+            "# Written by Pulsar Hub. Commit this file so everyone opens the project\n\
+             # in the same engine build; use \"Upgrade engine\" to move it forward.\n\n{}",
+            body
+        );
+        // Write beside the lock and rename over it, so the old lock stays
+        // intact until the new one is complete.
+        let path = project_path.join(ENGINE_LOCK_FILE);
+        let tmp = path.with_extension("lock.tmp");
+        std::fs::write(&tmp, content)?;
+        std::fs::rename(&tmp, &path).inspect_err(|_| {
+            let _ = std::fs::remove_file(&tmp);
+        })
+    }
+
+    pub fn remove_engine_lock(project_path: &Path) -> std::io::Result<()> {
+        match std::fs::remove_file(project_path.join(ENGINE_LOCK_FILE)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// The project's engine lock if it still satisfies `engine_version`. A
+    /// lock left behind by an edited requirement is ignored, so the project
+    /// re-resolves.
+    pub fn valid_engine_lock(project_path: &Path) -> Option<EngineLock> {
+        let lock = Self::read_engine_lock(project_path)?;
+        match Self::project_engine_version(project_path) {
+            Some(required) if !installer_service::installed_satisfies(&lock.version, &required) => {
+                tracing::info!(
+                    "{} pins engine {}, which no longer satisfies '{}'",
+                    ENGINE_LOCK_FILE,
+                    lock.version,
+                    required
+                );
+                None
+            }
+            _ => Some(lock),
+        }
+    }
+
+    /// Pick the engine to open `project_path` with: the locked build if there
+    /// is a valid lock, otherwise the newest installed engine satisfying
+    /// `engine_version`, which is then written to the lock.
+    pub fn resolve_project_engine(project_path: &Path, installed: &[InstalledVersion]) -> EngineResolution {
+        let required = Self::project_engine_version(project_path);
+        if required.as_deref().is_some_and(|r| r.eq_ignore_ascii_case("src")) {
+            return EngineResolution::Src;
+        }
+
+        if let Some(lock) = Self::valid_engine_lock(project_path) {
+            let Some(engine) = installed.iter().find(|v| same_version(&v.metadata.version, &lock.version)) else {
+                return EngineResolution::Missing(exact_requirement(&lock.version));
+            };
+            // A module install records its core archive, so only a digest of
+            // the same asset says anything about the build.
+            let same_asset = lock.archive_name.is_some() && lock.archive_name == engine.metadata.archive_name;
+            if let (Some(locked), Some(actual)) = (&lock.archive_sha256, &engine.metadata.archive_sha256) {
+                if same_asset && !locked.eq_ignore_ascii_case(actual) {
+                    tracing::warn!(
+                        "Engine {} is installed from a different build than {} records ({} vs {})",
+                        lock.version,
+                        ENGINE_LOCK_FILE,
+                        actual,
+                        locked
+                    );
+                    return EngineResolution::Mismatch(engine.clone());
+                }
+            }
+            return EngineResolution::Installed(engine.clone());
+        }
+
+        Self::lock_newest_engine(project_path, required, installed)
+    }
+
+    /// Lock `project_path` to the newest installed engine satisfying its
+    /// requirement, ignoring and replacing any existing lock. An existing
+    /// lock is left alone if nothing installed matches.
+    pub fn relock_project_engine(project_path: &Path, installed: &[InstalledVersion]) -> EngineResolution {
+        let required = Self::project_engine_version(project_path);
+        Self::lock_newest_engine(project_path, required, installed)
+    }
+
+    fn lock_newest_engine(
+        project_path: &Path,
+        required: Option<String>,
+        installed: &[InstalledVersion],
+    ) -> EngineResolution {
+        let engine = match &required {
+            Some(required) => installed
+                .iter()
+                .filter(|v| installer_service::installed_satisfies(&v.metadata.version, required))
+                .max_by_key(|v| installer_service::parse_version(&v.metadata.version)),
+            None => installed.first(),
+        };
+        let Some(engine) = engine else {
+            return match required {
+                Some(required) => EngineResolution::Missing(required),
+                None => EngineResolution::NoEngine,
+            };
+        };
+        if !engine.metadata.version.eq_ignore_ascii_case("src") {
+            let lock = EngineLock {
+                version: engine.metadata.version.clone(),
+                requirement: required,
+                archive_sha256: engine.metadata.archive_sha256.clone(),
+                archive_name: engine.metadata.archive_name.clone(),
+            };
+            if let Err(e) = Self::write_engine_lock(project_path, &lock) {
+                tracing::warn!("Failed to write {} in {}: {}", ENGINE_LOCK_FILE, project_path.display(), e);
+            }
+        }
+        EngineResolution::Installed(engine.clone())
+    }
+
     /// Read tool preferences from Pulsar.toml
     pub fn load_tool_preferences(project_path: &PathBuf) -> (Option<String>, Option<String>) {
         let config_path = project_path.join("Pulsar.toml");
@@ -137,3 +313,104 @@ default_scene = "scenes/main.scene"
         (None, None)
     }
 }
+
+fn same_version(a: &str, b: &str) -> bool {
+    a.trim().trim_start_matches(['v', 'V'])
+        .eq_ignore_ascii_case(b.trim().trim_start_matches(['v', 'V']))
+}
+
+/// An `engine_version` requirement matching only `version`.
+pub fn exact_requirement(version: &str) -> String {
+    match installer_service::parse_version(version) {
+        Some(v) => format!("={}", v),
+        None => version.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::installer_service::PulsarInstallMetadata;
+
+    fn engine(version: &str) -> InstalledVersion {
+        InstalledVersion {
+            metadata: PulsarInstallMetadata {
+                version: version.to_string(),
+                install_date: String::new(),
+                install_path: PathBuf::from(format!("/engines/{}", version)),
+                archive_sha256: Some(format!("sha-{}", version)),
+                archive_name: Some(format!("pulsar-{}-linux.tar.gz", version)),
+                modules: Vec::new(),
+            },
+            disk_size_bytes: 0,
+            shared_bytes: 0,
+            update_available: false,
+        }
+    }
+
+    fn set_requirement(project: &Path, required: &str) {
+        std::fs::write(
+            project.join("Pulsar.toml"),
+            format!("[project]\nname = \"lock\"\nengine_version = \"{}\"\n", required),
+        )
+        .unwrap();
+    }
+
+    fn resolved(project: &Path, installed: &[InstalledVersion]) -> String {
+        match ProjectService::resolve_project_engine(project, installed) {
+            EngineResolution::Installed(v) => v.metadata.version,
+            EngineResolution::Missing(required) => format!("missing {}", required),
+            EngineResolution::Mismatch(v) => format!("reinstall {}", v.metadata.version),
+            other => format!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn first_open_locks_the_engine_and_later_opens_keep_it() {
+        let project = std::env::temp_dir().join("pulsar_engine_lock_test");
+        let _ = std::fs::remove_dir_all(&project);
+        std::fs::create_dir_all(&project).unwrap();
+        set_requirement(&project, "^0.1");
+
+        let mut installed = vec![engine("0.1.5"), engine("0.2.0")];
+        assert_eq!(resolved(&project, &installed), "0.1.5");
+        let lock = ProjectService::read_engine_lock(&project).unwrap();
+        assert_eq!(lock.version, "0.1.5");
+        assert_eq!(lock.requirement.as_deref(), Some("^0.1"));
+        assert_eq!(lock.archive_sha256.as_deref(), Some("sha-0.1.5"));
+
+        // A newer matching install doesn't move a locked project...
+        installed.push(engine("0.1.9"));
+        assert_eq!(resolved(&project, &installed), "0.1.5");
+        // ...nor does the same build installed from its modules...
+        installed[0].metadata.archive_name = Some("pulsar-0.1.5-linux-core.tar.gz".to_string());
+        installed[0].metadata.archive_sha256 = Some("sha-core".to_string());
+        assert_eq!(resolved(&project, &installed), "0.1.5");
+        // ...but a different build of it has to be reinstalled...
+        installed[0].metadata.archive_name = Some("pulsar-0.1.5-linux.tar.gz".to_string());
+        installed[0].metadata.archive_sha256 = Some("sha-rebuilt".to_string());
+        assert_eq!(resolved(&project, &installed), "reinstall 0.1.5");
+        // ...and a missing locked build has to be installed exactly.
+        installed.remove(0);
+        assert_eq!(resolved(&project, &installed), "missing =0.1.5");
+
+        // Editing the requirement past the lock re-resolves it.
+        set_requirement(&project, ">=0.2");
+        assert_eq!(resolved(&project, &installed), "0.2.0");
+        assert_eq!(ProjectService::read_engine_lock(&project).unwrap().version, "0.2.0");
+
+        // Upgrading replaces the lock in place with the newest match.
+        installed.push(engine("0.2.3"));
+        assert!(matches!(
+            ProjectService::relock_project_engine(&project, &installed),
+            EngineResolution::Installed(v) if v.metadata.version == "0.2.3"
+        ));
+        assert_eq!(ProjectService::read_engine_lock(&project).unwrap().version, "0.2.3");
+
+        ProjectService::remove_engine_lock(&project).unwrap();
+        ProjectService::remove_engine_lock(&project).unwrap();
+        assert!(ProjectService::read_engine_lock(&project).is_none());
+
+        let _ = std::fs::remove_dir_all(&project);
+    }
+}
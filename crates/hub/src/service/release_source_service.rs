@@ -0,0 +1,484 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::service::http_cache_service::HttpCacheService;
+use crate::service::installer_service::{
+    self as svc, ChannelFilter, ChannelSource, GitHubAsset, GitHubRelease, ReleaseChannel,
+};
+
+/// One page of releases from a [`ReleaseSource`].
+#[derive(Debug, Clone, Default)]
+pub struct ReleasePage {
+    pub releases: Vec<GitHubRelease>,
+    pub has_more: bool,
+}
+
+/// Somewhere engine releases are published. All methods block and are called
+/// from background threads.
+pub trait ReleaseSource: std::fmt::Debug + Send + Sync {
+    /// Id that channels in `release_sources.json` refer to this source by.
+    fn id(&self) -> &str;
+
+    /// Page `page` (1-based) of releases, newest first.
+    fn fetch_page(&self, page: u32) -> Result<ReleasePage, String>;
+
+    /// The last fetched copy of `page`, if cached, for showing something
+    /// before `fetch_page` returns. Cheap enough for the UI thread.
+    fn cached_page(&self, _page: u32) -> Option<ReleasePage> {
+        None
+    }
+
+    /// The release for an engine version or tag (`0.1.23`, `v0.1.23`,
+    /// `nightly-…`), or `Ok(None)` if this source doesn't publish it.
+    fn fetch_release(&self, tag: &str) -> Result<Option<GitHubRelease>, String>;
+}
+
+// ── GitHub ──────────────────────────────────────────────────────────────────
+
+/// Releases of an `owner/repo` on GitHub.
+#[derive(Debug)]
+pub struct GitHubSource {
+    id: String,
+    repo: String,
+}
+
+impl GitHubSource {
+    pub fn new(id: impl Into<String>, repo: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            repo: repo.into(),
+        }
+    }
+}
+
+impl ReleaseSource for GitHubSource {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn fetch_page(&self, page: u32) -> Result<ReleasePage, String> {
+        let releases = svc::fetch_repo_releases_blocking(&self.repo, page)?;
+        Ok(ReleasePage {
+            has_more: releases.len() == svc::RELEASES_PER_PAGE as usize,
+            releases,
+        })
+    }
+
+    fn cached_page(&self, page: u32) -> Option<ReleasePage> {
+        let releases = svc::cached_repo_releases(&self.repo, page)?;
+        Some(ReleasePage {
+            has_more: releases.len() == svc::RELEASES_PER_PAGE as usize,
+            releases,
+        })
+    }
+
+    fn fetch_release(&self, tag: &str) -> Result<Option<GitHubRelease>, String> {
+        // Engine releases are tagged `v<version>`; nightlies keep their tag.
+        let tag = if tag.to_lowercase().starts_with("nightly-") || tag.starts_with(['v', 'V']) {
+            tag.to_string()
+        } else {
+            format!("v{}", tag)
+        };
+        svc::fetch_repo_release_by_tag_blocking(&self.repo, &tag)
+    }
+}
+
+// ── JSON Index ──────────────────────────────────────────────────────────────
+
+/// Releases listed in a JSON document served over HTTP, for mirrors that
+/// aren't on GitHub. The document is either an array of releases or
+/// `{"releases": [...]}`, each in the shape of a GitHub release:
+///
+/// ```json
+/// { "releases": [ {
+///     "tag_name": "v0.1.23", "name": "0.1.23", "body": "Notes…",
+///     "prerelease": false, "published_at": "2025-01-31T12:00:00Z",
+///     "assets": [ { "name": "pulsar-linux-x86_64.tar.gz",
+///                   "browser_download_url": "0.1.23/pulsar-linux-x86_64.tar.gz",
+///                   "size": 123456 } ] } ] }
+/// ```
+///
+/// Relative asset URLs resolve against the index URL.
+#[derive(Debug)]
+pub struct JsonIndexSource {
+    id: String,
+    url: String,
+}
+
+impl JsonIndexSource {
+    pub fn new(id: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            url: url.into(),
+        }
+    }
+
+    fn releases(&self) -> Result<Vec<GitHubRelease>, String> {
+        let client = reqwest::blocking::Client::builder()
+            .user_agent("Pulsar-Hub/1.0")
+            .timeout(std::time::Duration::from_secs(30))
+            .connect_timeout(std::time::Duration::from_secs(10))
+            .build()
+            .map_err(|e| e.to_string())?;
+        let resp = HttpCacheService::get(&client, &self.url, std::time::Duration::ZERO)?;
+        if !resp.status.is_success() {
+            return Err(format!("HTTP {} fetching {}", resp.status, self.url));
+        }
+        let base = reqwest::Url::parse(&self.url).map_err(|e| e.to_string())?;
+        parse_index(&resp.body, &base)
+    }
+}
+
+impl ReleaseSource for JsonIndexSource {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn fetch_page(&self, page: u32) -> Result<ReleasePage, String> {
+        Ok(paginate(self.releases()?, page))
+    }
+
+    fn cached_page(&self, page: u32) -> Option<ReleasePage> {
+        let base = reqwest::Url::parse(&self.url).ok()?;
+        let releases = parse_index(&HttpCacheService::cached(&self.url)?, &base).ok()?;
+        Some(paginate(releases, page))
+    }
+
+    fn fetch_release(&self, tag: &str) -> Result<Option<GitHubRelease>, String> {
+        Ok(find_tag(self.releases()?, tag))
+    }
+}
+
+// ── Local Directory ─────────────────────────────────────────────────────────
+
+/// A folder of builds, e.g. on a network share. An `index.json` in the folder
+/// is read like a [`JsonIndexSource`], with URLs relative to the folder.
+/// Without one, every subfolder is a release named after its tag, every file
+/// in it an asset, and `RELEASE_NOTES.md` its notes.
+#[derive(Debug)]
+pub struct LocalDirSource {
+    id: String,
+    path: PathBuf,
+}
+
+/// Release notes file inside a local release folder; not an asset.
+const LOCAL_RELEASE_NOTES: &str = "RELEASE_NOTES.md";
+
+impl LocalDirSource {
+    pub fn new(id: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            id: id.into(),
+            path: path.into(),
+        }
+    }
+
+    fn releases(&self) -> Result<Vec<GitHubRelease>, String> {
+        let index = self.path.join("index.json");
+        if index.is_file() {
+            let base = reqwest::Url::from_directory_path(&self.path)
+                .map_err(|()| format!("{} is not an absolute path", self.path.display()))?;
+            let text = std::fs::read_to_string(&index).map_err(|e| e.to_string())?;
+            return parse_index(&text, &base);
+        }
+
+        let entries = std::fs::read_dir(&self.path)
+            .map_err(|e| format!("Reading {}: {}", self.path.display(), e))?;
+        let mut releases = Vec::new();
+        for entry in entries.filter_map(|e| e.ok()) {
+            if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                if let Some(release) = local_release(&entry.path()) {
+                    releases.push(release);
+                }
+            }
+        }
+        svc::sort_releases_newest_first(&mut releases);
+        Ok(releases)
+    }
+}
+
+impl ReleaseSource for LocalDirSource {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn fetch_page(&self, page: u32) -> Result<ReleasePage, String> {
+        Ok(paginate(self.releases()?, page))
+    }
+
+    fn fetch_release(&self, tag: &str) -> Result<Option<GitHubRelease>, String> {
+        Ok(find_tag(self.releases()?, tag))
+    }
+}
+
+/// The release described by a folder of a [`LocalDirSource`].
+fn local_release(dir: &Path) -> Option<GitHubRelease> {
+    let tag = dir.file_name()?.to_string_lossy().into_owned();
+    let published_at = std::fs::metadata(dir)
+        .and_then(|m| m.modified())
+        .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339())
+        .unwrap_or_default();
+    let mut assets = Vec::new();
+    for entry in std::fs::read_dir(dir).ok()?.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if !meta.is_file() || entry.file_name() == LOCAL_RELEASE_NOTES {
+            continue;
+        }
+        let Ok(url) = reqwest::Url::from_file_path(&path) else {
+            continue;
+        };
+        assets.push(GitHubAsset {
+            name: entry.file_name().to_string_lossy().into_owned(),
+            browser_download_url: url.to_string(),
+            size: meta.len(),
+        });
+    }
+    assets.sort_by(|a, b| a.name.cmp(&b.name));
+    Some(GitHubRelease {
+        name: tag.clone(),
+        prerelease: tag.to_lowercase().starts_with("nightly-"),
+        body: std::fs::read_to_string(dir.join(LOCAL_RELEASE_NOTES)).unwrap_or_default(),
+        tag_name: tag,
+        assets,
+        published_at,
+    })
+}
+
+// ── Shared Helpers ──────────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum IndexDocument {
+    List(Vec<GitHubRelease>),
+    Wrapped { releases: Vec<GitHubRelease> },
+}
+
+/// Parse a release index, resolving relative asset URLs against `base`.
+/// Assets must be http(s); `file://` is only accepted from an index that is
+/// itself on disk (a [`LocalDirSource`]), so a remote index can't point the
+/// hub at local paths.
+fn parse_index(text: &str, base: &reqwest::Url) -> Result<Vec<GitHubRelease>, String> {
+    let doc: IndexDocument =
+        serde_json::from_str(text).map_err(|e| format!("Invalid release index: {}", e))?;
+    let mut releases = match doc {
+        IndexDocument::List(releases) | IndexDocument::Wrapped { releases } => releases,
+    };
+    for asset in releases.iter_mut().flat_map(|r| r.assets.iter_mut()) {
+        let url = base
+            .join(&asset.browser_download_url)
+            .map_err(|e| format!("Invalid asset URL {}: {}", asset.browser_download_url, e))?;
+        let allowed = match url.scheme() {
+            "http" | "https" => true,
+            "file" => base.scheme() == "file",
+            _ => false,
+        };
+        if !allowed {
+            return Err(format!("Unsupported asset URL {}", url));
+        }
+        asset.browser_download_url = url.to_string();
+    }
+    svc::sort_releases_newest_first(&mut releases);
+    Ok(releases)
+}
+
+/// Page `page` of an already complete, newest-first release list.
+fn paginate(releases: Vec<GitHubRelease>, page: u32) -> ReleasePage {
+    let per_page = svc::RELEASES_PER_PAGE as usize;
+    let skip = page.saturating_sub(1) as usize * per_page;
+    let has_more = releases.len() > skip + per_page;
+    ReleasePage {
+        releases: releases.into_iter().skip(skip).take(per_page).collect(),
+        has_more,
+    }
+}
+
+fn find_tag(releases: Vec<GitHubRelease>, tag: &str) -> Option<GitHubRelease> {
+    let wanted = tag.trim_start_matches(['v', 'V']);
+    releases
+        .into_iter()
+        .find(|r| r.tag_name.trim_start_matches(['v', 'V']) == wanted)
+}
+
+// ── Configuration ───────────────────────────────────────────────────────────
+
+/// A release source as written in `release_sources.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SourceConfig {
+    Github { id: String, repo: String },
+    JsonIndex { id: String, url: String },
+    LocalDir { id: String, path: PathBuf },
+}
+
+impl SourceConfig {
+    pub fn id(&self) -> &str {
+        match self {
+            SourceConfig::Github { id, .. }
+            | SourceConfig::JsonIndex { id, .. }
+            | SourceConfig::LocalDir { id, .. } => id,
+        }
+    }
+
+    pub fn build(&self) -> Arc<dyn ReleaseSource> {
+        match self {
+            SourceConfig::Github { id, repo } => Arc::new(GitHubSource::new(id, repo)),
+            SourceConfig::JsonIndex { id, url } => Arc::new(JsonIndexSource::new(id, url)),
+            SourceConfig::LocalDir { id, path } => Arc::new(LocalDirSource::new(id, path)),
+        }
+    }
+}
+
+/// The user-editable list of release sources and the channels built on them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseSourcesConfig {
+    pub sources: Vec<SourceConfig>,
+    pub channels: Vec<ReleaseChannel>,
+}
+
+impl Default for ReleaseSourcesConfig {
+    fn default() -> Self {
+        let channel = |name: &str, source: &str, filter, enabled_by_default| ReleaseChannel {
+            name: name.to_string(),
+            source: source.to_string(),
+            filter,
+            enabled_by_default,
+        };
+        Self {
+            sources: vec![
+                SourceConfig::Github {
+                    id: "pulsar-native".to_string(),
+                    repo: "Far-Beyond-Pulsar/Pulsar-Native".to_string(),
+                },
+                SourceConfig::Github {
+                    id: "nightly".to_string(),
+                    repo: "Far-Beyond-Pulsar/Nightly".to_string(),
+                },
+            ],
+            channels: vec![
+                channel("Stable", "pulsar-native", ChannelFilter::Stable, true),
+                channel("Alpha", "pulsar-native", ChannelFilter::Alpha, true),
+                channel("Nightly", "nightly", ChannelFilter::All, false),
+            ],
+        }
+    }
+}
+
+impl ReleaseSourcesConfig {
+    fn path() -> PathBuf {
+        directories::ProjectDirs::from("com", "Pulsar", "Pulsar_Engine")
+            .map(|d| d.data_dir().join("release_sources.json"))
+            .unwrap_or_else(|| PathBuf::from("release_sources.json"))
+    }
+
+    /// Load the config. The defaults are written out on first use so there's
+    /// a file to edit; a file that doesn't parse is left alone.
+    pub fn load() -> Self {
+        let path = Self::path();
+        match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring invalid {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => {
+                let config = Self::default();
+                config.save();
+                config
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            let _ = std::fs::write(path, json);
+        }
+    }
+
+    /// Pagination state for every configured source.
+    pub fn channel_sources(&self) -> Vec<ChannelSource> {
+        self.sources
+            .iter()
+            .map(|s| ChannelSource::new(s.build()))
+            .collect()
+    }
+
+    /// Channels selected when the hub starts.
+    pub fn default_channels(&self) -> Vec<ReleaseChannel> {
+        self.channels
+            .iter()
+            .filter(|c| c.enabled_by_default)
+            .cloned()
+            .collect()
+    }
+
+    /// Sources worth asking for `tag`: those with a channel that would list
+    /// it come first, then the rest.
+    pub fn sources_for_tag(&self, tag: &str) -> Vec<Arc<dyn ReleaseSource>> {
+        let listed = |s: &&SourceConfig| {
+            self.channels
+                .iter()
+                .any(|c| c.source == s.id() && c.includes_tag(tag))
+        };
+        let (first, rest): (Vec<_>, Vec<_>) = self.sources.iter().partition(listed);
+        first.into_iter().chain(rest).map(|s| s.build()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_index_resolves_urls_and_pages() {
+        let index = r#"{ "releases": [
+            { "tag_name": "v0.1.22", "name": "0.1.22", "body": "", "prerelease": false,
+              "published_at": "2025-01-01T00:00:00Z",
+              "assets": [ { "name": "a.tar.gz", "browser_download_url": "0.1.22/a.tar.gz", "size": 1 } ] },
+            { "tag_name": "v0.1.23", "name": "0.1.23", "body": "", "prerelease": false,
+              "published_at": "2025-02-01T00:00:00Z",
+              "assets": [ { "name": "a.tar.gz", "browser_download_url": "https://cdn.example/a.tar.gz", "size": 1 } ] }
+        ] }"#;
+        let base = reqwest::Url::parse("https://builds.example/pulsar/index.json").unwrap();
+        let releases = parse_index(index, &base).unwrap();
+        assert_eq!(releases[0].tag_name, "v0.1.23");
+        assert_eq!(
+            releases[1].assets[0].browser_download_url,
+            "https://builds.example/pulsar/0.1.22/a.tar.gz"
+        );
+        assert_eq!(releases[0].assets[0].browser_download_url, "https://cdn.example/a.tar.gz");
+        assert_eq!(find_tag(releases.clone(), "0.1.22").unwrap().tag_name, "v0.1.22");
+
+        // A remote index can't point at local files; one on disk can.
+        let local = index.replace("https://cdn.example/a.tar.gz", "file:///srv/builds/a.tar.gz");
+        assert!(parse_index(&local, &base).is_err());
+        let dir = reqwest::Url::parse("file:///srv/builds/").unwrap();
+        assert!(parse_index(&local, &dir).is_ok());
+
+        let many: Vec<_> = (0..svc::RELEASES_PER_PAGE + 1).map(|_| releases[0].clone()).collect();
+        assert!(paginate(many.clone(), 1).has_more);
+        let last = paginate(many, 2);
+        assert_eq!(last.releases.len(), 1);
+        assert!(!last.has_more);
+    }
+
+    #[test]
+    fn nightly_tags_ask_the_nightly_source_first() {
+        let config = ReleaseSourcesConfig::default();
+        let ids = |tag| {
+            config
+                .sources_for_tag(tag)
+                .iter()
+                .map(|s| s.id().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids("nightly-abc123"), ["nightly", "pulsar-native"]);
+        assert_eq!(ids("0.1.23"), ["pulsar-native", "nightly"]);
+    }
+}
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use gpui::RenderImage;
 
 use crate::core::types::Template;
+use crate::service::github_service::GitHubService;
 
 /// Pure functions for thumbnail loading paths and downloading
 pub struct ThumbnailService;
@@ -73,7 +74,7 @@ impl ThumbnailService {
             .user_agent("Pulsar-Native/1.0")
             .build()
             .ok()?;
-        let response = client.get(url).send().ok()?;
+        let response = GitHubService::get(&client, url).ok()?;
         if !response.status().is_success() {
             return None;
         }
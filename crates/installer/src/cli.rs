@@ -0,0 +1,485 @@
+//! Headless command line interface. With no subcommand the hub window opens;
+//! with one, the command runs without a display and exits.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use parking_lot::Mutex;
+use pulsar_hub::installer_service as svc;
+use pulsar_hub::project_service::{EngineResolution, ProjectService, RecentProjectsList};
+use pulsar_installer::updater;
+use serde::Serialize;
+
+#[derive(Parser, Default)]
+#[command(name = "pulsar-installer", version, about = "Pulsar Hub and engine manager")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Print machine-readable JSON instead of text
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Set when relaunched into a freshly installed build
+    #[arg(long, hide = true)]
+    pub updated: bool,
+
+    /// Apply an update bundle (directory or archive) and exit; same as `update --from`
+    #[arg(long, value_name = "PATH")]
+    pub update_from: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Manage installed engine versions
+    #[command(subcommand)]
+    Engines(EnginesCommand),
+    /// Inspect and open projects
+    #[command(subcommand)]
+    Projects(ProjectsCommand),
+    /// Update pulsar-installer itself
+    Update {
+        /// Read the update from a local bundle instead of the network
+        #[arg(long, value_name = "PATH")]
+        from: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum EnginesCommand {
+    /// List installed engine versions
+    List,
+    /// Download and install an engine release
+    Install {
+        /// Release tag or version, e.g. `0.1.23`, `v0.1.23` or `nightly-<hash>`
+        tag: String,
+        /// Module to install, for releases shipped in modules (repeatable;
+        /// defaults to the release's default modules)
+        #[arg(long = "module", value_name = "ID")]
+        modules: Vec<String>,
+    },
+    /// Install an engine from a `.zip` or `.tar.gz` archive on disk
+    Import {
+        archive: PathBuf,
+        /// Version to install it as; read from the archive when omitted
+        #[arg(long = "as", value_name = "VERSION")]
+        version: Option<String>,
+    },
+    /// Remove an installed engine version
+    Remove { version: String },
+    /// Check an installed engine's files against the hashes recorded at
+    /// install time
+    Verify {
+        version: String,
+        /// Download the release again and restore missing or modified files
+        #[arg(long)]
+        repair: bool,
+    },
+    /// Start an installed engine (the newest one if no version is given)
+    Launch {
+        version: Option<String>,
+        /// Project directory to open in the engine
+        #[arg(long)]
+        project: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProjectsCommand {
+    /// List recently opened projects
+    List,
+    /// Open a project with the engine pinned in its `Pulsar.lock`, or the
+    /// newest installed one satisfying its `engine_version` (which is locked)
+    Open { path: PathBuf },
+}
+
+#[derive(Serialize)]
+struct EngineEntry {
+    version: String,
+    path: PathBuf,
+    install_date: String,
+    size_bytes: u64,
+    /// Part of `size_bytes` shared with other installed versions.
+    shared_bytes: u64,
+}
+
+#[derive(Serialize)]
+struct ImportOutcome {
+    version: String,
+    path: PathBuf,
+}
+
+#[derive(Serialize)]
+struct VerifyOutcome {
+    version: String,
+    checked: usize,
+    missing: Vec<String>,
+    modified: Vec<String>,
+    /// Whether a repair was attempted.
+    repaired: bool,
+}
+
+#[derive(Serialize)]
+struct ProjectEntry {
+    name: String,
+    path: String,
+    last_opened: Option<String>,
+    engine_version: Option<String>,
+}
+
+#[derive(Serialize)]
+struct UpdateOutcome {
+    current_version: String,
+    /// The version installed, or `None` when already up to date.
+    installed_version: Option<String>,
+}
+
+/// Run a headless command, returning the process exit code.
+pub fn run(command: Command, json: bool) -> i32 {
+    let result = match command {
+        Command::Engines(cmd) => run_engines(cmd, json),
+        Command::Projects(cmd) => run_projects(cmd, json),
+        Command::Update { from } => run_update(from.as_deref(), json),
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            if json {
+                print_json(&serde_json::json!({ "error": format!("{:#}", e) }));
+            } else {
+                eprintln!("error: {:#}", e);
+            }
+            1
+        }
+    }
+}
+
+fn print_json(value: &impl Serialize) {
+    match serde_json::to_string_pretty(value) {
+        Ok(text) => println!("{}", text),
+        Err(e) => eprintln!("error: failed to serialize output: {}", e),
+    }
+}
+
+fn engine_entry(v: &svc::InstalledVersion) -> EngineEntry {
+    EngineEntry {
+        version: v.metadata.version.clone(),
+        path: v.metadata.install_path.clone(),
+        install_date: v.metadata.install_date.clone(),
+        size_bytes: v.disk_size_bytes,
+        shared_bytes: v.shared_bytes,
+    }
+}
+
+fn same_version(a: &str, b: &str) -> bool {
+    a.trim().trim_start_matches(['v', 'V']) == b.trim().trim_start_matches(['v', 'V'])
+}
+
+fn run_engines(cmd: EnginesCommand, json: bool) -> Result<()> {
+    match cmd {
+        EnginesCommand::List => {
+            let engines: Vec<EngineEntry> =
+                svc::scan_installed_versions().iter().map(engine_entry).collect();
+            if json {
+                print_json(&engines);
+            } else if engines.is_empty() {
+                println!("No engine versions installed.");
+            } else {
+                for e in &engines {
+                    println!(
+                        "{:<24} {:>10}  {}",
+                        e.version,
+                        pulsar_hub::format_bytes(e.size_bytes),
+                        e.path.display()
+                    );
+                }
+            }
+        }
+        EnginesCommand::Install { tag, modules } => {
+            let entry = install_engine(&tag, &modules, json)?;
+            if json {
+                print_json(&entry);
+            } else {
+                println!("Installed {} to {}", entry.version, entry.path.display());
+            }
+        }
+        EnginesCommand::Remove { version } => {
+            let installed = svc::scan_installed_versions();
+            let target = installed
+                .iter()
+                .find(|v| same_version(&v.metadata.version, &version))
+                .with_context(|| format!("engine {} is not installed", version))?;
+            svc::remove_version(&target.metadata.install_path).map_err(anyhow::Error::msg)?;
+            if json {
+                print_json(&engine_entry(target));
+            } else {
+                println!(
+                    "Removed {} from {}",
+                    target.metadata.version,
+                    target.metadata.install_path.display()
+                );
+            }
+        }
+        EnginesCommand::Import { archive, version } => {
+            let import = svc::prepare_import(&archive).map_err(anyhow::Error::msg)?;
+            let version = match version.or_else(|| import.version.clone()) {
+                Some(version) => version,
+                None => {
+                    svc::cancel_import(&import);
+                    anyhow::bail!(
+                        "couldn't tell the version of {}; pass it with --as",
+                        archive.display()
+                    );
+                }
+            };
+            if !json {
+                eprintln!("Importing {} as {}", archive.display(), version);
+            }
+            let path = svc::finish_import(&import, &version).map_err(|e| {
+                svc::cancel_import(&import);
+                anyhow::Error::msg(e)
+            })?;
+            let version = svc::read_metadata(&path).map_or(version, |m| m.version);
+            if json {
+                print_json(&ImportOutcome { version, path });
+            } else {
+                println!("Imported {} to {}", version, path.display());
+            }
+        }
+        EnginesCommand::Verify { version, repair } => {
+            let installed = svc::scan_installed_versions();
+            let target = installed
+                .iter()
+                .find(|v| same_version(&v.metadata.version, &version))
+                .with_context(|| format!("engine {} is not installed", version))?;
+            let dir = &target.metadata.install_path;
+            let mut report = svc::verify_install(dir).map_err(anyhow::Error::msg)?;
+            let repaired = repair && !report.is_intact();
+            if repaired {
+                if !json {
+                    eprintln!("Repairing {} broken file(s)", report.broken().count());
+                }
+                let progress = Arc::new(Mutex::new(svc::DownloadProgress::default()));
+                report = svc::repair_install(dir, &report, &progress)
+                    .map_err(|e| anyhow::anyhow!("repairing {} failed: {}", version, e))?;
+            }
+            if json {
+                print_json(&VerifyOutcome {
+                    version: target.metadata.version.clone(),
+                    checked: report.checked,
+                    missing: report.missing.clone(),
+                    modified: report.modified.clone(),
+                    repaired,
+                });
+            } else {
+                for file in &report.missing {
+                    println!("missing   {}", file);
+                }
+                for file in &report.modified {
+                    println!("modified  {}", file);
+                }
+                println!(
+                    "{}: {} of {} files intact",
+                    target.metadata.version,
+                    report.checked - report.broken().count(),
+                    report.checked
+                );
+            }
+            if !report.is_intact() {
+                anyhow::bail!("engine {} is damaged", target.metadata.version);
+            }
+        }
+        EnginesCommand::Launch { version, project } => {
+            let installed = svc::scan_installed_versions();
+            let engine = match &version {
+                Some(version) => installed
+                    .iter()
+                    .find(|v| same_version(&v.metadata.version, version))
+                    .with_context(|| format!("engine {} is not installed", version))?,
+                None => installed.first().context("no engine versions installed")?,
+            };
+            launch(engine, project.as_deref(), json)?;
+        }
+    }
+    Ok(())
+}
+
+fn install_engine(tag: &str, modules: &[String], json: bool) -> Result<EngineEntry> {
+    let release = svc::fetch_release_by_tag_blocking(tag).map_err(anyhow::Error::msg)?;
+    if !svc::has_platform_build(&release) {
+        anyhow::bail!("release {} has no build for this platform", release.tag_name);
+    }
+    if !modules.is_empty() && !svc::has_module_manifest(&release) {
+        anyhow::bail!("release {} isn't split into modules", release.tag_name);
+    }
+    let dest = svc::default_install_path().join(release.tag_name.trim_start_matches('v'));
+
+    if !json {
+        match svc::find_platform_asset(&release).filter(|_| !svc::has_module_manifest(&release)) {
+            Some(asset) => {
+                eprintln!("Downloading {} ({})", asset.name, pulsar_hub::format_bytes(asset.size))
+            }
+            None => eprintln!("Downloading {} modules", release.tag_name),
+        }
+    }
+    let progress = Arc::new(Mutex::new(svc::DownloadProgress::default()));
+    let worker = {
+        let release = release.clone();
+        let dest = dest.clone();
+        let modules = modules.to_vec();
+        let progress = progress.clone();
+        std::thread::spawn(move || svc::install_release_with_progress(&release, &dest, &modules, progress))
+    };
+
+    let mut last_percent = None;
+    loop {
+        std::thread::sleep(std::time::Duration::from_millis(250));
+        let p = progress.lock().clone();
+        if p.done {
+            break;
+        }
+        if !json && p.total_bytes > 0 {
+            let percent = p.bytes_downloaded * 100 / p.total_bytes;
+            if last_percent != Some(percent / 10) {
+                eprintln!("  {:>3}%", percent);
+                last_percent = Some(percent / 10);
+            }
+        }
+    }
+    let _ = worker.join();
+
+    if let Some(error) = progress.lock().error.clone() {
+        anyhow::bail!("installing {} failed: {}", release.tag_name, error);
+    }
+    let installed = svc::scan_installed_versions();
+    Ok(installed
+        .iter()
+        .find(|v| same_version(&v.metadata.version, &release.tag_name))
+        .map(engine_entry)
+        .unwrap_or_else(|| EngineEntry {
+            version: release.tag_name.clone(),
+            path: dest,
+            install_date: String::new(),
+            size_bytes: 0,
+            shared_bytes: 0,
+        }))
+}
+
+fn launch(engine: &svc::InstalledVersion, project: Option<&Path>, json: bool) -> Result<()> {
+    let dir = &engine.metadata.install_path;
+    match project {
+        Some(project) => svc::launch_engine_for_project(dir, project),
+        None => svc::launch_engine(dir),
+    }
+    .map_err(anyhow::Error::msg)?;
+
+    if json {
+        print_json(&serde_json::json!({
+            "engine": engine_entry(engine),
+            "project": project,
+        }));
+    } else {
+        println!("Launched engine {}", engine.metadata.version);
+    }
+    Ok(())
+}
+
+fn run_projects(cmd: ProjectsCommand, json: bool) -> Result<()> {
+    match cmd {
+        ProjectsCommand::List => {
+            let recent = RecentProjectsList::load(&pulsar_hub::recent_projects_path());
+            let projects: Vec<ProjectEntry> = recent
+                .projects
+                .into_iter()
+                .map(|p| ProjectEntry {
+                    engine_version: ProjectService::project_engine_version(Path::new(&p.path)),
+                    name: p.name,
+                    path: p.path,
+                    last_opened: p.last_opened,
+                })
+                .collect();
+            if json {
+                print_json(&projects);
+            } else if projects.is_empty() {
+                println!("No recent projects.");
+            } else {
+                for p in &projects {
+                    println!(
+                        "{:<24} {:<12} {}",
+                        p.name,
+                        p.engine_version.as_deref().unwrap_or("-"),
+                        p.path
+                    );
+                }
+            }
+        }
+        ProjectsCommand::Open { path } => {
+            anyhow::ensure!(
+                ProjectService::validate_project(&path),
+                "{} is not a Pulsar project (no Pulsar.toml)",
+                path.display()
+            );
+            let installed = svc::scan_installed_versions();
+            let engine = match ProjectService::resolve_project_engine(&path, &installed) {
+                EngineResolution::Installed(engine) => engine,
+                EngineResolution::Missing(required) => anyhow::bail!(
+                    "project requires engine {}, which isn't installed; \
+                     run `pulsar-installer engines install <tag>` first",
+                    required
+                ),
+                EngineResolution::Mismatch(engine) => anyhow::bail!(
+                    "engine {} is installed from a different build than the project's lock \
+                     records; reinstall it with `pulsar-installer engines install {}`",
+                    engine.metadata.version,
+                    engine.metadata.version
+                ),
+                EngineResolution::Src => {
+                    anyhow::bail!("project uses the 'src' engine; open it from the hub")
+                }
+                EngineResolution::NoEngine => anyhow::bail!("no engine versions installed"),
+            };
+            launch(&engine, Some(&path), json)?;
+        }
+    }
+    Ok(())
+}
+
+fn run_update(from: Option<&Path>, json: bool) -> Result<()> {
+    let installed_version = match from {
+        Some(path) => {
+            let bundle = updater::offline::UpdateBundle::open(path)?;
+            let result = apply_update(updater::check_local_update(&bundle));
+            bundle.cleanup();
+            result?
+        }
+        None => {
+            let rt = tokio::runtime::Runtime::new()?;
+            apply_update(rt.block_on(updater::check_for_update()))?
+        }
+    };
+
+    let outcome = UpdateOutcome {
+        current_version: updater::current_version().to_string(),
+        installed_version,
+    };
+    if json {
+        print_json(&outcome);
+    } else {
+        match &outcome.installed_version {
+            Some(version) => println!("Updated to v{}", version),
+            None => println!("Already up to date (v{})", outcome.current_version),
+        }
+    }
+    Ok(())
+}
+
+fn apply_update(update: Result<Option<updater::PendingUpdate>>) -> Result<Option<String>> {
+    let Some(update) = update? else {
+        return Ok(None);
+    };
+    let rt = tokio::runtime::Runtime::new()?;
+    rt.block_on(updater::install_update(&update, None))?;
+    Ok(Some(update.version().to_string()))
+}
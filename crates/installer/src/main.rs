@@ -1,3 +1,6 @@
+mod cli;
+
+use clap::{error::ErrorKind, CommandFactory, Parser};
 use gpui::{prelude::*, *};
 use pulsar_hub::EntryWindow;
 use ui::Assets;
@@ -10,31 +13,50 @@ fn main() {
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
         )
+        // Keep stdout clean for `--json` output.
+        .with_writer(std::io::stderr)
         .init();
 
-    let args: Vec<String> = std::env::args().collect();
-    let skip_update_check = args.iter().any(|a| a == "--updated");
+    let cli = match cli::Cli::try_parse() {
+        Ok(cli) => cli,
+        // Launchers and file associations can pass arguments we don't know;
+        // open the hub rather than refusing to start. A mistyped headless
+        // command still fails, so scripts see a non-zero exit.
+        Err(e)
+            if !names_a_command()
+                && !matches!(
+                    e.kind(),
+                    ErrorKind::DisplayHelp
+                        | ErrorKind::DisplayVersion
+                        | ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+                ) =>
+        {
+            tracing::warn!("Ignoring unrecognised arguments: {}", e.kind());
+            cli::Cli::default()
+        }
+        Err(e) => e.exit(),
+    };
 
-    if !skip_update_check {
-        let rt = tokio::runtime::Runtime::new().expect("failed to create tokio runtime");
-        let should_relaunch = rt.block_on(async {
-            match pulsar_installer::updater::check_and_update().await {
-                Ok(relaunch) => relaunch,
-                Err(e) => {
-                    tracing::warn!("Update check failed: {}", e);
-                    false
-                }
-            }
-        });
+    // Headless runs don't take part in the post-update crash check: only a
+    // hub window reaching `confirm_update` counts as a healthy start, so the
+    // check stays pending until the hub is next opened.
+    if let Some(bundle) = cli.update_from {
+        std::process::exit(cli::run(cli::Command::Update { from: Some(bundle) }, cli.json));
+    }
+    if let Some(command) = cli.command {
+        std::process::exit(cli::run(command, cli.json));
+    }
 
-        if should_relaunch {
-            if let Ok(exe) = std::env::current_exe() {
-                let _ = std::process::Command::new(exe)
-                    .arg("--updated")
-                    .spawn();
-            }
-            std::process::exit(0);
-        }
+    // Undo an update that crashed before confirming it started cleanly.
+    pulsar_installer::updater::recover_failed_update();
+
+    // Update checks run in the background once the hub is open; the hub asks
+    // before installing anything. `--updated` means we were just relaunched
+    // into a new build, so don't look for another one this session.
+    if !cli.updated {
+        pulsar_hub::set_hub_updater(std::sync::Arc::new(
+            pulsar_installer::updater::hub::HubSelfUpdater::new(),
+        ));
     }
 
     tracing::info!("Starting Pulsar Hub");
@@ -75,5 +97,17 @@ fn main() {
             cx.new(|cx| Root::new(entry_window.into(), window, cx))
         })
         .expect("Failed to open hub window");
+
+        // The window is up, so this build works; drop the pre-update backup.
+        pulsar_installer::updater::confirm_update();
     });
 }
+
+/// Whether the command line asks for a headless command rather than the hub.
+fn names_a_command() -> bool {
+    let command = cli::Cli::command();
+    std::env::args_os().skip(1).any(|arg| {
+        arg.to_string_lossy().starts_with("--update-from")
+            || command.find_subcommand(&arg).is_some()
+    })
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::updater::manifest::{PatchInfo, PlatformUpdateInfo};
 
 pub const MAX_PATCH_CHAIN_LENGTH: usize = 4;
@@ -24,64 +26,62 @@ pub struct UpdateChain {
     pub total_download_bytes: u64,
 }
 
+/// Plan the cheapest way from `from_version` to `to_version`: a chain of at
+/// most [`MAX_PATCH_CHAIN_LENGTH`] patches minimizing total download size, or a
+/// full download when no chain exists or the chain would cost more than the
+/// full binary.
 pub fn compute_update_chain(
     from_version: &str,
     to_version: &str,
     platform: &PlatformUpdateInfo,
 ) -> UpdateChain {
-    let mut steps = Vec::new();
-    let mut current_version = from_version.to_string();
+    if from_version == to_version {
+        return UpdateChain {
+            steps: Vec::new(),
+            has_full_download: false,
+            total_download_bytes: 0,
+        };
+    }
 
-    loop {
-        if current_version == to_version {
-            break;
-        }
+    let Some((total_download_bytes, path)) =
+        cheapest_patch_path(from_version, to_version, &platform.patches)
+    else {
+        tracing::info!(
+            "No patch chain from {} to {} within {} hops, falling back to full download",
+            from_version,
+            to_version,
+            MAX_PATCH_CHAIN_LENGTH
+        );
+        return full_download_chain(to_version, platform);
+    };
 
-        if steps.len() >= MAX_PATCH_CHAIN_LENGTH {
+    if total_download_bytes > platform.full_size_bytes {
+        tracing::info!(
+            "Patch chain {} -> {} costs {} bytes, more than the {} byte full binary; \
+             falling back to full download",
+            from_version,
+            to_version,
+            total_download_bytes,
+            platform.full_size_bytes
+        );
+        return full_download_chain(to_version, platform);
+    }
+
+    let steps = path
+        .into_iter()
+        .map(|patch| {
             tracing::info!(
-                "Patch chain exceeded {} hops ({}), falling back to full download",
-                MAX_PATCH_CHAIN_LENGTH,
-                steps.len()
+                "Found patch: {} -> {}",
+                patch.from_version,
+                patch.to_version
             );
-            return full_download_chain(to_version, platform);
-        }
-
-        match platform
-            .patches
-            .iter()
-            .find(|p| p.from_version == current_version && p.to_version == to_version)
-        {
-            Some(patch) => {
-                tracing::info!(
-                    "Found patch: {} -> {}",
-                    current_version,
-                    patch.to_version
-                );
-                steps.push(UpdateStep::Patch {
-                    from_version: current_version.clone(),
-                    to_version: patch.to_version.clone(),
-                    patch_info: patch.clone(),
-                });
-                current_version = patch.to_version.clone();
-            }
-            None => {
-                tracing::info!(
-                    "No patch from {} to {}, falling back to full download",
-                    current_version,
-                    to_version
-                );
-                return full_download_chain(to_version, platform);
+            UpdateStep::Patch {
+                from_version: patch.from_version.clone(),
+                to_version: patch.to_version.clone(),
+                patch_info: patch.clone(),
             }
-        }
-    }
-
-    let total_download_bytes = steps
-        .iter()
-        .map(|step| match step {
-            UpdateStep::Patch { patch_info, .. } => patch_info.size_bytes,
-            UpdateStep::FullDownload { size_bytes, .. } => *size_bytes,
         })
-        .sum();
+        .collect();
 
     UpdateChain {
         steps,
@@ -90,6 +90,44 @@ pub fn compute_update_chain(
     }
 }
 
+/// Hop-limited shortest path (Bellman-Ford over at most
+/// [`MAX_PATCH_CHAIN_LENGTH`] rounds) through the patch graph, weighted by
+/// patch size. Returns the total size and the patches in application order.
+fn cheapest_patch_path<'a>(
+    from_version: &str,
+    to_version: &str,
+    patches: &'a [PatchInfo],
+) -> Option<(u64, Vec<&'a PatchInfo>)> {
+    let mut best: HashMap<&str, (u64, Vec<&PatchInfo>)> = HashMap::new();
+    best.insert(from_version, (0, Vec::new()));
+
+    for _ in 0..MAX_PATCH_CHAIN_LENGTH {
+        let mut next = best.clone();
+        for patch in patches {
+            // Never route back through the starting version.
+            if patch.to_version == from_version {
+                continue;
+            }
+            let Some((cost, path)) = best.get(patch.from_version.as_str()) else {
+                continue;
+            };
+            let cost = cost + patch.size_bytes;
+            let improves = next
+                .get(patch.to_version.as_str())
+                .map(|(existing, _)| cost < *existing)
+                .unwrap_or(true);
+            if improves {
+                let mut path = path.clone();
+                path.push(patch);
+                next.insert(patch.to_version.as_str(), (cost, path));
+            }
+        }
+        best = next;
+    }
+
+    best.remove(to_version)
+}
+
 fn full_download_chain(to_version: &str, platform: &PlatformUpdateInfo) -> UpdateChain {
     UpdateChain {
         steps: vec![UpdateStep::FullDownload {
@@ -102,3 +140,146 @@ fn full_download_chain(to_version: &str, platform: &PlatformUpdateInfo) -> Updat
         total_download_bytes: platform.full_size_bytes,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patch(from: &str, to: &str, size_bytes: u64) -> PatchInfo {
+        PatchInfo {
+            from_version: from.into(),
+            to_version: to.into(),
+            asset_name: format!("patch-{}-{}.zst", from, to),
+            url: format!("https://example.invalid/patch-{}-{}.zst", from, to),
+            sha256: String::new(),
+            size_bytes,
+        }
+    }
+
+    fn platform(patches: Vec<PatchInfo>) -> PlatformUpdateInfo {
+        PlatformUpdateInfo {
+            full_asset_name: "pulsar-installer-linux-x86_64".into(),
+            full_url: "https://example.invalid/pulsar-installer-linux-x86_64".into(),
+            full_sha256: String::new(),
+            full_size_bytes: 50_000,
+            patches,
+        }
+    }
+
+    fn hops(chain: &UpdateChain) -> Vec<(String, String)> {
+        chain
+            .steps
+            .iter()
+            .map(|step| match step {
+                UpdateStep::Patch {
+                    from_version,
+                    to_version,
+                    ..
+                } => (from_version.clone(), to_version.clone()),
+                UpdateStep::FullDownload { version, .. } => ("full".into(), version.clone()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn already_up_to_date() {
+        let chain = compute_update_chain("0.1.7", "0.1.7", &platform(vec![]));
+        assert!(chain.steps.is_empty());
+        assert_eq!(chain.total_download_bytes, 0);
+    }
+
+    #[test]
+    fn direct_patch() {
+        let chain = compute_update_chain(
+            "0.1.6",
+            "0.1.7",
+            &platform(vec![patch("0.1.6", "0.1.7", 900)]),
+        );
+        assert!(!chain.has_full_download);
+        assert_eq!(hops(&chain), [("0.1.6".into(), "0.1.7".into())]);
+        assert_eq!(chain.total_download_bytes, 900);
+    }
+
+    #[test]
+    fn multi_hop_chain() {
+        let chain = compute_update_chain(
+            "0.1.5",
+            "0.1.7",
+            &platform(vec![
+                patch("0.1.5", "0.1.6", 1_000),
+                patch("0.1.6", "0.1.7", 2_000),
+            ]),
+        );
+        assert!(!chain.has_full_download);
+        assert_eq!(
+            hops(&chain),
+            [
+                ("0.1.5".into(), "0.1.6".into()),
+                ("0.1.6".into(), "0.1.7".into())
+            ]
+        );
+        assert_eq!(chain.total_download_bytes, 3_000);
+    }
+
+    #[test]
+    fn prefers_fewest_bytes_over_fewest_hops() {
+        let chain = compute_update_chain(
+            "0.1.5",
+            "0.1.7",
+            &platform(vec![
+                patch("0.1.5", "0.1.7", 10_000),
+                patch("0.1.5", "0.1.6", 1_000),
+                patch("0.1.6", "0.1.7", 2_000),
+            ]),
+        );
+        assert_eq!(chain.steps.len(), 2);
+        assert_eq!(chain.total_download_bytes, 3_000);
+    }
+
+    #[test]
+    fn falls_back_when_chain_costs_more_than_full_binary() {
+        let chain = compute_update_chain(
+            "0.1.5",
+            "0.1.7",
+            &platform(vec![
+                patch("0.1.5", "0.1.6", 30_000),
+                patch("0.1.6", "0.1.7", 30_000),
+            ]),
+        );
+        assert!(chain.has_full_download);
+        assert_eq!(hops(&chain), [("full".into(), "0.1.7".into())]);
+        assert_eq!(chain.total_download_bytes, 50_000);
+    }
+
+    #[test]
+    fn falls_back_when_no_path_exists() {
+        let chain = compute_update_chain(
+            "0.1.4",
+            "0.1.7",
+            &platform(vec![
+                patch("0.1.5", "0.1.6", 100),
+                patch("0.1.6", "0.1.7", 100),
+            ]),
+        );
+        assert!(chain.has_full_download);
+    }
+
+    #[test]
+    fn respects_max_chain_length() {
+        let versions: Vec<String> = (0..=MAX_PATCH_CHAIN_LENGTH + 1)
+            .map(|i| format!("0.1.{}", i))
+            .collect();
+        let patches: Vec<PatchInfo> = versions
+            .windows(2)
+            .map(|w| patch(&w[0], &w[1], 10))
+            .collect();
+        let latest = versions.last().unwrap();
+
+        let chain = compute_update_chain(&versions[0], latest, &platform(patches.clone()));
+        assert!(chain.has_full_download);
+
+        let chain = compute_update_chain(&versions[1], latest, &platform(patches));
+        assert!(!chain.has_full_download);
+        assert_eq!(chain.steps.len(), MAX_PATCH_CHAIN_LENGTH);
+    }
+}
@@ -0,0 +1,150 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+
+/// Environment overrides; each takes precedence over the config file.
+pub const CONFIG_PATH_ENV: &str = "PULSAR_UPDATER_CONFIG";
+pub const CHANNEL_ENV: &str = "PULSAR_UPDATE_CHANNEL";
+pub const PIN_ENV: &str = "PULSAR_UPDATE_PIN";
+pub const MANIFEST_URL_ENV: &str = "PULSAR_UPDATE_MANIFEST_URL";
+
+/// Which stream of hub releases the self-updater follows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    /// Full releases only.
+    #[default]
+    Stable,
+    /// Pre-releases (`x.y.z-beta.n`) as well as full releases.
+    Beta,
+}
+
+impl UpdateChannel {
+    fn parse(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "stable" => Ok(Self::Stable),
+            "beta" => Ok(Self::Beta),
+            other => anyhow::bail!("unknown update channel '{}' (expected stable or beta)", other),
+        }
+    }
+}
+
+/// Self-updater settings, read from `updater.json` in the Pulsar data
+/// directory (or the file named by `PULSAR_UPDATER_CONFIG`) and then
+/// overridden by `PULSAR_UPDATE_*` environment variables.
+///
+/// ```json
+/// { "channel": "beta", "pin": "~0.1", "manifest_url": "https://mirror.internal/update-manifest.json" }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdaterConfig {
+    #[serde(default)]
+    pub channel: UpdateChannel,
+    /// Only update to versions matching this semver requirement, e.g.
+    /// `"=0.1.7"` to hold a version or `"~0.1"` to stay on a minor line.
+    #[serde(default)]
+    pub pin: Option<String>,
+    /// Fetch the manifest from here instead of GitHub releases.
+    #[serde(default)]
+    pub manifest_url: Option<String>,
+}
+
+impl UpdaterConfig {
+    fn default_path() -> Option<PathBuf> {
+        directories::ProjectDirs::from("com", "Pulsar", "Pulsar_Engine")
+            .map(|d| d.data_dir().join("updater.json"))
+    }
+
+    /// Load the config file (if any) and apply environment overrides.
+    pub fn load() -> Result<Self> {
+        let path = std::env::var_os(CONFIG_PATH_ENV)
+            .map(PathBuf::from)
+            .or_else(Self::default_path);
+
+        let mut config = match path {
+            Some(path) if path.is_file() => {
+                let text = std::fs::read_to_string(&path)
+                    .with_context(|| format!("reading {}", path.display()))?;
+                serde_json::from_str(&text)
+                    .with_context(|| format!("parsing updater config {}", path.display()))?
+            }
+            _ => Self::default(),
+        };
+
+        if let Ok(channel) = std::env::var(CHANNEL_ENV) {
+            config.channel = UpdateChannel::parse(&channel)?;
+        }
+        if let Ok(pin) = std::env::var(PIN_ENV) {
+            config.pin = Some(pin).filter(|p| !p.trim().is_empty());
+        }
+        if let Ok(url) = std::env::var(MANIFEST_URL_ENV) {
+            config.manifest_url = Some(url).filter(|u| !u.trim().is_empty());
+        }
+
+        if let Some(pin) = &config.pin {
+            VersionReq::parse(pin).with_context(|| format!("invalid update pin '{}'", pin))?;
+        }
+        Ok(config)
+    }
+
+    /// Whether `latest` should be offered to a hub running `current`. Only
+    /// strictly newer versions qualify, so a manifest never causes a
+    /// downgrade; pre-releases need the beta channel and the pin must match.
+    pub fn should_update(&self, current: &str, latest: &str) -> Result<bool> {
+        let current = parse_version(current)
+            .with_context(|| format!("current version '{}' is not valid semver", current))?;
+        let latest = parse_version(latest)
+            .with_context(|| format!("manifest version '{}' is not valid semver", latest))?;
+
+        if latest <= current {
+            return Ok(false);
+        }
+        if !latest.pre.is_empty() && self.channel == UpdateChannel::Stable {
+            return Ok(false);
+        }
+        if let Some(pin) = &self.pin {
+            let req = VersionReq::parse(pin)
+                .with_context(|| format!("invalid update pin '{}'", pin))?;
+            if !req.matches(&latest) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+fn parse_version(s: &str) -> Option<Version> {
+    Version::parse(s.trim().trim_start_matches(['v', 'V'])).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_offers_newer_allowed_versions() {
+        let stable = UpdaterConfig::default();
+        assert!(stable.should_update("0.1.7", "0.1.8").unwrap());
+        assert!(stable.should_update("0.1.9", "0.1.10").unwrap());
+        assert!(!stable.should_update("0.1.8", "0.1.8").unwrap());
+        assert!(!stable.should_update("0.1.8", "0.1.7").unwrap());
+        assert!(!stable.should_update("0.1.7", "0.2.0-beta.1").unwrap());
+
+        let beta = UpdaterConfig {
+            channel: UpdateChannel::Beta,
+            ..Default::default()
+        };
+        assert!(beta.should_update("0.1.7", "0.2.0-beta.1").unwrap());
+        assert!(beta.should_update("0.2.0-beta.1", "0.2.0").unwrap());
+        assert!(!beta.should_update("0.2.0", "0.2.0-beta.2").unwrap());
+
+        let pinned = UpdaterConfig {
+            pin: Some("~0.1".into()),
+            ..Default::default()
+        };
+        assert!(pinned.should_update("0.1.7", "0.1.8").unwrap());
+        assert!(!pinned.should_update("0.1.7", "0.2.0").unwrap());
+    }
+}
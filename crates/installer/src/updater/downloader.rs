@@ -1,21 +1,103 @@
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
 
 use crate::updater::chain::{UpdateChain, UpdateStep};
 use crate::updater::manifest::PlatformUpdateInfo;
 
+/// How many times a download is attempted before giving up.
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 5;
+
+/// Delay before the first retry; doubled after every failed attempt.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Receives the number of bytes downloaded so far across a whole chain.
+pub type ProgressCallback = Arc<dyn Fn(u64) + Send + Sync>;
+
 pub struct UpdateDownloader {
     temp_dir: PathBuf,
+    /// Held locked for the downloader's lifetime; see [`Self::for_version`].
+    _lock: std::fs::File,
+    client: reqwest::Client,
+    progress: Option<ProgressCallback>,
+    /// Read assets from this directory instead of their URLs (offline bundles).
+    local_source: Option<PathBuf>,
+    /// Bytes belonging to chain steps that have already finished downloading.
+    completed_bytes: AtomicU64,
 }
 
 impl UpdateDownloader {
-    pub fn new() -> Self {
-        let temp_dir = std::env::temp_dir().join("pulsar-update");
-        let _ = std::fs::remove_dir_all(&temp_dir);
-        let _ = std::fs::create_dir_all(&temp_dir);
-        Self { temp_dir }
+    /// Downloader for an update to `version`. Its files go to
+    /// `pulsar-update/<version>`, and partially downloaded files there are
+    /// kept so an interrupted update resumes where it left off on the next
+    /// run. The directory is locked while in use, so a second process
+    /// updating to the same version at the same time fails instead of
+    /// writing the same files.
+    pub fn for_version(version: &str) -> Result<Self> {
+        let temp_dir = std::env::temp_dir()
+            .join("pulsar-update")
+            .join(version.trim_start_matches('v'));
+        std::fs::create_dir_all(&temp_dir)
+            .with_context(|| format!("creating {}", temp_dir.display()))?;
+        let lock_path = temp_dir.join(".lock");
+        let lock = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .with_context(|| format!("opening {}", lock_path.display()))?;
+        match lock.try_lock() {
+            Ok(()) => {}
+            Err(std::fs::TryLockError::WouldBlock) => {
+                anyhow::bail!("another update to v{} is already running", version)
+            }
+            Err(std::fs::TryLockError::Error(e)) => {
+                return Err(e).with_context(|| format!("locking {}", lock_path.display()));
+            }
+        }
+        let client = reqwest::Client::builder()
+            .user_agent("Pulsar-Installer/1.0")
+            .connect_timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Ok(Self {
+            temp_dir,
+            _lock: lock,
+            client,
+            progress: None,
+            local_source: None,
+            completed_bytes: AtomicU64::new(0),
+        })
+    }
+
+    /// Report download progress to `callback`; totals are comparable with
+    /// [`UpdateChain::total_download_bytes`].
+    pub fn with_progress(mut self, callback: ProgressCallback) -> Self {
+        self.progress = Some(callback);
+        self
+    }
+
+    /// Resolve every asset by file name inside `dir` rather than fetching its
+    /// URL, so an update can be applied without network access.
+    pub fn with_local_source(mut self, dir: PathBuf) -> Self {
+        self.local_source = Some(dir);
+        self
+    }
+
+    fn report_progress(&self, in_flight: u64) {
+        if let Some(progress) = &self.progress {
+            progress(self.completed_bytes.load(Ordering::Relaxed) + in_flight);
+        }
+    }
+
+    /// Remove downloaded and patched files once an update has been installed.
+    pub fn cleanup(&self) {
+        let _ = std::fs::remove_dir_all(&self.temp_dir);
     }
 
     pub async fn apply_chain(
@@ -23,7 +105,9 @@ impl UpdateDownloader {
         chain: &UpdateChain,
         _platform: &PlatformUpdateInfo,
     ) -> Result<PathBuf> {
-        let mut current_file: Option<PathBuf> = None;
+        // Patches apply on top of the running binary.
+        let mut current_file: Option<PathBuf> = std::env::current_exe().ok();
+        self.completed_bytes.store(0, Ordering::Relaxed);
 
         for step in &chain.steps {
             match step {
@@ -35,12 +119,15 @@ impl UpdateDownloader {
                     tracing::info!("Applying patch {} -> {}", from_version, to_version);
 
                     let patch_path = self.temp_dir.join(&patch_info.asset_name);
-                    self.download_file(&patch_info.url, &patch_path)
+                    let actual_hash = self
+                        .download_file(&patch_info.url, &patch_path, &patch_info.sha256)
                         .await
                         .context("downloading patch")?;
+                    self.completed_bytes
+                        .fetch_add(patch_info.size_bytes, Ordering::Relaxed);
 
-                    let actual_hash = compute_file_hash(&patch_path)?;
                     if actual_hash != patch_info.sha256 {
+                        let _ = std::fs::remove_file(&patch_path);
                         anyhow::bail!(
                             "Patch SHA256 mismatch for {} -> {}: expected {}, got {}",
                             from_version,
@@ -59,18 +146,10 @@ impl UpdateDownloader {
                         crate::updater::platform::platform_extension()
                     ));
 
-                    let compressed = std::fs::read(&patch_path)
-                        .context("reading patch file")?;
-                    let uncompressed = zstd::decode_all(compressed.as_slice())
-                        .context("zstd decompression failed")?;
-
-                    let old = std::fs::read(old_file).context("reading base binary")?;
-                    let mut new = Vec::with_capacity(old.len() + 1024);
-                    bsdiff::patch(&old, &mut uncompressed.as_slice(), &mut new)
-                        .context("bsdiff::patch failed")?;
-
-                    std::fs::write(&patched_file, &new)
-                        .context("writing patched binary")?;
+                    pulsar_patch_tool::apply_patch(old_file, &patch_path, &patched_file)
+                        .with_context(|| {
+                            format!("applying patch {} -> {}", from_version, to_version)
+                        })?;
 
                     current_file = Some(patched_file);
                 }
@@ -91,12 +170,14 @@ impl UpdateDownloader {
                         crate::updater::platform::platform_extension()
                     );
                     let dest = self.temp_dir.join(&binary_name);
-                    self.download_file(url, &dest)
+                    let actual_hash = self
+                        .download_file(url, &dest, sha256)
                         .await
                         .context("downloading full binary")?;
+                    self.completed_bytes.fetch_add(*size_bytes, Ordering::Relaxed);
 
-                    let actual_hash = compute_file_hash(&dest)?;
                     if actual_hash != *sha256 {
+                        let _ = std::fs::remove_file(&dest);
                         anyhow::bail!(
                             "Binary SHA256 mismatch: expected {}, got {}",
                             sha256,
@@ -112,28 +193,207 @@ impl UpdateDownloader {
         current_file.context("update chain produced no output file")
     }
 
-    async fn download_file(&self, url: &str, dest: &Path) -> Result<()> {
-        tracing::info!("Downloading {} -> {}", url, dest.display());
+    /// Download `url` to `dest`, returning the SHA256 of the result.
+    ///
+    /// The body is streamed into `<dest>.part` while being hashed. An existing
+    /// partial file is resumed with an HTTP `Range` request, and transient
+    /// failures are retried with exponential backoff. A finished `dest` whose
+    /// hash already matches `expected_sha256` is reused without downloading.
+    async fn download_file(&self, url: &str, dest: &Path, expected_sha256: &str) -> Result<String> {
+        if dest.exists() {
+            let existing = compute_file_hash(dest)?;
+            if existing == expected_sha256 {
+                tracing::info!("Reusing already downloaded {}", dest.display());
+                self.report_progress(std::fs::metadata(dest).map(|m| m.len()).unwrap_or(0));
+                return Ok(existing);
+            }
+            let _ = std::fs::remove_file(dest);
+        }
+
+        if let Some(dir) = &self.local_source {
+            return self.copy_local(dir, url, dest);
+        }
+
+        let part_path = partial_path(dest);
+        let mut delay = INITIAL_RETRY_DELAY;
+        let mut attempt = 1;
+        loop {
+            match self.download_attempt(url, &part_path).await {
+                Ok(hash) => {
+                    std::fs::rename(&part_path, dest).with_context(|| {
+                        format!("moving {} into place", part_path.display())
+                    })?;
+                    return Ok(hash);
+                }
+                Err(e) if attempt < MAX_DOWNLOAD_ATTEMPTS && is_transient(&e) => {
+                    tracing::warn!(
+                        "Download of {} failed (attempt {}/{}): {:#}; retrying in {:?}",
+                        url,
+                        attempt,
+                        MAX_DOWNLOAD_ATTEMPTS,
+                        e,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                    attempt += 1;
+                }
+                Err(e) if attempt > 1 => {
+                    return Err(e).with_context(|| format!("giving up after {} attempts", attempt));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Copy the asset `url` names out of the local bundle `dir` to `dest`,
+    /// hashing it on the way.
+    fn copy_local(&self, dir: &Path, url: &str, dest: &Path) -> Result<String> {
+        let name = url.rsplit(['/', '\\']).next().unwrap_or(url);
+        let source = dir.join(name);
+        tracing::info!("Copying {} -> {}", source.display(), dest.display());
+
+        let mut reader = std::fs::File::open(&source)
+            .with_context(|| format!("asset {} missing from update bundle", name))?;
+        let mut writer = HashingWriter {
+            file: std::fs::File::create(dest)
+                .with_context(|| format!("creating {}", dest.display()))?,
+            hasher: Sha256::new(),
+            written: 0,
+            downloader: self,
+        };
+        std::io::copy(&mut reader, &mut writer)
+            .with_context(|| format!("copying {}", source.display()))?;
+        Ok(hex::encode(writer.hasher.finalize()))
+    }
+
+    /// One attempt at fetching `url` into `part_path`, resuming from whatever
+    /// it already contains.
+    async fn download_attempt(&self, url: &str, part_path: &Path) -> Result<String> {
+        let resume_from = std::fs::metadata(part_path).map(|m| m.len()).unwrap_or(0);
+        tracing::info!(
+            "Downloading {} -> {} (resuming at byte {})",
+            url,
+            part_path.display(),
+            resume_from
+        );
 
-        let dest = dest.to_path_buf();
-        let url = url.to_string();
+        let mut request = self.client.get(url);
+        if resume_from > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={}-", resume_from));
+        }
+        let mut response = request.send().await.context("HTTP request failed")?;
+
+        if response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            // The partial file is as long as (or longer than) the resource;
+            // it can't be trusted, so start over on the next attempt.
+            let _ = std::fs::remove_file(part_path);
+            return Err(ResumeRejected.into());
+        }
+        let response_status = response.status();
+        response = response.error_for_status().context("HTTP error response")?;
 
-        let response = reqwest::get(&url)
-            .await
-            .context("HTTP request failed")?
-            .error_for_status()
-            .context("HTTP error response")?;
+        let resuming = resume_from > 0 && response_status == reqwest::StatusCode::PARTIAL_CONTENT;
+        let mut hasher = Sha256::new();
+        let mut written = if resuming { resume_from } else { 0 };
+        let mut file = if resuming {
+            hash_into(part_path, &mut hasher)?;
+            tokio::fs::OpenOptions::new()
+                .append(true)
+                .open(part_path)
+                .await
+                .context("opening partial download")?
+        } else {
+            tokio::fs::File::create(part_path)
+                .await
+                .context("creating download file")?
+        };
 
-        let bytes = response.bytes().await.context("reading response body")?;
-        std::fs::write(&dest, &bytes).context("writing downloaded file")?;
+        while let Some(chunk) = response.chunk().await.context("reading response body")? {
+            hasher.update(&chunk);
+            file.write_all(&chunk)
+                .await
+                .context("writing downloaded data")?;
+            written += chunk.len() as u64;
+            self.report_progress(written);
+        }
+        file.flush().await.context("flushing downloaded file")?;
 
-        Ok(())
+        Ok(hex::encode(hasher.finalize()))
     }
 }
 
+/// The server refused to resume a partial download, which was discarded so
+/// the next attempt starts over.
+#[derive(Debug)]
+struct ResumeRejected;
+
+impl std::fmt::Display for ResumeRejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("server rejected resume range; restarting download")
+    }
+}
+
+impl std::error::Error for ResumeRejected {}
+
+/// Whether a failed download attempt is worth repeating: the connection
+/// failed or dropped, or the server is overloaded (5xx, 429). Other HTTP
+/// errors such as 404 or 403 won't change by asking again.
+fn is_transient(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        if cause.is::<ResumeRejected>() {
+            return true;
+        }
+        let Some(e) = cause.downcast_ref::<reqwest::Error>() else {
+            return false;
+        };
+        match e.status() {
+            Some(status) => {
+                status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            None => e.is_connect() || e.is_timeout() || e.is_request() || e.is_body(),
+        }
+    })
+}
+
+/// Writes to a file while hashing and reporting progress, for local copies.
+struct HashingWriter<'a> {
+    file: std::fs::File,
+    hasher: Sha256,
+    written: u64,
+    downloader: &'a UpdateDownloader,
+}
+
+impl std::io::Write for HashingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.file.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.written += n as u64;
+        self.downloader.report_progress(self.written);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn partial_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+/// Feed the contents of `path` into `hasher` without loading it into memory.
+fn hash_into(path: &Path, hasher: &mut Sha256) -> Result<()> {
+    let mut file =
+        std::fs::File::open(path).with_context(|| format!("reading {}", path.display()))?;
+    std::io::copy(&mut file, hasher).with_context(|| format!("hashing {}", path.display()))?;
+    Ok(())
+}
+
 fn compute_file_hash(path: &Path) -> Result<String> {
-    let bytes = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
     let mut hasher = Sha256::new();
-    hasher.update(&bytes);
+    hash_into(path, &mut hasher)?;
     Ok(hex::encode(hasher.finalize()))
 }
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use pulsar_hub::{HubUpdateInfo, HubUpdateProgress, HubUpdater};
+
+use crate::updater::{PendingUpdate, check_for_update, current_version, install_update, relaunch};
+
+/// Exposes the self-updater to the hub UI so update checks run in the
+/// background and the user decides when to install.
+#[derive(Default)]
+pub struct HubSelfUpdater {
+    /// The update found by the last [`HubUpdater::check`], reused by `install`.
+    pending: Mutex<Option<Arc<PendingUpdate>>>,
+}
+
+impl HubSelfUpdater {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn pending_for(&self, update: &HubUpdateInfo) -> Option<Arc<PendingUpdate>> {
+        self.pending
+            .lock()
+            .clone()
+            .filter(|pending| pending.version() == update.latest_version)
+    }
+}
+
+impl HubUpdater for HubSelfUpdater {
+    fn check(&self) -> Result<Option<HubUpdateInfo>, String> {
+        let pending = block_on(check_for_update())?;
+        let info = pending.as_ref().map(|update| HubUpdateInfo {
+            current_version: current_version().to_string(),
+            latest_version: update.version().to_string(),
+            release_notes: update.manifest.release_notes.clone(),
+            download_bytes: update.chain.total_download_bytes,
+        });
+        *self.pending.lock() = pending.map(Arc::new);
+        Ok(info)
+    }
+
+    fn install(
+        &self,
+        update: &HubUpdateInfo,
+        progress: Arc<Mutex<HubUpdateProgress>>,
+    ) -> Result<(), String> {
+        let pending = match self.pending_for(update) {
+            Some(pending) => pending,
+            None => block_on(check_for_update())?
+                .map(Arc::new)
+                .ok_or_else(|| "Pulsar Hub is already up to date".to_string())?,
+        };
+
+        progress.lock().total_bytes = pending.chain.total_download_bytes;
+        let sink = progress.clone();
+        let result = block_on(install_update(
+            &pending,
+            Some(Arc::new(move |bytes| sink.lock().bytes_downloaded = bytes)),
+        ));
+
+        let mut p = progress.lock();
+        p.done = true;
+        if let Err(e) = &result {
+            p.error = Some(e.clone());
+        }
+        result
+    }
+
+    fn relaunch(&self) {
+        relaunch();
+    }
+}
+
+/// Run an updater future to completion on a private tokio runtime.
+fn block_on<T>(future: impl Future<Output = anyhow::Result<T>>) -> Result<T, String> {
+    tokio::runtime::Runtime::new()
+        .map_err(|e| e.to_string())?
+        .block_on(future)
+        .map_err(|e| format!("{:#}", e))
+}
@@ -8,6 +8,13 @@ pub struct UpdateManifest {
     pub latest_version: String,
     pub generated_at: String,
     pub platforms: HashMap<String, PlatformUpdateInfo>,
+    /// Markdown release notes shown in the hub's update banner.
+    #[serde(default)]
+    pub release_notes: String,
+    /// Hex-encoded ed25519 signature over the rest of the manifest, checked by
+    /// [`crate::updater::signature::verify_manifest`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +63,8 @@ mod tests {
                     }],
                 },
             )]),
+            release_notes: String::new(),
+            signature: None,
         };
 
         let json = serde_json::to_string_pretty(&manifest).unwrap();
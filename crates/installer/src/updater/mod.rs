@@ -1,8 +1,12 @@
 pub mod chain;
+pub mod config;
 pub mod downloader;
+pub mod hub;
 pub mod manifest;
+pub mod offline;
 pub mod platform;
 pub mod replacer;
+pub mod signature;
 
 use anyhow::{Context, Result};
 
@@ -11,23 +15,74 @@ const GITHUB_REPO: &str = "Pulsar-Installer";
 
 pub const MAX_PATCH_CHAIN_LENGTH: usize = 4;
 
+/// A new scratch directory under the system temp dir, unique to this
+/// process and call, for files that are never resumed (unpacked bundles).
+pub(crate) fn scratch_dir(prefix: &str) -> std::path::PathBuf {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    std::env::temp_dir().join(format!(
+        "{}-{}-{}",
+        prefix,
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
 pub fn current_version() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
 
-fn manifest_url() -> String {
-    format!(
-        "https://github.com/{}/{}/releases/latest/download/update-manifest.json",
-        GITHUB_OWNER, GITHUB_REPO
-    )
+/// Where the update manifest is fetched from: the configured mirror if set,
+/// otherwise the GitHub release feed for the configured channel.
+pub fn manifest_url(config: &config::UpdaterConfig) -> String {
+    if let Some(url) = &config.manifest_url {
+        return url.clone();
+    }
+    match config.channel {
+        config::UpdateChannel::Stable => format!(
+            "https://github.com/{}/{}/releases/latest/download/update-manifest.json",
+            GITHUB_OWNER, GITHUB_REPO
+        ),
+        // `latest` skips pre-releases, so the release workflow also publishes
+        // every manifest to a rolling `channel-beta` release.
+        config::UpdateChannel::Beta => format!(
+            "https://github.com/{}/{}/releases/download/channel-beta/update-manifest.json",
+            GITHUB_OWNER, GITHUB_REPO
+        ),
+    }
 }
 
-pub async fn check_and_update() -> Result<bool> {
+/// An update found by [`check_for_update`], ready to be installed.
+#[derive(Debug)]
+pub struct PendingUpdate {
+    pub manifest: manifest::UpdateManifest,
+    pub platform: manifest::PlatformUpdateInfo,
+    pub chain: chain::UpdateChain,
+    /// Directory to read assets from instead of downloading them, for
+    /// updates found by [`check_local_update`].
+    pub local_source: Option<std::path::PathBuf>,
+}
+
+impl PendingUpdate {
+    pub fn version(&self) -> &str {
+        &self.manifest.latest_version
+    }
+}
+
+/// Fetch and verify the update manifest and plan an update chain, without
+/// downloading anything. Returns `None` when already on the latest version.
+pub async fn check_for_update() -> Result<Option<PendingUpdate>> {
     let version = current_version();
     tracing::info!("Current version: v{}", version);
 
-    tracing::info!("Fetching update manifest from {}", manifest_url());
-    let manifest_text = reqwest::get(&manifest_url())
+    let config = config::UpdaterConfig::load().context("failed to load updater config")?;
+    let url = manifest_url(&config);
+    tracing::info!(
+        "Fetching {:?} update manifest from {}",
+        config.channel,
+        url
+    );
+    let manifest_text = reqwest::get(&url)
         .await
         .context("failed to fetch update manifest")?
         .error_for_status()
@@ -36,8 +91,50 @@ pub async fn check_and_update() -> Result<bool> {
         .await
         .context("failed to read update manifest body")?;
 
+    plan_update(&manifest_text, &config, signature::UPDATE_PUBLIC_KEY)
+}
+
+/// Like [`check_for_update`], but reads the manifest and assets from a local
+/// bundle (see [`offline::UpdateBundle`]) and never touches the network.
+/// The channel setting is ignored since the bundle was chosen explicitly,
+/// but downgrades are still refused and the pin still applies.
+pub fn check_local_update(bundle: &offline::UpdateBundle) -> Result<Option<PendingUpdate>> {
+    check_local_update_with_key(bundle, signature::UPDATE_PUBLIC_KEY)
+}
+
+/// [`check_local_update`] with the manifest checked against `public_key`.
+pub(crate) fn check_local_update_with_key(
+    bundle: &offline::UpdateBundle,
+    public_key: Option<&str>,
+) -> Result<Option<PendingUpdate>> {
+    tracing::info!("Current version: v{}", current_version());
+    tracing::info!("Reading update bundle from {}", bundle.dir.display());
+
+    let config = config::UpdaterConfig {
+        channel: config::UpdateChannel::Beta,
+        ..config::UpdaterConfig::load().context("failed to load updater config")?
+    };
+    let manifest_text = bundle.read_manifest()?;
+    let mut update = plan_update(&manifest_text, &config, public_key)?;
+    if let Some(update) = &mut update {
+        update.local_source = Some(bundle.dir.clone());
+    }
+    Ok(update)
+}
+
+/// Verify and parse a manifest and plan the chain from the running version.
+fn plan_update(
+    manifest_text: &str,
+    config: &config::UpdaterConfig,
+    public_key: Option<&str>,
+) -> Result<Option<PendingUpdate>> {
+    let version = current_version();
+
+    signature::verify_manifest(manifest_text, public_key)
+        .context("update manifest failed signature verification")?;
+
     let manifest: manifest::UpdateManifest =
-        serde_json::from_str(&manifest_text).context("failed to parse update manifest")?;
+        serde_json::from_str(manifest_text).context("failed to parse update manifest")?;
 
     if manifest.schema_version != 1 {
         tracing::warn!(
@@ -46,9 +143,15 @@ pub async fn check_and_update() -> Result<bool> {
         );
     }
 
-    if manifest.latest_version == version {
-        tracing::info!("Already on latest version (v{})", version);
-        return Ok(false);
+    if !config.should_update(version, &manifest.latest_version)? {
+        tracing::info!(
+            "No update offered: running v{}, manifest has v{} (channel {:?}, pin {:?})",
+            version,
+            manifest.latest_version,
+            config.channel,
+            config.pin
+        );
+        return Ok(None);
     }
 
     tracing::info!(
@@ -58,15 +161,19 @@ pub async fn check_and_update() -> Result<bool> {
     );
 
     let target_triple = platform::current_target_triple();
-    let platform_info = manifest.platforms.get(target_triple).with_context(|| {
-        format!(
-            "no update info for platform '{}' in manifest",
-            target_triple
-        )
-    })?;
+    let platform_info = manifest
+        .platforms
+        .get(target_triple)
+        .cloned()
+        .with_context(|| {
+            format!(
+                "no update info for platform '{}' in manifest",
+                target_triple
+            )
+        })?;
 
     let update_chain =
-        chain::compute_update_chain(version, &manifest.latest_version, platform_info);
+        chain::compute_update_chain(version, &manifest.latest_version, &platform_info);
 
     tracing::info!(
         "Update chain: {} steps, full_download={}, total_bytes={}",
@@ -75,17 +182,92 @@ pub async fn check_and_update() -> Result<bool> {
         update_chain.total_download_bytes
     );
 
-    let downloader = downloader::UpdateDownloader::new();
+    Ok(Some(PendingUpdate {
+        manifest,
+        platform: platform_info,
+        chain: update_chain,
+        local_source: None,
+    }))
+}
+
+/// Download and apply `update`, then swap it in for the running executable.
+/// The new binary takes effect the next time the hub is started, and the old
+/// one is kept until that start is confirmed with [`confirm_update`].
+pub async fn install_update(
+    update: &PendingUpdate,
+    progress: Option<downloader::ProgressCallback>,
+) -> Result<()> {
+    let mut downloader = downloader::UpdateDownloader::for_version(update.version())?;
+    if let Some(progress) = progress {
+        downloader = downloader.with_progress(progress);
+    }
+    if let Some(dir) = &update.local_source {
+        downloader = downloader.with_local_source(dir.clone());
+    }
     let new_binary_path = downloader
-        .apply_chain(&update_chain, platform_info)
+        .apply_chain(&update.chain, &update.platform)
         .await
         .context("failed to apply update chain")?;
 
     let replacer = replacer::SelfReplacer::new().context("failed to initialize self-replacer")?;
     replacer
-        .replace(&new_binary_path)
+        .replace(&new_binary_path, current_version(), update.version())
         .context("failed to replace binary")?;
+    // Only now; a failed update keeps its downloads for the next attempt.
+    downloader.cleanup();
+
+    tracing::info!("Update to v{} applied successfully", update.version());
+    Ok(())
+}
+
+/// Check for an update and install it if one is available. Returns whether
+/// the binary was replaced and should be relaunched.
+pub async fn check_and_update() -> Result<bool> {
+    match check_for_update().await? {
+        Some(update) => {
+            install_update(&update, None).await?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Call before opening the hub window. On the first start after an update this
+/// arms the crash check; if the previous start of an update never reached
+/// [`confirm_update`], the old binary is restored and started instead.
+pub fn recover_failed_update() {
+    let replacer = match replacer::SelfReplacer::new() {
+        Ok(replacer) => replacer,
+        Err(e) => {
+            tracing::warn!("Skipping update recovery: {:#}", e);
+            return;
+        }
+    };
+    match replacer.check_pending(current_version()) {
+        Ok(replacer::PendingUpdateState::RolledBack { failed_version }) => {
+            tracing::warn!("Rolled back failed update to v{}; restarting", failed_version);
+            relaunch();
+        }
+        Ok(replacer::PendingUpdateState::AwaitingConfirmation) => {
+            tracing::info!("First start of v{}; waiting for a healthy start", current_version());
+        }
+        Ok(replacer::PendingUpdateState::None) => {}
+        Err(e) => tracing::error!("Update recovery failed: {:#}", e),
+    }
+}
 
-    tracing::info!("Update to v{} applied successfully", manifest.latest_version);
-    Ok(true)
+/// Record that this build started up successfully, discarding the backup of
+/// the previous version kept by [`install_update`].
+pub fn confirm_update() {
+    if let Ok(replacer) = replacer::SelfReplacer::new() {
+        replacer.confirm();
+    }
+}
+
+/// Start a fresh copy of the (updated) executable and exit this process.
+pub fn relaunch() -> ! {
+    if let Ok(exe) = std::env::current_exe() {
+        let _ = std::process::Command::new(exe).arg("--updated").spawn();
+    }
+    std::process::exit(0);
 }
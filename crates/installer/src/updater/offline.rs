@@ -0,0 +1,222 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// File name of the manifest inside an update bundle.
+pub const BUNDLE_MANIFEST: &str = "update-manifest.json";
+
+/// A local update bundle: `update-manifest.json` plus the patch and binary
+/// assets it references, stored side by side under their asset names. This
+/// is the same layout `pulsar-patch-tool build-manifest` writes to its
+/// output directory.
+pub struct UpdateBundle {
+    /// Directory holding the manifest and assets.
+    pub dir: PathBuf,
+    /// Where an archive bundle was unpacked, removed by [`Self::cleanup`].
+    extracted: Option<PathBuf>,
+}
+
+impl UpdateBundle {
+    /// Open a bundle directory, or unpack a `.zip`, `.tar`, `.tar.gz` or
+    /// `.tgz` archive of one into a temporary directory.
+    pub fn open(path: &Path) -> Result<Self> {
+        if path.is_dir() {
+            return Ok(Self {
+                dir: bundle_root(path)?,
+                extracted: None,
+            });
+        }
+        anyhow::ensure!(path.is_file(), "update bundle {} not found", path.display());
+
+        let dest = crate::updater::scratch_dir("pulsar-update-bundle");
+        std::fs::create_dir_all(&dest)
+            .with_context(|| format!("creating {}", dest.display()))?;
+
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("opening {}", path.display()))?;
+        if name.ends_with(".zip") {
+            zip::ZipArchive::new(file)
+                .context("invalid zip archive")?
+                .extract(&dest)
+                .context("extracting zip archive")?;
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            tar::Archive::new(flate2::read::GzDecoder::new(file))
+                .unpack(&dest)
+                .context("extracting tar.gz archive")?;
+        } else if name.ends_with(".tar") {
+            tar::Archive::new(file)
+                .unpack(&dest)
+                .context("extracting tar archive")?;
+        } else {
+            anyhow::bail!(
+                "unsupported update bundle {}: expected a directory, .zip, .tar or .tar.gz",
+                path.display()
+            );
+        }
+
+        Ok(Self {
+            dir: bundle_root(&dest)?,
+            extracted: Some(dest),
+        })
+    }
+
+    pub fn read_manifest(&self) -> Result<String> {
+        let path = self.dir.join(BUNDLE_MANIFEST);
+        std::fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))
+    }
+
+    /// Remove the unpacked copy of an archive bundle.
+    pub fn cleanup(&self) {
+        if let Some(dir) = &self.extracted {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+}
+
+/// The directory containing the manifest: `dir` itself, or its only
+/// subdirectory when the bundle was archived with a top-level folder.
+fn bundle_root(dir: &Path) -> Result<PathBuf> {
+    if dir.join(BUNDLE_MANIFEST).is_file() {
+        return Ok(dir.to_path_buf());
+    }
+    let subdirs: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("reading {}", dir.display()))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    match subdirs.as_slice() {
+        [only] if only.join(BUNDLE_MANIFEST).is_file() => Ok(only.clone()),
+        _ => anyhow::bail!("no {} found in {}", BUNDLE_MANIFEST, dir.display()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::updater::chain::{UpdateChain, UpdateStep};
+    use crate::updater::downloader::UpdateDownloader;
+    use crate::updater::manifest::PlatformUpdateInfo;
+    use sha2::{Digest, Sha256};
+    use std::fs;
+
+    #[test]
+    fn applies_full_binary_from_archive_bundle() {
+        let dir = std::env::temp_dir().join("pulsar_offline_bundle_test");
+        let _ = fs::remove_dir_all(&dir);
+        let bundle_dir = dir.join("bundle");
+        fs::create_dir_all(&bundle_dir).unwrap();
+
+        let binary = b"new pulsar-installer build".to_vec();
+        let sha256 = hex::encode(Sha256::digest(&binary));
+        fs::write(bundle_dir.join(BUNDLE_MANIFEST), "{}").unwrap();
+        fs::write(bundle_dir.join("pulsar-installer-linux-x86_64"), &binary).unwrap();
+
+        let archive = dir.join("bundle.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            fs::File::create(&archive).unwrap(),
+            flate2::Compression::fast(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        builder.append_dir_all("bundle", &bundle_dir).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let bundle = UpdateBundle::open(&archive).unwrap();
+        assert_eq!(bundle.read_manifest().unwrap(), "{}");
+
+        let url = "https://example.invalid/v0.1.8/pulsar-installer-linux-x86_64".to_string();
+        let chain = UpdateChain {
+            steps: vec![UpdateStep::FullDownload {
+                version: "0.1.8".into(),
+                url: url.clone(),
+                sha256: sha256.clone(),
+                size_bytes: binary.len() as u64,
+            }],
+            total_download_bytes: binary.len() as u64,
+            has_full_download: true,
+        };
+        let platform = PlatformUpdateInfo {
+            full_asset_name: "pulsar-installer-linux-x86_64".into(),
+            full_url: url,
+            full_sha256: sha256,
+            full_size_bytes: binary.len() as u64,
+            patches: Vec::new(),
+        };
+
+        let downloader = UpdateDownloader::for_version("0.1.8-offline-test")
+            .unwrap()
+            .with_local_source(bundle.dir.clone());
+        let output = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(downloader.apply_chain(&chain, &platform))
+            .unwrap();
+        assert_eq!(fs::read(&output).unwrap(), binary);
+
+        downloader.cleanup();
+        bundle.cleanup();
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn checks_and_applies_a_signed_bundle() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let dir = crate::updater::scratch_dir("pulsar_offline_check_test");
+        fs::create_dir_all(&dir).unwrap();
+
+        let binary = b"pulsar-installer 99.0.0".to_vec();
+        let asset = "pulsar-installer-99.0.0".to_string();
+        fs::write(dir.join(&asset), &binary).unwrap();
+        let triple = crate::updater::platform::current_target_triple();
+        let mut manifest = serde_json::json!({
+            "schema_version": 1,
+            "latest_version": "99.0.0",
+            "generated_at": "",
+            "platforms": {
+                (triple): {
+                    "full_asset_name": asset,
+                    "full_url": format!("https://example.invalid/v99.0.0/{}", asset),
+                    "full_sha256": hex::encode(Sha256::digest(&binary)),
+                    "full_size_bytes": binary.len(),
+                    "patches": []
+                }
+            }
+        });
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let payload = pulsar_patch_tool::signing::signing_payload(&manifest).unwrap();
+        manifest["signature"] = hex::encode(key.sign(&payload).to_bytes()).into();
+        fs::write(dir.join(BUNDLE_MANIFEST), manifest.to_string()).unwrap();
+
+        let bundle = UpdateBundle::open(&dir).unwrap();
+        let public_key = hex::encode(key.verifying_key().to_bytes());
+        let update = crate::updater::check_local_update_with_key(&bundle, Some(&public_key))
+            .unwrap()
+            .expect("bundle offers a newer version");
+        assert_eq!(update.version(), "99.0.0");
+        assert_eq!(update.local_source.as_deref(), Some(dir.as_path()));
+
+        let downloader = UpdateDownloader::for_version("99.0.0-offline-test")
+            .unwrap()
+            .with_local_source(dir.clone());
+
+        // Only one downloader may use a version's directory at a time.
+        assert!(UpdateDownloader::for_version("99.0.0-offline-test").is_err());
+        let output = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(downloader.apply_chain(&update.chain, &update.platform))
+            .unwrap();
+        assert_eq!(fs::read(&output).unwrap(), binary);
+
+        // A bundle signed with another key is refused.
+        let other_key = SigningKey::from_bytes(&[8u8; 32]);
+        let other = hex::encode(other_key.verifying_key().to_bytes());
+        assert!(crate::updater::check_local_update_with_key(&bundle, Some(&other)).is_err());
+
+        downloader.cleanup();
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,6 +1,33 @@
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Written next to the executable when an update is swapped in, and removed
+/// once the new build has started successfully. While it exists the previous
+/// binary is kept as `<exe>.bak` so a build that never comes up can be undone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingMarker {
+    from_version: String,
+    to_version: String,
+    /// Set on the new build's first start; finding it still set on a later
+    /// start means that run never confirmed it was healthy.
+    #[serde(default)]
+    started: bool,
+}
+
+/// What [`SelfReplacer::check_pending`] found on startup.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PendingUpdateState {
+    /// No update is awaiting confirmation.
+    None,
+    /// This is the first start of a freshly installed update; call
+    /// [`SelfReplacer::confirm`] once it is up and running.
+    AwaitingConfirmation,
+    /// The last update never confirmed a healthy start and the previous
+    /// binary has been put back. The process should restart into it.
+    RolledBack { failed_version: String },
+}
 
 pub struct SelfReplacer {
     current_exe: PathBuf,
@@ -10,48 +37,254 @@ impl SelfReplacer {
     pub fn new() -> Result<Self> {
         let current_exe =
             std::env::current_exe().context("failed to get current executable path")?;
-        Ok(Self { current_exe })
+        Ok(Self::at(current_exe))
+    }
+
+    fn at(current_exe: PathBuf) -> Self {
+        Self { current_exe }
+    }
+
+    fn backup_path(&self) -> PathBuf {
+        self.current_exe.with_extension("bak")
     }
 
-    pub fn replace(&self, new_binary: &Path) -> Result<()> {
-        let backup_path = self.current_exe.with_extension("bak");
+    fn staged_path(&self) -> PathBuf {
+        self.current_exe.with_extension("new")
+    }
+
+    /// Where a rolled-back binary is parked; Windows can't delete a running
+    /// executable, so it is removed on a later start instead.
+    fn failed_path(&self) -> PathBuf {
+        self.current_exe.with_extension("failed")
+    }
+
+    fn marker_path(&self) -> PathBuf {
+        self.current_exe.with_extension("update-pending")
+    }
 
+    /// Swap `new_binary` in for the running executable, keeping the current
+    /// one as a backup until the new version confirms a healthy start.
+    pub fn replace(&self, new_binary: &Path, from_version: &str, to_version: &str) -> Result<()> {
         tracing::info!(
             "Self-replace: {} <- {}",
             self.current_exe.display(),
             new_binary.display()
         );
 
-        self.do_replace(new_binary, &backup_path)
-            .context("self-replace failed")?;
+        // Stage the new binary beside the executable so the final swap is a
+        // rename within one filesystem.
+        let staged = self.staged_path();
+        std::fs::copy(new_binary, &staged).context("failed to stage new binary")?;
+        preserve_permissions(&self.current_exe, &staged)?;
+        std::fs::File::open(&staged)
+            .and_then(|f| f.sync_all())
+            .context("failed to flush staged binary")?;
+
+        self.write_marker(&PendingMarker {
+            from_version: from_version.to_string(),
+            to_version: to_version.to_string(),
+            started: false,
+        })?;
 
-        if backup_path.exists() {
-            let _ = std::fs::remove_file(&backup_path);
+        if let Err(e) = self.swap_in(&staged) {
+            let _ = std::fs::remove_file(&staged);
+            let _ = std::fs::remove_file(self.marker_path());
+            return Err(e).context("self-replace failed");
         }
 
+        tracing::info!("New binary installed; previous kept at {}", self.backup_path().display());
         Ok(())
     }
 
-    fn do_replace(&self, new_binary: &Path, backup_path: &Path) -> Result<()> {
-        if backup_path.exists() {
-            let _ = std::fs::remove_file(backup_path);
-        }
-
-        std::fs::rename(&self.current_exe, backup_path)
+    #[cfg(unix)]
+    fn swap_in(&self, staged: &Path) -> Result<()> {
+        // Copy rather than move the backup so the executable path is never
+        // missing; the rename over it is atomic.
+        std::fs::copy(&self.current_exe, self.backup_path())
             .context("failed to backup current binary")?;
+        std::fs::rename(staged, &self.current_exe).context("failed to install new binary")
+    }
 
-        match std::fs::copy(new_binary, &self.current_exe) {
-            Ok(_) => {
-                tracing::info!("New binary installed successfully");
-                Ok(())
+    #[cfg(not(unix))]
+    fn swap_in(&self, staged: &Path) -> Result<()> {
+        // A running executable can be renamed but not overwritten on Windows.
+        let backup = self.backup_path();
+        if backup.exists() {
+            let _ = std::fs::remove_file(&backup);
+        }
+        std::fs::rename(&self.current_exe, &backup).context("failed to backup current binary")?;
+        if let Err(e) = std::fs::rename(staged, &self.current_exe) {
+            tracing::error!("Failed to install new binary: {}", e);
+            if std::fs::rename(&backup, &self.current_exe).is_ok() {
+                tracing::info!("Rolled back to backup binary");
             }
-            Err(e) => {
-                tracing::error!("Failed to install new binary: {}", e);
-                if std::fs::copy(backup_path, &self.current_exe).is_ok() {
-                    tracing::info!("Rolled back to backup binary");
-                }
-                Err(e).context("failed to install new binary (rolled back)")
+            return Err(e).context("failed to install new binary (rolled back)");
+        }
+        Ok(())
+    }
+
+    /// Inspect the pending-update marker on startup and roll back an update
+    /// whose previous start never confirmed it was healthy.
+    pub fn check_pending(&self, running_version: &str) -> Result<PendingUpdateState> {
+        let _ = std::fs::remove_file(self.failed_path());
+
+        let Some(mut marker) = self.read_marker() else {
+            return Ok(PendingUpdateState::None);
+        };
+
+        if marker.to_version != running_version {
+            // The swap never took effect (or was undone by hand); nothing to
+            // confirm or restore.
+            tracing::warn!(
+                "Discarding stale update marker for v{} (running v{})",
+                marker.to_version,
+                running_version
+            );
+            self.clear();
+            return Ok(PendingUpdateState::None);
+        }
+
+        if !marker.started {
+            marker.started = true;
+            self.write_marker(&marker)?;
+            return Ok(PendingUpdateState::AwaitingConfirmation);
+        }
+
+        tracing::error!(
+            "v{} never confirmed a healthy start; restoring v{}",
+            marker.to_version,
+            marker.from_version
+        );
+        self.restore_backup().context("failed to restore previous binary")?;
+        let _ = std::fs::remove_file(self.marker_path());
+        Ok(PendingUpdateState::RolledBack {
+            failed_version: marker.to_version,
+        })
+    }
+
+    fn restore_backup(&self) -> Result<()> {
+        let backup = self.backup_path();
+        anyhow::ensure!(backup.is_file(), "no backup at {}", backup.display());
+        let failed = self.failed_path();
+        let _ = std::fs::remove_file(&failed);
+        std::fs::rename(&self.current_exe, &failed).context("failed to move aside failed binary")?;
+        if let Err(e) = std::fs::rename(&backup, &self.current_exe) {
+            let _ = std::fs::rename(&failed, &self.current_exe);
+            return Err(e).context("failed to move backup into place");
+        }
+        let _ = std::fs::remove_file(&failed);
+        Ok(())
+    }
+
+    /// Mark the running update as good: drop the marker and the backup.
+    pub fn confirm(&self) {
+        if self.read_marker().is_some() {
+            tracing::info!("Update confirmed healthy; removing backup");
+        }
+        self.clear();
+    }
+
+    fn clear(&self) {
+        let _ = std::fs::remove_file(self.marker_path());
+        let _ = std::fs::remove_file(self.backup_path());
+        let _ = std::fs::remove_file(self.staged_path());
+    }
+
+    fn read_marker(&self) -> Option<PendingMarker> {
+        let text = std::fs::read_to_string(self.marker_path()).ok()?;
+        serde_json::from_str(&text).ok()
+    }
+
+    fn write_marker(&self, marker: &PendingMarker) -> Result<()> {
+        let json = serde_json::to_string_pretty(marker)?;
+        std::fs::write(self.marker_path(), json).context("failed to write update marker")
+    }
+}
+
+/// Give `staged` the same permission bits as the binary it replaces so the
+/// executable bit (and any tightened mode) survives the update.
+#[cfg(unix)]
+fn preserve_permissions(current: &Path, staged: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = std::fs::metadata(current)
+        .context("failed to read current binary permissions")?
+        .permissions()
+        .mode();
+    std::fs::set_permissions(staged, std::fs::Permissions::from_mode(mode | 0o100))
+        .context("failed to set permissions on new binary")
+}
+
+#[cfg(not(unix))]
+fn preserve_permissions(_current: &Path, _staged: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn setup(name: &str) -> (PathBuf, SelfReplacer, PathBuf) {
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let exe = dir.join("pulsar-installer");
+        fs::write(&exe, b"old").unwrap();
+        let new = dir.join("downloaded");
+        fs::write(&new, b"new").unwrap();
+        (dir, SelfReplacer::at(exe), new)
+    }
+
+    #[test]
+    fn rolls_back_when_update_never_confirms() {
+        let (dir, replacer, new) = setup("pulsar_replacer_rollback_test");
+        replacer.replace(&new, "0.1.7", "0.1.8").unwrap();
+        assert_eq!(fs::read(&replacer.current_exe).unwrap(), b"new");
+
+        // First start of the new build, which then crashes before confirming.
+        assert_eq!(
+            replacer.check_pending("0.1.8").unwrap(),
+            PendingUpdateState::AwaitingConfirmation
+        );
+        assert_eq!(
+            replacer.check_pending("0.1.8").unwrap(),
+            PendingUpdateState::RolledBack {
+                failed_version: "0.1.8".into()
             }
+        );
+        assert_eq!(fs::read(&replacer.current_exe).unwrap(), b"old");
+        assert_eq!(replacer.check_pending("0.1.7").unwrap(), PendingUpdateState::None);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn confirm_keeps_update_and_drops_backup() {
+        let (dir, replacer, new) = setup("pulsar_replacer_confirm_test");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&replacer.current_exe, fs::Permissions::from_mode(0o750)).unwrap();
+        }
+
+        replacer.replace(&new, "0.1.7", "0.1.8").unwrap();
+        assert_eq!(
+            replacer.check_pending("0.1.8").unwrap(),
+            PendingUpdateState::AwaitingConfirmation
+        );
+        replacer.confirm();
+
+        assert!(!replacer.backup_path().exists());
+        assert_eq!(replacer.check_pending("0.1.8").unwrap(), PendingUpdateState::None);
+        assert_eq!(fs::read(&replacer.current_exe).unwrap(), b"new");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&replacer.current_exe).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o750);
         }
+
+        let _ = fs::remove_dir_all(&dir);
     }
 }
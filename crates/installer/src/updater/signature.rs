@@ -0,0 +1,52 @@
+use anyhow::{Context, Result};
+
+/// Hex-encoded ed25519 key that update manifests must be signed with. Baked in
+/// at build time from `PULSAR_UPDATE_PUBLIC_KEY`; builds without it refuse to
+/// self-update.
+pub const UPDATE_PUBLIC_KEY: Option<&str> = option_env!("PULSAR_UPDATE_PUBLIC_KEY");
+
+/// Verify the detached signature of a raw `update-manifest.json` against
+/// `public_key`, normally the compiled-in [`UPDATE_PUBLIC_KEY`]. Without a
+/// key every manifest is refused.
+///
+/// Checked by `pulsar_patch_tool::signing`, the same code that signs
+/// manifests, so both sides agree on the signed payload.
+pub fn verify_manifest(manifest_text: &str, public_key: Option<&str>) -> Result<()> {
+    let public_key =
+        public_key.context("this build has no update signing key; self-update is disabled")?;
+    pulsar_patch_tool::signing::verify_manifest(manifest_text, public_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use pulsar_patch_tool::signing::{signing_payload, SIGNATURE_FIELD};
+
+    fn sign(unsigned: &str, key: &SigningKey) -> String {
+        let mut manifest: serde_json::Value = serde_json::from_str(unsigned).unwrap();
+        let payload = signing_payload(&manifest).unwrap();
+        let signature = hex::encode(key.sign(&payload).to_bytes());
+        manifest[SIGNATURE_FIELD] = signature.into();
+        serde_json::to_string_pretty(&manifest).unwrap()
+    }
+
+    #[test]
+    fn rejects_tampered_or_unsigned_manifests() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let public_hex = hex::encode(key.verifying_key().to_bytes());
+        let unsigned = r#"{"schema_version": 1, "latest_version": "0.1.8", "generated_at": "", "platforms": {}}"#;
+
+        let signed = sign(unsigned, &key);
+        verify_manifest(&signed, Some(&public_hex)).unwrap();
+        assert!(verify_manifest(&signed, None).is_err());
+
+        let tampered = signed.replace("0.1.8", "0.1.9");
+        assert!(verify_manifest(&tampered, Some(&public_hex)).is_err());
+        assert!(verify_manifest(unsigned, Some(&public_hex)).is_err());
+
+        let other = SigningKey::from_bytes(&[9u8; 32]);
+        let other_hex = hex::encode(other.verifying_key().to_bytes());
+        assert!(verify_manifest(&signed, Some(&other_hex)).is_err());
+    }
+}
@@ -0,0 +1,308 @@
+//! Per-file deltas between two engine install directories.
+//!
+//! A delta is published as an uncompressed tar next to the full engine
+//! archive, named by [`delta_asset_name`]. It holds [`DELTA_MANIFEST`], a
+//! patch (see [`crate::format`]) for every changed file, and the contents of
+//! files that are new or didn't patch smaller. The hub clones the old
+//! install, applies the delta on top and checks every file against the hash
+//! recorded for it.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::{PatchFormat, apply_patch, generate_patch_as, sha256_file};
+
+/// Name of the manifest inside a delta tar.
+pub const DELTA_MANIFEST: &str = "engine-delta.json";
+
+/// Whether the install-relative, `/`-separated path `rel` is bookkeeping the
+/// hub keeps in an install (its metadata, file hashes, module files) rather
+/// than part of the engine. Never part of a delta, nor hashed for repairs.
+pub fn is_hub_file(rel: &str) -> bool {
+    rel.starts_with(".pulsar-")
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineDelta {
+    pub from_version: String,
+    pub to_version: String,
+    pub files: Vec<DeltaFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaFile {
+    /// Path relative to the install root, `/`-separated.
+    pub path: String,
+    /// SHA256 of the file after the delta is applied; empty for removals.
+    #[serde(default)]
+    pub sha256: String,
+    #[serde(flatten)]
+    pub op: DeltaOp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum DeltaOp {
+    /// Unchanged from the old install.
+    Keep,
+    /// Patch the old file, which must hash to `base_sha256`, with the tar
+    /// entry `patch`.
+    Patch { base_sha256: String, patch: String },
+    /// Take the file verbatim from the tar entry `data`.
+    Add { data: String },
+    /// Present in the old install only.
+    Remove,
+}
+
+/// Release asset name of the delta from `from_version` to the release whose
+/// full archive for this platform is `full_asset_name`.
+pub fn delta_asset_name(full_asset_name: &str, from_version: &str) -> String {
+    format!(
+        "{}.from-{}.delta.tar",
+        full_asset_name,
+        from_version.trim_start_matches(['v', 'V'])
+    )
+}
+
+/// Every file under `root` keyed by its `/`-separated relative path.
+fn list_files(root: &Path) -> Result<BTreeMap<String, PathBuf>> {
+    let mut files = BTreeMap::new();
+    for entry in WalkDir::new(root) {
+        let entry = entry.with_context(|| format!("reading {}", root.display()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = entry.path().strip_prefix(root)?;
+        let key = rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if is_hub_file(&key) {
+            continue;
+        }
+        files.insert(key, entry.path().to_path_buf());
+    }
+    Ok(files)
+}
+
+/// Resolve a delta path below `root`, refusing anything that could escape it.
+fn safe_join(root: &Path, rel: &str) -> Result<PathBuf> {
+    let rel = Path::new(rel);
+    anyhow::ensure!(
+        rel.components().all(|c| matches!(c, Component::Normal(_))),
+        "unsafe path in engine delta: {}",
+        rel.display()
+    );
+    Ok(root.join(rel))
+}
+
+/// Diff two install directories and write the delta tar to `output`.
+pub fn generate_engine_delta(
+    old_dir: &Path,
+    new_dir: &Path,
+    from_version: &str,
+    to_version: &str,
+    output: &Path,
+) -> Result<EngineDelta> {
+    let old_files = list_files(old_dir)?;
+    let new_files = list_files(new_dir)?;
+
+    let work_dir = std::env::temp_dir().join(format!("pulsar-engine-delta-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&work_dir);
+    std::fs::create_dir_all(&work_dir)
+        .with_context(|| format!("creating {}", work_dir.display()))?;
+
+    let mut tar = tar::Builder::new(
+        std::fs::File::create(output).with_context(|| format!("writing {}", output.display()))?,
+    );
+    let mut files = Vec::new();
+    for (index, (path, new_file)) in new_files.iter().enumerate() {
+        let sha256 = sha256_file(new_file)?;
+        let op = match old_files.get(path) {
+            Some(old_file) => {
+                let base_sha256 = sha256_file(old_file)?;
+                if base_sha256 == sha256 {
+                    DeltaOp::Keep
+                } else {
+                    let patch_path = work_dir.join(format!("{}.patch", index));
+                    generate_patch_as(old_file, new_file, &patch_path, PatchFormat::Windowed)?;
+                    if std::fs::metadata(&patch_path)?.len() < std::fs::metadata(new_file)?.len() {
+                        let patch = format!("patches/{}.patch", index);
+                        tar.append_path_with_name(&patch_path, &patch)?;
+                        DeltaOp::Patch { base_sha256, patch }
+                    } else {
+                        let data = format!("files/{}", path);
+                        tar.append_path_with_name(new_file, &data)?;
+                        DeltaOp::Add { data }
+                    }
+                }
+            }
+            None => {
+                let data = format!("files/{}", path);
+                tar.append_path_with_name(new_file, &data)?;
+                DeltaOp::Add { data }
+            }
+        };
+        files.push(DeltaFile {
+            path: path.clone(),
+            sha256,
+            op,
+        });
+    }
+    for path in old_files.keys().filter(|p| !new_files.contains_key(*p)) {
+        files.push(DeltaFile {
+            path: path.clone(),
+            sha256: String::new(),
+            op: DeltaOp::Remove,
+        });
+    }
+
+    let delta = EngineDelta {
+        from_version: from_version.trim_start_matches(['v', 'V']).to_string(),
+        to_version: to_version.trim_start_matches(['v', 'V']).to_string(),
+        files,
+    };
+    let manifest_path = work_dir.join(DELTA_MANIFEST);
+    std::fs::write(&manifest_path, serde_json::to_string_pretty(&delta)?)?;
+    tar.append_path_with_name(&manifest_path, DELTA_MANIFEST)?;
+    tar.into_inner()?;
+
+    let _ = std::fs::remove_dir_all(&work_dir);
+    Ok(delta)
+}
+
+/// Build `dest_dir` from a copy of `base_dir` plus the unpacked delta in
+/// `delta_dir`, verifying every resulting file. `dest_dir` is removed again
+/// if anything fails, so the caller can fall back to a full install.
+pub fn apply_engine_delta(base_dir: &Path, delta_dir: &Path, dest_dir: &Path) -> Result<EngineDelta> {
+    let manifest_path = delta_dir.join(DELTA_MANIFEST);
+    let delta: EngineDelta = serde_json::from_str(
+        &std::fs::read_to_string(&manifest_path)
+            .with_context(|| format!("reading {}", manifest_path.display()))?,
+    )
+    .context("parsing engine delta manifest")?;
+
+    anyhow::ensure!(
+        !dest_dir.exists(),
+        "{} already exists",
+        dest_dir.display()
+    );
+    let result = clone_and_patch(base_dir, delta_dir, dest_dir, &delta);
+    if result.is_err() {
+        let _ = std::fs::remove_dir_all(dest_dir);
+    }
+    result.map(|()| delta)
+}
+
+fn clone_and_patch(base_dir: &Path, delta_dir: &Path, dest_dir: &Path, delta: &EngineDelta) -> Result<()> {
+    for (path, file) in list_files(base_dir)? {
+        let target = safe_join(dest_dir, &path)?;
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(&file, &target).with_context(|| format!("copying {}", file.display()))?;
+    }
+
+    for file in &delta.files {
+        let target = safe_join(dest_dir, &file.path)?;
+        match &file.op {
+            DeltaOp::Keep => {}
+            DeltaOp::Patch { base_sha256, patch } => {
+                let actual = sha256_file(&target)
+                    .with_context(|| format!("{} is missing from the installed version", file.path))?;
+                anyhow::ensure!(
+                    actual == *base_sha256,
+                    "{} differs from the version the delta was built against",
+                    file.path
+                );
+                let patched = target.with_extension("delta-patched");
+                apply_patch(&target, &safe_join(delta_dir, patch)?, &patched)
+                    .with_context(|| format!("patching {}", file.path))?;
+                std::fs::set_permissions(&patched, std::fs::metadata(&target)?.permissions())?;
+                std::fs::rename(&patched, &target)?;
+            }
+            DeltaOp::Add { data } => {
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::copy(safe_join(delta_dir, data)?, &target)
+                    .with_context(|| format!("adding {}", file.path))?;
+            }
+            DeltaOp::Remove => {
+                let _ = std::fs::remove_file(&target);
+                continue;
+            }
+        }
+
+        let actual = sha256_file(&target)
+            .with_context(|| format!("{} is missing after applying the delta", file.path))?;
+        anyhow::ensure!(
+            actual == file.sha256,
+            "SHA256 mismatch for {}: expected {}, got {}",
+            file.path,
+            file.sha256,
+            actual
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn delta_rebuilds_new_install_from_old() {
+        let dir = std::env::temp_dir().join("pulsar_engine_delta_test");
+        let _ = fs::remove_dir_all(&dir);
+        let (old, new) = (dir.join("old"), dir.join("new"));
+        fs::create_dir_all(old.join("assets")).unwrap();
+        fs::create_dir_all(new.join("assets")).unwrap();
+
+        let binary: Vec<u8> = (0..20_000).map(|i| (i % 253) as u8).collect();
+        let mut binary_v2 = binary.clone();
+        binary_v2[777] = 1;
+        fs::write(old.join("pulsar"), &binary).unwrap();
+        fs::write(new.join("pulsar"), &binary_v2).unwrap();
+        fs::write(old.join("assets/logo.png"), b"logo").unwrap();
+        fs::write(new.join("assets/logo.png"), b"logo").unwrap();
+        fs::write(old.join("assets/old.txt"), b"gone").unwrap();
+        fs::write(new.join("assets/new.txt"), b"added").unwrap();
+        fs::write(old.join(".pulsar-install.json"), b"{}").unwrap();
+        fs::create_dir_all(old.join(".pulsar-modules/physics")).unwrap();
+        fs::write(old.join(".pulsar-modules/physics/lib.so"), b"module").unwrap();
+
+        let tar_path = dir.join(delta_asset_name("pulsar-linux-x86_64.tar.gz", "v0.1.22"));
+        assert!(tar_path.ends_with("pulsar-linux-x86_64.tar.gz.from-0.1.22.delta.tar"));
+        let delta = generate_engine_delta(&old, &new, "v0.1.22", "v0.1.23", &tar_path).unwrap();
+        let ops: BTreeMap<_, _> = delta.files.iter().map(|f| (f.path.as_str(), &f.op)).collect();
+        assert_eq!(ops["assets/logo.png"], &DeltaOp::Keep);
+        assert!(matches!(ops["pulsar"], DeltaOp::Patch { .. }));
+        assert!(matches!(ops["assets/new.txt"], DeltaOp::Add { .. }));
+        assert_eq!(ops["assets/old.txt"], &DeltaOp::Remove);
+
+        let unpacked = dir.join("unpacked");
+        tar::Archive::new(fs::File::open(&tar_path).unwrap()).unpack(&unpacked).unwrap();
+        let dest = dir.join("dest");
+        apply_engine_delta(&old, &unpacked, &dest).unwrap();
+        assert_eq!(fs::read(dest.join("pulsar")).unwrap(), binary_v2);
+        assert_eq!(fs::read(dest.join("assets/new.txt")).unwrap(), b"added");
+        assert!(!dest.join("assets/old.txt").exists());
+        assert!(!dest.join(".pulsar-install.json").exists());
+        assert!(!dest.join(".pulsar-modules").exists());
+
+        // A modified base install is rejected and leaves nothing behind.
+        fs::write(old.join("assets/logo.png"), b"edited").unwrap();
+        let dest = dir.join("dest2");
+        assert!(apply_engine_delta(&old, &unpacked, &dest).is_err());
+        assert!(!dest.exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
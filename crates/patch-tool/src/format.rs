@@ -0,0 +1,248 @@
+//! On-disk patch formats.
+//!
+//! * **Legacy** patches are a zstd-compressed bsdiff of the whole binary with
+//!   no header. Applying one needs the old binary, the new binary and the
+//!   decompressed patch in memory at once.
+//! * **Windowed** patches start with [`MAGIC`] and a format version byte,
+//!   followed by a zstd stream of independent bsdiff chunks. Each chunk
+//!   produces at most `window_size` bytes of the new binary from a bounded
+//!   slice of the old one, so generating and applying run in memory
+//!   proportional to the window rather than the binary.
+//!
+//! Legacy patches begin with the zstd frame magic, which can never match
+//! [`MAGIC`], so the format is detected from the first bytes of the file.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Leading bytes of every patch with a versioned header.
+pub const MAGIC: &[u8; 8] = b"PLSRPTCH";
+
+/// Header version byte of windowed patches.
+pub const FORMAT_WINDOWED: u8 = 2;
+
+/// Bytes of the new binary produced per chunk. The matching slice of the old
+/// binary extends half a window either side, so bsdiff's suffix array stays
+/// around 16x this size while generating.
+pub const DEFAULT_WINDOW_SIZE: u64 = 8 * 1024 * 1024;
+
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Patch layout written by `generate-patch` and `build-manifest`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum PatchFormat {
+    /// Whole-file bsdiff, readable by installers that predate windowed patches.
+    Legacy,
+    /// Chunked bsdiff with a versioned header; applies in bounded memory.
+    #[default]
+    Windowed,
+}
+
+/// Identify the format of the patch at `path` from its first bytes.
+pub fn detect(path: &Path) -> Result<PatchFormat> {
+    let mut head = [0u8; 9];
+    let mut file = File::open(path).with_context(|| format!("opening patch: {}", path.display()))?;
+    let len = read_up_to(&mut file, &mut head)?;
+    if len >= 9 && head[..8] == MAGIC[..] {
+        return match head[8] {
+            FORMAT_WINDOWED => Ok(PatchFormat::Windowed),
+            v => anyhow::bail!("unsupported patch format version {} in {}", v, path.display()),
+        };
+    }
+    if len >= 4 && head[..4] == ZSTD_MAGIC {
+        return Ok(PatchFormat::Legacy);
+    }
+    anyhow::bail!("{} is not a recognised patch file", path.display())
+}
+
+fn read_up_to(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+struct Header {
+    window_size: u64,
+    old_len: u64,
+    new_len: u64,
+}
+
+/// One chunk record: which slice of the old binary the bsdiff patch that
+/// follows it applies to, and how many new bytes it produces.
+struct Chunk {
+    old_offset: u64,
+    old_len: u64,
+    new_len: u64,
+    patch_len: u64,
+}
+
+fn write_u64(w: &mut impl Write, v: u64) -> std::io::Result<()> {
+    w.write_all(&v.to_le_bytes())
+}
+
+fn read_u64(r: &mut impl Read) -> std::io::Result<u64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_slice(file: &mut File, offset: u64, len: u64, buf: &mut Vec<u8>) -> Result<()> {
+    buf.resize(len as usize, 0);
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(buf)?;
+    Ok(())
+}
+
+/// Old-binary slice used as the diff base for new bytes `start..start+len`:
+/// the proportionally equivalent position, widened by half a window.
+fn old_window(start: u64, len: u64, window_size: u64, old_len: u64, new_len: u64) -> (u64, u64) {
+    let center = if new_len == 0 {
+        0
+    } else {
+        (start as u128 * old_len as u128 / new_len as u128) as u64
+    };
+    let margin = window_size / 2;
+    let begin = center.saturating_sub(margin).min(old_len);
+    let end = center.saturating_add(len).saturating_add(margin).min(old_len);
+    (begin, end - begin)
+}
+
+/// Write a windowed patch turning `old_path` into `new_path`.
+pub fn generate_windowed(old_path: &Path, new_path: &Path, patch_path: &Path, window_size: u64) -> Result<()> {
+    anyhow::ensure!(window_size > 0, "window size must be positive");
+    let mut old = File::open(old_path)
+        .with_context(|| format!("reading old binary: {}", old_path.display()))?;
+    let mut new = File::open(new_path)
+        .with_context(|| format!("reading new binary: {}", new_path.display()))?;
+    let header = Header {
+        window_size,
+        old_len: old.metadata()?.len(),
+        new_len: new.metadata()?.len(),
+    };
+
+    let mut out = BufWriter::new(
+        File::create(patch_path).with_context(|| format!("writing patch: {}", patch_path.display()))?,
+    );
+    out.write_all(MAGIC)?;
+    out.write_all(&[FORMAT_WINDOWED])?;
+    write_u64(&mut out, header.window_size)?;
+    write_u64(&mut out, header.old_len)?;
+    write_u64(&mut out, header.new_len)?;
+
+    let mut encoder = zstd::stream::write::Encoder::new(out, 19).context("zstd compression failed")?;
+    let (mut old_buf, mut new_buf, mut chunk_patch) = (Vec::new(), Vec::new(), Vec::new());
+    let mut start = 0;
+    while start < header.new_len {
+        let len = window_size.min(header.new_len - start);
+        let (old_offset, old_len) = old_window(start, len, window_size, header.old_len, header.new_len);
+        read_slice(&mut old, old_offset, old_len, &mut old_buf).context("reading old binary")?;
+        read_slice(&mut new, start, len, &mut new_buf).context("reading new binary")?;
+
+        chunk_patch.clear();
+        bsdiff::diff(&old_buf, &new_buf, &mut chunk_patch).context("bsdiff::diff failed")?;
+
+        write_u64(&mut encoder, old_offset)?;
+        write_u64(&mut encoder, old_len)?;
+        write_u64(&mut encoder, len)?;
+        write_u64(&mut encoder, chunk_patch.len() as u64)?;
+        encoder.write_all(&chunk_patch)?;
+        start += len;
+    }
+    encoder
+        .finish()
+        .context("zstd compression failed")?
+        .flush()
+        .with_context(|| format!("writing patch: {}", patch_path.display()))?;
+    Ok(())
+}
+
+/// Apply a windowed patch, holding at most one chunk in memory.
+pub fn apply_windowed(old_path: &Path, patch_path: &Path, new_path: &Path) -> Result<()> {
+    let mut patch = BufReader::new(
+        File::open(patch_path).with_context(|| format!("reading patch: {}", patch_path.display()))?,
+    );
+    let mut magic = [0u8; 9];
+    patch.read_exact(&mut magic).context("truncated patch header")?;
+    anyhow::ensure!(
+        magic[..8] == MAGIC[..] && magic[8] == FORMAT_WINDOWED,
+        "{} is not a windowed patch",
+        patch_path.display()
+    );
+    let header = Header {
+        window_size: read_u64(&mut patch).context("truncated patch header")?,
+        old_len: read_u64(&mut patch).context("truncated patch header")?,
+        new_len: read_u64(&mut patch).context("truncated patch header")?,
+    };
+
+    let mut old = File::open(old_path)
+        .with_context(|| format!("reading old binary: {}", old_path.display()))?;
+    let actual_old_len = old.metadata()?.len();
+    anyhow::ensure!(
+        actual_old_len == header.old_len,
+        "patch expects a {} byte base binary, {} is {} bytes",
+        header.old_len,
+        old_path.display(),
+        actual_old_len
+    );
+
+    let mut decoder = zstd::stream::read::Decoder::with_buffer(patch).context("zstd decompression failed")?;
+    let mut out = BufWriter::new(
+        File::create(new_path).with_context(|| format!("writing result: {}", new_path.display()))?,
+    );
+    let (mut old_buf, mut new_buf) = (Vec::new(), Vec::new());
+    let mut written = 0;
+    while written < header.new_len {
+        let chunk = Chunk {
+            old_offset: read_u64(&mut decoder).context("truncated patch")?,
+            old_len: read_u64(&mut decoder).context("truncated patch")?,
+            new_len: read_u64(&mut decoder).context("truncated patch")?,
+            patch_len: read_u64(&mut decoder).context("truncated patch")?,
+        };
+        // Refuse chunks that would break the memory bound, even though the
+        // patch hash has normally been checked by now.
+        anyhow::ensure!(
+            chunk.new_len > 0
+                && chunk.new_len <= header.window_size
+                && header
+                    .window_size
+                    .checked_mul(2)
+                    .is_some_and(|max_old_len| chunk.old_len <= max_old_len)
+                && chunk.old_offset.saturating_add(chunk.old_len) <= header.old_len,
+            "corrupt patch chunk at output offset {}",
+            written
+        );
+
+        read_slice(&mut old, chunk.old_offset, chunk.old_len, &mut old_buf).context("reading base binary")?;
+
+        // bsdiff reads controls until EOF, so bound it to this chunk's bytes.
+        let mut chunk_patch = (&mut decoder).take(chunk.patch_len);
+        new_buf.clear();
+        bsdiff::patch(&old_buf, &mut chunk_patch, &mut new_buf).context("bsdiff::patch failed")?;
+        anyhow::ensure!(
+            chunk_patch.limit() == 0,
+            "truncated patch chunk at output offset {}",
+            written
+        );
+        anyhow::ensure!(
+            new_buf.len() as u64 == chunk.new_len,
+            "patch chunk at output offset {} produced {} bytes, expected {}",
+            written,
+            new_buf.len(),
+            chunk.new_len
+        );
+        out.write_all(&new_buf)
+            .with_context(|| format!("writing result: {}", new_path.display()))?;
+        written += chunk.new_len;
+    }
+    out.flush()
+        .with_context(|| format!("writing result: {}", new_path.display()))?;
+    Ok(())
+}
@@ -1,16 +1,47 @@
+pub mod engine_delta;
+pub mod format;
+pub mod manifest;
+pub mod signing;
+
 use std::path::Path;
 
 use anyhow::{Context, Result};
 use sha2::{Digest, Sha256};
 
+pub use format::PatchFormat;
+
 pub fn sha256_file(path: &Path) -> Result<String> {
-    let bytes = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    let mut file = std::fs::File::open(path).with_context(|| format!("reading {}", path.display()))?;
     let mut hasher = Sha256::new();
-    hasher.update(&bytes);
+    std::io::copy(&mut file, &mut hasher).with_context(|| format!("reading {}", path.display()))?;
     Ok(hex::encode(hasher.finalize()))
 }
 
+/// Write a patch turning `old_path` into `new_path` in the default
+/// (windowed) format and return its SHA256.
 pub fn generate_patch(old_path: &Path, new_path: &Path, patch_path: &Path) -> Result<String> {
+    generate_patch_as(old_path, new_path, patch_path, PatchFormat::default())
+}
+
+pub fn generate_patch_as(
+    old_path: &Path,
+    new_path: &Path,
+    patch_path: &Path,
+    format: PatchFormat,
+) -> Result<String> {
+    match format {
+        PatchFormat::Legacy => generate_legacy_patch(old_path, new_path, patch_path)?,
+        PatchFormat::Windowed => format::generate_windowed(
+            old_path,
+            new_path,
+            patch_path,
+            format::DEFAULT_WINDOW_SIZE,
+        )?,
+    }
+    sha256_file(patch_path)
+}
+
+fn generate_legacy_patch(old_path: &Path, new_path: &Path, patch_path: &Path) -> Result<()> {
     let old = std::fs::read(old_path)
         .with_context(|| format!("reading old binary: {}", old_path.display()))?;
     let new = std::fs::read(new_path)
@@ -24,13 +55,18 @@ pub fn generate_patch(old_path: &Path, new_path: &Path, patch_path: &Path) -> Re
         .context("zstd compression failed")?;
 
     std::fs::write(patch_path, &compressed)
-        .with_context(|| format!("writing patch: {}", patch_path.display()))?;
-
-    let hash = sha256_file(patch_path)?;
-    Ok(hash)
+        .with_context(|| format!("writing patch: {}", patch_path.display()))
 }
 
+/// Apply a patch in either format, detected from its header.
 pub fn apply_patch(old_path: &Path, patch_path: &Path, new_path: &Path) -> Result<()> {
+    match format::detect(patch_path)? {
+        PatchFormat::Legacy => apply_legacy_patch(old_path, patch_path, new_path),
+        PatchFormat::Windowed => format::apply_windowed(old_path, patch_path, new_path),
+    }
+}
+
+fn apply_legacy_patch(old_path: &Path, patch_path: &Path, new_path: &Path) -> Result<()> {
     let old = std::fs::read(old_path)
         .with_context(|| format!("reading old binary: {}", old_path.display()))?;
     let compressed = std::fs::read(patch_path)
@@ -84,4 +120,37 @@ mod tests {
 
         let _ = fs::remove_dir_all(&dir);
     }
+
+    #[test]
+    fn detects_format_and_applies_multi_window_patches() {
+        let dir = std::env::temp_dir().join("pulsar_patch_format_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let old_path = dir.join("old.bin");
+        let new_path = dir.join("new.bin");
+        let old_data: Vec<u8> = (0..50_000).map(|i| (i % 251) as u8).collect();
+        let mut new_data = old_data.clone();
+        new_data[1234] = 7;
+        new_data.splice(20_000..20_000, [9u8; 300]);
+        new_data.truncate(45_000);
+        fs::write(&old_path, &old_data).unwrap();
+        fs::write(&new_path, &new_data).unwrap();
+
+        let windowed = dir.join("windowed.patch");
+        format::generate_windowed(&old_path, &new_path, &windowed, 4096).unwrap();
+        assert_eq!(format::detect(&windowed).unwrap(), PatchFormat::Windowed);
+
+        let legacy = dir.join("legacy.patch");
+        generate_patch_as(&old_path, &new_path, &legacy, PatchFormat::Legacy).unwrap();
+        assert_eq!(format::detect(&legacy).unwrap(), PatchFormat::Legacy);
+
+        for patch in [&windowed, &legacy] {
+            let result = dir.join("result.bin");
+            apply_patch(&old_path, patch, &result).unwrap();
+            assert_eq!(fs::read(&result).unwrap(), new_data);
+        }
+
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
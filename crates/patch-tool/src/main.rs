@@ -20,8 +20,11 @@ enum Commands {
         new_binary: PathBuf,
         #[arg(long)]
         output: PathBuf,
+        /// Patch layout; `legacy` is readable by installers older than 0.1.8
+        #[arg(long, value_enum, default_value_t)]
+        format: pulsar_patch_tool::PatchFormat,
     },
-    /// Apply a bsdiff patch to a binary
+    /// Apply a bsdiff patch to a binary (either format, detected automatically)
     ApplyPatch {
         #[arg(long)]
         binary: PathBuf,
@@ -35,6 +38,72 @@ enum Commands {
         #[arg(long)]
         file: PathBuf,
     },
+    /// Generate patches from previous releases and write a complete update manifest
+    BuildManifest {
+        /// Version being released
+        #[arg(long)]
+        version: String,
+        /// Directory containing this release's per-target binaries
+        #[arg(long)]
+        release_dir: PathBuf,
+        /// Directory with one subdirectory of binaries per previous version
+        #[arg(long)]
+        previous_dir: Option<PathBuf>,
+        /// Number of most recent previous versions to generate patches from
+        #[arg(long, default_value_t = 3)]
+        previous_count: usize,
+        /// Asset URL template; `{version}` and `{asset}` are substituted
+        #[arg(long, default_value = pulsar_patch_tool::manifest::DEFAULT_URL_TEMPLATE)]
+        url_template: String,
+        /// Directory patches and `update-manifest.json` are written to
+        #[arg(long)]
+        output_dir: PathBuf,
+        /// Markdown file with release notes to embed in the manifest
+        #[arg(long)]
+        release_notes: Option<PathBuf>,
+        /// Write windowed patches only from this version on; patches from
+        /// older versions use the legacy format their installers understand
+        #[arg(long)]
+        windowed_from: Option<String>,
+    },
+    /// Generate a per-file delta between two engine install directories
+    EngineDelta {
+        /// Install directory of the previous engine version
+        #[arg(long)]
+        old_dir: PathBuf,
+        /// Install directory of the new engine version
+        #[arg(long)]
+        new_dir: PathBuf,
+        #[arg(long)]
+        from_version: String,
+        #[arg(long)]
+        to_version: String,
+        /// Name of the new version's full archive asset for this platform;
+        /// the delta is written next to it as `<asset>.from-<version>.delta.tar`
+        #[arg(long)]
+        full_asset: String,
+        #[arg(long)]
+        output_dir: PathBuf,
+    },
+    /// Generate an ed25519 keypair for signing update manifests
+    Keygen {
+        /// Where to write the hex-encoded secret key
+        #[arg(long)]
+        secret_key: PathBuf,
+        /// Where to write the hex-encoded public key
+        #[arg(long)]
+        public_key: PathBuf,
+    },
+    /// Sign an update manifest with an ed25519 secret key
+    SignManifest {
+        #[arg(long)]
+        manifest: PathBuf,
+        #[arg(long)]
+        secret_key: PathBuf,
+        /// Defaults to overwriting the input manifest
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
 }
 
 fn main() -> Result<()> {
@@ -45,8 +114,10 @@ fn main() -> Result<()> {
             old_binary,
             new_binary,
             output,
+            format,
         } => {
-            let hash = pulsar_patch_tool::generate_patch(&old_binary, &new_binary, &output)?;
+            let hash =
+                pulsar_patch_tool::generate_patch_as(&old_binary, &new_binary, &output, format)?;
             println!("Patch created: {}", output.display());
             println!("SHA256: {}", hash);
         }
@@ -62,6 +133,83 @@ fn main() -> Result<()> {
             let hash = pulsar_patch_tool::sha256_file(&file)?;
             println!("{}", hash);
         }
+        Commands::BuildManifest {
+            version,
+            release_dir,
+            previous_dir,
+            previous_count,
+            url_template,
+            output_dir,
+            release_notes,
+            windowed_from,
+        } => {
+            let mut manifest = pulsar_patch_tool::manifest::build_manifest(
+                &pulsar_patch_tool::manifest::BuildManifestOptions {
+                    version: &version,
+                    release_dir: &release_dir,
+                    previous_dir: previous_dir.as_deref(),
+                    previous_count,
+                    url_template: &url_template,
+                    output_dir: &output_dir,
+                    windowed_from: windowed_from.as_deref(),
+                },
+            )?;
+            if let Some(path) = release_notes {
+                manifest.release_notes = std::fs::read_to_string(&path)?;
+            }
+            let manifest_path = output_dir.join("update-manifest.json");
+            std::fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+            println!("Manifest written: {}", manifest_path.display());
+        }
+        Commands::EngineDelta {
+            old_dir,
+            new_dir,
+            from_version,
+            to_version,
+            full_asset,
+            output_dir,
+        } => {
+            use pulsar_patch_tool::engine_delta;
+
+            std::fs::create_dir_all(&output_dir)?;
+            let output = output_dir.join(engine_delta::delta_asset_name(&full_asset, &from_version));
+            let delta = engine_delta::generate_engine_delta(
+                &old_dir,
+                &new_dir,
+                &from_version,
+                &to_version,
+                &output,
+            )?;
+            let changed = delta
+                .files
+                .iter()
+                .filter(|f| f.op != engine_delta::DeltaOp::Keep)
+                .count();
+            println!("Delta written: {}", output.display());
+            println!("{} of {} files changed", changed, delta.files.len());
+        }
+        Commands::Keygen {
+            secret_key,
+            public_key,
+        } => {
+            let (secret_hex, public_hex) = pulsar_patch_tool::signing::generate_keypair();
+            std::fs::write(&secret_key, &secret_hex)?;
+            std::fs::write(&public_key, &public_hex)?;
+            println!("Secret key written: {}", secret_key.display());
+            println!("Public key written: {}", public_key.display());
+            println!("Public key: {}", public_hex);
+        }
+        Commands::SignManifest {
+            manifest,
+            secret_key,
+            output,
+        } => {
+            let output = output.unwrap_or_else(|| manifest.clone());
+            let signature =
+                pulsar_patch_tool::signing::sign_manifest(&manifest, &secret_key, &output)?;
+            println!("Signed manifest written: {}", output.display());
+            println!("Signature: {}", signature);
+        }
     }
 
     Ok(())
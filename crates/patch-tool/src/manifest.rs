@@ -0,0 +1,268 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{PatchFormat, generate_patch_as, sha256_file};
+
+/// `update-manifest.json` as read by `pulsar-installer`'s
+/// `updater::manifest::UpdateManifest`. Platforms are kept in a `BTreeMap` so
+/// generated manifests are stable across runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateManifest {
+    pub schema_version: u32,
+    pub latest_version: String,
+    pub generated_at: String,
+    pub platforms: BTreeMap<String, PlatformUpdateInfo>,
+    /// Markdown release notes shown in the hub's update banner.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub release_notes: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformUpdateInfo {
+    pub full_asset_name: String,
+    pub full_url: String,
+    pub full_sha256: String,
+    pub full_size_bytes: u64,
+    pub patches: Vec<PatchInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchInfo {
+    pub from_version: String,
+    pub to_version: String,
+    pub asset_name: String,
+    pub url: String,
+    pub sha256: String,
+    pub size_bytes: u64,
+}
+
+/// Release targets and the asset name each one is published under. Mirrors
+/// `platform_asset_suffix` in the installer and the release workflow matrix.
+pub const RELEASE_TARGETS: &[(&str, &str)] = &[
+    ("x86_64-pc-windows-msvc", "pulsar-installer-windows-x86_64.exe"),
+    ("i686-pc-windows-msvc", "pulsar-installer-windows-i686.exe"),
+    ("aarch64-pc-windows-msvc", "pulsar-installer-windows-arm64.exe"),
+    ("aarch64-apple-darwin", "pulsar-installer-macos-arm64"),
+    ("x86_64-apple-darwin", "pulsar-installer-macos-x86_64"),
+    ("x86_64-unknown-linux-gnu", "pulsar-installer-linux-x86_64"),
+    ("aarch64-unknown-linux-gnu", "pulsar-installer-linux-arm64"),
+    ("i686-unknown-linux-gnu", "pulsar-installer-linux-i686"),
+    ("armv7-unknown-linux-gnueabihf", "pulsar-installer-linux-armv7"),
+];
+
+/// Default asset URL template, pointing at GitHub release downloads.
+pub const DEFAULT_URL_TEMPLATE: &str =
+    "https://github.com/Far-Beyond-Pulsar/Pulsar-Installer/releases/download/v{version}/{asset}";
+
+pub struct BuildManifestOptions<'a> {
+    /// Version being released.
+    pub version: &'a str,
+    /// Directory holding this release's per-target binaries.
+    pub release_dir: &'a Path,
+    /// Directory with one subdirectory of binaries per previous version
+    /// (e.g. `previous/0.1.5/pulsar-installer-linux-x86_64`).
+    pub previous_dir: Option<&'a Path>,
+    /// How many of the newest previous versions to generate patches from.
+    pub previous_count: usize,
+    /// Asset URL template; `{version}` and `{asset}` are substituted.
+    pub url_template: &'a str,
+    /// Where generated patches are written.
+    pub output_dir: &'a Path,
+    /// Oldest version whose installer can apply windowed patches. Patches
+    /// from older versions are written in the legacy whole-file format,
+    /// since the running (old) installer is the one applying them.
+    pub windowed_from: Option<&'a str>,
+}
+
+/// Generate every `previous -> version` patch for each target found in
+/// `release_dir` and assemble the resulting manifest.
+pub fn build_manifest(options: &BuildManifestOptions) -> Result<UpdateManifest> {
+    std::fs::create_dir_all(options.output_dir)
+        .with_context(|| format!("creating {}", options.output_dir.display()))?;
+
+    let windowed_from = options
+        .windowed_from
+        .map(|v| parse_version(v).with_context(|| format!("'{}' is not valid semver", v)))
+        .transpose()?;
+    let previous = match options.previous_dir {
+        Some(dir) => previous_versions(dir, options.version, options.previous_count)?,
+        None => Vec::new(),
+    };
+
+    let mut platforms = BTreeMap::new();
+    for (triple, asset) in RELEASE_TARGETS {
+        let current = options.release_dir.join(asset);
+        if !current.is_file() {
+            println!("Skipping {}: {} not found", triple, current.display());
+            continue;
+        }
+
+        let mut patches = Vec::new();
+        for (from_version, from_dir) in &previous {
+            let old = from_dir.join(asset);
+            if !old.is_file() {
+                println!("No {} binary for v{}, skipping patch", triple, from_version);
+                continue;
+            }
+
+            let patch_asset = format!(
+                "pulsar-installer-patch-{}-{}-{}.zst",
+                from_version, options.version, asset
+            );
+            let patch_path = options.output_dir.join(&patch_asset);
+            let format = match (&windowed_from, parse_version(from_version)) {
+                (Some(min), Some(from)) if from < *min => PatchFormat::Legacy,
+                _ => PatchFormat::Windowed,
+            };
+            println!(
+                "Generating {:?} patch: {} v{} -> v{}",
+                format, triple, from_version, options.version
+            );
+            let sha256 = generate_patch_as(&old, &current, &patch_path, format)?;
+
+            patches.push(PatchInfo {
+                from_version: from_version.clone(),
+                to_version: options.version.to_string(),
+                url: asset_url(options.url_template, options.version, &patch_asset),
+                asset_name: patch_asset,
+                sha256,
+                size_bytes: file_size(&patch_path)?,
+            });
+        }
+
+        platforms.insert(
+            triple.to_string(),
+            PlatformUpdateInfo {
+                full_asset_name: asset.to_string(),
+                full_url: asset_url(options.url_template, options.version, asset),
+                full_sha256: sha256_file(&current)?,
+                full_size_bytes: file_size(&current)?,
+                patches,
+            },
+        );
+    }
+
+    if platforms.is_empty() {
+        anyhow::bail!(
+            "no release binaries found in {}",
+            options.release_dir.display()
+        );
+    }
+
+    Ok(UpdateManifest {
+        schema_version: 1,
+        latest_version: options.version.to_string(),
+        generated_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        platforms,
+        release_notes: String::new(),
+    })
+}
+
+/// The newest `count` version subdirectories of `dir` that are older than
+/// `current`, newest first.
+fn previous_versions(dir: &Path, current: &str, count: usize) -> Result<Vec<(String, PathBuf)>> {
+    let current = parse_version(current)
+        .with_context(|| format!("release version '{}' is not valid semver", current))?;
+
+    let mut versions = Vec::new();
+    for entry in std::fs::read_dir(dir).with_context(|| format!("reading {}", dir.display()))? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Some(version) = parse_version(&name) else {
+            println!("Ignoring {}: not a version directory", entry.path().display());
+            continue;
+        };
+        if version < current {
+            versions.push((version, entry.path()));
+        }
+    }
+
+    versions.sort_by(|a, b| b.0.cmp(&a.0));
+    versions.truncate(count);
+    Ok(versions
+        .into_iter()
+        .map(|(version, path)| (version.to_string(), path))
+        .collect())
+}
+
+fn parse_version(s: &str) -> Option<semver::Version> {
+    semver::Version::parse(s.trim().trim_start_matches(['v', 'V'])).ok()
+}
+
+fn asset_url(template: &str, version: &str, asset: &str) -> String {
+    template
+        .replace("{version}", version)
+        .replace("{asset}", asset)
+}
+
+fn file_size(path: &Path) -> Result<u64> {
+    Ok(std::fs::metadata(path)
+        .with_context(|| format!("reading metadata of {}", path.display()))?
+        .len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn builds_patches_for_recent_versions() {
+        let dir = std::env::temp_dir().join("pulsar_patch_manifest_test");
+        let _ = fs::remove_dir_all(&dir);
+        let asset = "pulsar-installer-linux-x86_64";
+
+        let binary = |version: u8| -> Vec<u8> {
+            let mut data: Vec<u8> = (0..4096).map(|i| (i % 251) as u8).collect();
+            data[100] = version;
+            data
+        };
+        for (name, version) in [("0.1.4", 4), ("0.1.5", 5), ("v0.1.6", 6)] {
+            let d = dir.join("previous").join(name);
+            fs::create_dir_all(&d).unwrap();
+            fs::write(d.join(asset), binary(version)).unwrap();
+        }
+        let release_dir = dir.join("release");
+        fs::create_dir_all(&release_dir).unwrap();
+        fs::write(release_dir.join(asset), binary(7)).unwrap();
+
+        let previous_dir = dir.join("previous");
+        let output_dir = dir.join("out");
+        let manifest = build_manifest(&BuildManifestOptions {
+            version: "0.1.7",
+            release_dir: &release_dir,
+            previous_dir: Some(&previous_dir),
+            previous_count: 2,
+            url_template: "https://mirror.example/{version}/{asset}",
+            output_dir: &output_dir,
+            windowed_from: Some("0.1.6"),
+        })
+        .unwrap();
+
+        assert_eq!(manifest.latest_version, "0.1.7");
+        let platform = &manifest.platforms["x86_64-unknown-linux-gnu"];
+        assert_eq!(platform.full_url, format!("https://mirror.example/0.1.7/{}", asset));
+        assert_eq!(platform.full_size_bytes, 4096);
+
+        let from: Vec<_> = platform.patches.iter().map(|p| p.from_version.as_str()).collect();
+        assert_eq!(from, ["0.1.6", "0.1.5"]);
+        let formats: Vec<_> = platform
+            .patches
+            .iter()
+            .map(|p| crate::format::detect(&output_dir.join(&p.asset_name)).unwrap())
+            .collect();
+        assert_eq!(formats, [PatchFormat::Windowed, PatchFormat::Legacy]);
+        for patch in &platform.patches {
+            assert_eq!(patch.to_version, "0.1.7");
+            assert_eq!(sha256_file(&output_dir.join(&patch.asset_name)).unwrap(), patch.sha256);
+        }
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
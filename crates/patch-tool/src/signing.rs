@@ -0,0 +1,132 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand_core::OsRng;
+
+/// Top-level manifest field holding the hex-encoded ed25519 signature.
+pub const SIGNATURE_FIELD: &str = "signature";
+
+/// Generate a new ed25519 keypair, returned as `(secret_hex, public_hex)`.
+pub fn generate_keypair() -> (String, String) {
+    let signing_key = SigningKey::generate(&mut OsRng);
+    (
+        hex::encode(signing_key.to_bytes()),
+        hex::encode(signing_key.verifying_key().to_bytes()),
+    )
+}
+
+/// The exact bytes a manifest signature covers: the manifest with its
+/// `signature` field removed, re-serialized as compact JSON.
+///
+/// The installer computes the same payload from the downloaded text, so both
+/// sides agree regardless of the whitespace the manifest was written with.
+pub fn signing_payload(manifest: &serde_json::Value) -> Result<Vec<u8>> {
+    let mut unsigned = manifest.clone();
+    unsigned
+        .as_object_mut()
+        .context("update manifest is not a JSON object")?
+        .remove(SIGNATURE_FIELD);
+    serde_json::to_vec(&unsigned).context("serializing manifest payload")
+}
+
+/// Sign `manifest_path` with the hex secret key stored in `secret_key_path`
+/// and write the signed manifest to `output_path`.
+pub fn sign_manifest(
+    manifest_path: &Path,
+    secret_key_path: &Path,
+    output_path: &Path,
+) -> Result<String> {
+    let signing_key = read_signing_key(secret_key_path)?;
+
+    let text = std::fs::read_to_string(manifest_path)
+        .with_context(|| format!("reading manifest: {}", manifest_path.display()))?;
+    let mut manifest: serde_json::Value =
+        serde_json::from_str(&text).context("parsing update manifest")?;
+
+    let payload = signing_payload(&manifest)?;
+    let signature = hex::encode(signing_key.sign(&payload).to_bytes());
+
+    manifest
+        .as_object_mut()
+        .context("update manifest is not a JSON object")?
+        .insert(SIGNATURE_FIELD.into(), signature.clone().into());
+
+    let signed = serde_json::to_string_pretty(&manifest).context("serializing signed manifest")?;
+    std::fs::write(output_path, signed)
+        .with_context(|| format!("writing signed manifest: {}", output_path.display()))?;
+
+    Ok(signature)
+}
+
+/// Check a signed manifest against a hex-encoded public key.
+pub fn verify_manifest(manifest_text: &str, public_key_hex: &str) -> Result<()> {
+    let manifest: serde_json::Value =
+        serde_json::from_str(manifest_text).context("parsing update manifest")?;
+    let signature_hex = manifest
+        .get(SIGNATURE_FIELD)
+        .and_then(|s| s.as_str())
+        .context("update manifest is not signed")?;
+
+    let key_bytes: [u8; 32] = hex::decode(public_key_hex.trim())
+        .context("public key is not valid hex")?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("public key must be 32 bytes"))?;
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes).context("invalid public key")?;
+
+    let signature_bytes: [u8; 64] = hex::decode(signature_hex)
+        .context("signature is not valid hex")?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("signature must be 64 bytes"))?;
+    let signature = Signature::from_bytes(&signature_bytes);
+
+    verifying_key
+        .verify(&signing_payload(&manifest)?, &signature)
+        .context("manifest signature does not match")
+}
+
+fn read_signing_key(path: &Path) -> Result<SigningKey> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("reading secret key: {}", path.display()))?;
+    let bytes: [u8; 32] = hex::decode(text.trim())
+        .context("secret key is not valid hex")?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("secret key must be 32 bytes"))?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn sign_and_verify() {
+        let dir = std::env::temp_dir().join("pulsar_patch_sign_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let (secret, public) = generate_keypair();
+        let key_path = dir.join("update-signing.key");
+        let manifest_path = dir.join("update-manifest.json");
+        let signed_path = dir.join("update-manifest.signed.json");
+        fs::write(&key_path, &secret).unwrap();
+        fs::write(
+            &manifest_path,
+            r#"{"schema_version": 1, "latest_version": "0.1.8", "generated_at": "", "platforms": {}}"#,
+        )
+        .unwrap();
+
+        sign_manifest(&manifest_path, &key_path, &signed_path).unwrap();
+        let signed = fs::read_to_string(&signed_path).unwrap();
+        verify_manifest(&signed, &public).unwrap();
+
+        let tampered = signed.replace("0.1.8", "0.1.9");
+        assert!(verify_manifest(&tampered, &public).is_err());
+
+        let (_, other_public) = generate_keypair();
+        assert!(verify_manifest(&signed, &other_public).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}